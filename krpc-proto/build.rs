@@ -1,3 +1,7 @@
 fn main() {
-    protobuf_but_worse::generate("krpc.proto").unwrap();
+    protobuf_but_worse::Config::new()
+        .field_style(protobuf_but_worse::FieldStyle::Default)
+        .serde(std::env::var_os("CARGO_FEATURE_SERDE").is_some())
+        .compile(&["krpc.proto"])
+        .unwrap();
 }
@@ -0,0 +1,6 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(
+    |data: &[u8]| krpc_proto_fuzz::roundtrip::<krpc_proto::Class>(data)
+);
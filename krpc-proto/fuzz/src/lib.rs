@@ -0,0 +1,17 @@
+use protobuf_but_worse::encoding::{Decode, Encode};
+
+/// Decodes `data` as `T`, and if it's valid, checks that re-encoding
+/// is stable. Bytes are compared instead of values, as floats can be NaN.
+pub fn roundtrip<T: Encode + Decode>(data: &[u8]) {
+    let message = match T::decode(data) {
+        Ok(message) => message,
+        Err(_) => return,
+    };
+    let mut encoded = vec![];
+    message.encode(&mut encoded).unwrap();
+    assert_eq!(message.size() as usize, encoded.len());
+    let decoded = T::decode(encoded.as_slice()).unwrap();
+    let mut reencoded = vec![];
+    decoded.encode(&mut reencoded).unwrap();
+    assert_eq!(encoded, reencoded);
+}
@@ -1,5 +1,50 @@
 include!(concat!(env!("OUT_DIR"), "/krpc.rs"));
 
+/// Source of `krpc.proto` the types were generated from
+pub const PROTO_SOURCE: &str = include_str!("../krpc.proto");
+
+/// Runtime descriptors of all messages, for dynamic decoding
+/// of payloads with `Pool::decode`
+pub fn descriptor_pool() -> &'static protobuf_but_worse::descriptor::Pool {
+    static POOL: std::sync::OnceLock<protobuf_but_worse::descriptor::Pool> =
+        std::sync::OnceLock::new();
+    // Already parsed successfully by build script
+    POOL.get_or_init(|| {
+        protobuf_but_worse::descriptor::Pool::from_source(PROTO_SOURCE)
+            .expect("Invalid krpc.proto")
+    })
+}
+
+/// Proto3 JSON mapping of a message, as understood by other kRPC clients
+#[cfg(feature = "json")]
+pub fn to_json<T: Encode + MessageName>(
+    message: &T,
+) -> Result<String, protobuf_but_worse::json::JsonError> {
+    protobuf_but_worse::json::to_string_pretty(descriptor_pool(), message)
+}
+
+/// Parses message from proto3 JSON mapping
+#[cfg(feature = "json")]
+pub fn from_json<T: Decode + MessageName>(
+    json: &str,
+) -> Result<T, protobuf_but_worse::json::JsonError> {
+    protobuf_but_worse::json::from_str(descriptor_pool(), json)
+}
+
+/// Text format of a message, for logs and debugging
+pub fn to_text<T: Encode + MessageName>(
+    message: &T,
+) -> Result<String, EncodingError> {
+    protobuf_but_worse::text::to_string(descriptor_pool(), message)
+}
+
+/// Parses message from text format
+pub fn from_text<T: Decode + MessageName>(
+    text: &str,
+) -> Result<T, protobuf_but_worse::text::TextError> {
+    protobuf_but_worse::text::from_str(descriptor_pool(), text)
+}
+
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self)
@@ -15,19 +60,17 @@ mod tests {
     #[test]
     fn encode() {
         let input = ConnectionRequest {
-            r#type: Some(connection_request::Type::Rpc),
-            client_name: Some("test".to_string()),
-            client_identifier: None,
+            r#type: connection_request::Type::Rpc,
+            client_name: "test".to_string(),
+            client_identifier: vec![],
         };
         let mut encoded = vec![];
         input.encode(&mut encoded).unwrap();
+        // type is left out, as Rpc is the default
         let output = [
-            // type
-            1 << 3,
-            0,
             // client_name
             2 << 3 | 2,
-            input.client_name.as_ref().map(|x| x.len()).unwrap_or(0) as u8,
+            input.client_name.len() as u8,
             b't',
             b'e',
             b's',
@@ -40,12 +83,9 @@ mod tests {
         input.encode_with_len(&mut packet).unwrap();
         let packet_output = [
             len as u8,
-            // type
-            1 << 3,
-            0,
             // client_name
             2 << 3 | 2,
-            input.client_name.as_ref().map(|x| x.len()).unwrap_or(0) as u8,
+            input.client_name.len() as u8,
             b't',
             b'e',
             b's',
@@ -70,9 +110,9 @@ mod tests {
         ];
         let decoded = ConnectionRequest::decode(&input[..]).unwrap();
         let output = ConnectionRequest {
-            r#type: Some(connection_request::Type::Rpc),
-            client_name: Some("test".to_string()),
-            client_identifier: None,
+            r#type: connection_request::Type::Rpc,
+            client_name: "test".to_string(),
+            client_identifier: vec![],
         };
         assert_eq!(decoded, output);
 
@@ -94,4 +134,164 @@ mod tests {
             ConnectionRequest::decode_with_len(&packet_input[..]).unwrap();
         assert_eq!(packet, output);
     }
+
+    #[test]
+    fn frames() {
+        let response = Response {
+            error: None,
+            results: vec![ProcedureResult {
+                error: None,
+                value: vec![1, 2, 3],
+            }],
+        };
+        let update = StreamUpdate {
+            results: vec![StreamResult {
+                id: 7,
+                result: Some(ProcedureResult {
+                    error: None,
+                    value: vec![4],
+                }),
+            }],
+        };
+        let mut input = vec![];
+        response.encode_with_len(&mut input).unwrap();
+        update.encode_with_len(&mut input).unwrap();
+
+        let mut decoder = FrameDecoder::new();
+        let (first, second) = input.split_at(3);
+        decoder.push(first);
+        assert_eq!(decoder.decode::<Response>().unwrap(), None);
+        decoder.push(second);
+        assert_eq!(decoder.decode::<Response>().unwrap(), Some(response));
+        assert_eq!(decoder.decode::<StreamUpdate>().unwrap(), Some(update));
+        assert_eq!(decoder.decode::<StreamUpdate>().unwrap(), None);
+    }
+
+    #[test]
+    fn unknown_enum_values() {
+        // game_scenes: FLIGHT, 42; return_type { code: 400 }
+        let input = [6 << 3, 1, 6 << 3, 42, 3 << 3 | 2, 3, 1 << 3, 0x90, 3];
+        let procedure = Procedure::decode(&input[..]).unwrap();
+        assert_eq!(
+            procedure.game_scenes,
+            [
+                procedure::GameScene::Flight,
+                procedure::GameScene::Unknown(42)
+            ]
+        );
+        let code = procedure.return_type.as_ref().unwrap().code;
+        assert_eq!(code, r#type::TypeCode::Unknown(400));
+        assert_eq!(code.number(), 400);
+        assert_eq!(r#type::TypeCode::from(100), r#type::TypeCode::Class);
+
+        let mut encoded = vec![];
+        procedure.encode(&mut encoded).unwrap();
+        assert_eq!(Procedure::decode(encoded.as_slice()).unwrap(), procedure);
+
+        // Negative values are sign-extended to 10 bytes
+        let code = r#type::TypeCode::Unknown(-2);
+        let mut encoded = vec![];
+        code.encode(&mut encoded).unwrap();
+        assert_eq!(
+            encoded,
+            [0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 1]
+        );
+        assert_eq!(code.size(), 10);
+        assert_eq!(r#type::TypeCode::decode(encoded.as_slice()).unwrap(), code);
+    }
+
+    #[test]
+    fn dynamic() {
+        use protobuf_but_worse::dynamic::Value;
+
+        let error = Error {
+            service: "SpaceCenter".to_string(),
+            name: "Oops".to_string(),
+            ..Error::default()
+        };
+        let mut encoded = vec![];
+        error.encode(&mut encoded).unwrap();
+        let value = descriptor_pool().decode("krpc.schema.Error", &encoded);
+        let value = value.unwrap();
+        assert_eq!(value.get("name"), Some(&Value::String("Oops".into())));
+        assert_eq!(value.get("description"), None);
+    }
+
+    #[test]
+    fn text() {
+        let services = Services {
+            services: vec![Service {
+                name: "SpaceCenter".to_string(),
+                procedures: vec![Procedure {
+                    name: "Vessel_get_Name".to_string(),
+                    parameters: vec![Parameter {
+                        name: "this".to_string(),
+                        r#type: Some(Type {
+                            code: r#type::TypeCode::Class,
+                            service: "SpaceCenter".to_string(),
+                            name: "Vessel".to_string(),
+                            types: vec![],
+                        }),
+                        default_value: vec![],
+                    }],
+                    return_type: Some(Type {
+                        code: r#type::TypeCode::String,
+                        ..Type::default()
+                    }),
+                    game_scenes: vec![
+                        procedure::GameScene::Flight,
+                        procedure::GameScene::TrackingStation,
+                    ],
+                    documentation: "<doc>\n  \"Name\"</doc>".to_string(),
+                    ..Procedure::default()
+                }],
+                ..Service::default()
+            }],
+        };
+        let text = to_text(&services).unwrap();
+        assert!(text.contains("      code: CLASS\n"));
+        assert!(text.contains("    game_scenes: TRACKING_STATION\n"));
+        assert!(text.contains(r#"documentation: "<doc>\n  \"Name\"</doc>""#));
+        assert_eq!(from_text::<Services>(&text).unwrap(), services);
+
+        let request = Request {
+            calls: vec![ProcedureCall {
+                service: "SpaceCenter".to_string(),
+                procedure: "Vessel_get_Name".to_string(),
+                arguments: vec![Argument {
+                    position: 0,
+                    value: vec![0x01, 0xff, b'a'],
+                }],
+                ..ProcedureCall::default()
+            }],
+        };
+        let text = to_text(&request).unwrap();
+        assert!(text.contains(r#"value: "\001\377a""#));
+        assert_eq!(from_text::<Request>(&text).unwrap(), request);
+
+        let text = r#"
+            calls { service: "KRPC" procedure: "GetStatus" }
+            calls < procedure_id: 2, arguments: [{ position: 1 }] >
+        "#;
+        let request = from_text::<Request>(text).unwrap();
+        assert_eq!(request.calls.len(), 2);
+        assert_eq!(request.calls[1].arguments[0].position, 1);
+        assert!(from_text::<Request>("calls { bogus: 1 }").is_err());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json() {
+        let status = Status {
+            version: "0.5.1".to_string(),
+            bytes_read: 1234,
+            bytes_read_rate: 0.5,
+            ..Status::default()
+        };
+        let json = to_json(&status).unwrap();
+        assert!(json.contains(r#""version": "0.5.1""#));
+        assert!(json.contains(r#""bytesRead": "1234""#));
+        assert!(json.contains(r#""bytesReadRate": 0.5"#));
+        assert_eq!(from_json::<Status>(&json).unwrap(), status);
+    }
 }
@@ -0,0 +1,591 @@
+use crate::{
+    class::Class,
+    control::SASMode,
+    reference_frame::ReferenceFrame,
+    values::{TupleOf, Vector3},
+    CallResult, KrpcConnection,
+};
+
+/// Provides basic auto-piloting utilities for a vessel.
+/// Obtained by calling [`Vessel::get_auto_pilot`].
+///
+/// # Note
+///
+/// If a client engages the auto-pilot and then closes its connection to the
+/// server, the auto-pilot will be disengaged and its target reference frame,
+/// direction and roll reset to default.
+///
+/// [`Vessel::get_auto_pilot`]: crate::Vessel::get_auto_pilot
+pub struct AutoPilot {
+    class: Class,
+}
+
+impl AutoPilot {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// Engage the auto-pilot.
+    pub fn engage(&self, krpc: &mut KrpcConnection) -> CallResult {
+        krpc.call("SpaceCenter", "AutoPilot_Engage", &[&self.class])
+    }
+
+    /// Disengage the auto-pilot.
+    pub fn disengage(&self, krpc: &mut KrpcConnection) -> CallResult {
+        krpc.call("SpaceCenter", "AutoPilot_Disengage", &[&self.class])
+    }
+
+    /// Blocks until the vessel is pointing in the target direction and has the
+    /// target roll (if set). Throws an exception if the auto-pilot has not been
+    /// engaged.
+    pub fn wait(&self, krpc: &mut KrpcConnection) -> CallResult {
+        krpc.call("SpaceCenter", "AutoPilot_Wait", &[&self.class])
+    }
+
+    /// Set target pitch and heading angles.
+    ///
+    /// - `pitch`: Target pitch angle, in degrees between -90° and +90°.
+    /// - `heading`: Target heading angle, in degrees between 0° and 360°.
+    pub fn target_pitch_and_heading(
+        &self,
+        krpc: &mut KrpcConnection,
+        pitch: f32,
+        heading: f32,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "AutoPilot_TargetPitchAndHeading",
+            &[&self.class, &pitch, &heading],
+        )
+    }
+
+    /// The error, in degrees, between the direction the ship has been asked to
+    /// point in and the direction it is pointing in. Throws an exception if the
+    /// auto-pilot has not been engaged and SAS is not enabled or is in
+    /// stability assist mode.
+    pub fn get_error(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "AutoPilot_get_Error", &[&self.class])
+    }
+
+    /// The error, in degrees, between the vessels current and target pitch.
+    /// Throws an exception if the auto-pilot has not been engaged.
+    pub fn get_pitch_error(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "AutoPilot_get_PitchError", &[&self.class])
+    }
+
+    /// The error, in degrees, between the vessels current and target heading.
+    /// Throws an exception if the auto-pilot has not been engaged.
+    pub fn get_heading_error(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "AutoPilot_get_HeadingError", &[&self.class])
+    }
+
+    /// The error, in degrees, between the vessels current and target roll.
+    /// Throws an exception if the auto-pilot has not been engaged or no target
+    /// roll is set.
+    pub fn get_roll_error(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "AutoPilot_get_RollError", &[&self.class])
+    }
+
+    /// The reference frame for the target direction
+    /// ([`AutoPilot::get_target_direction`]).
+    ///
+    /// # Note
+    ///
+    /// An error will be thrown if this property is set to a reference frame
+    /// that rotates with the vessel being controlled, as it is impossible to
+    /// rotate the vessel in such a reference frame.
+    pub fn get_reference_frame(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<ReferenceFrame> {
+        krpc.call(
+            "SpaceCenter",
+            "AutoPilot_get_ReferenceFrame",
+            &[&self.class],
+        )
+        .map(ReferenceFrame::new)
+    }
+
+    /// The reference frame for the target direction
+    /// ([`AutoPilot::get_target_direction`]).
+    ///
+    /// # Note
+    ///
+    /// An error will be thrown if this property is set to a reference frame
+    /// that rotates with the vessel being controlled, as it is impossible to
+    /// rotate the vessel in such a reference frame.
+    pub fn set_reference_frame(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: &ReferenceFrame,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "AutoPilot_set_ReferenceFrame",
+            &[&self.class, &value.class],
+        )
+    }
+
+    /// The target pitch, in degrees, between -90° and +90°.
+    pub fn get_target_pitch(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "AutoPilot_get_TargetPitch", &[&self.class])
+    }
+
+    /// The target pitch, in degrees, between -90° and +90°.
+    pub fn set_target_pitch(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "AutoPilot_set_TargetPitch",
+            &[&self.class, &value],
+        )
+    }
+
+    /// The target heading, in degrees, between 0° and 360°.
+    pub fn get_target_heading(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "AutoPilot_get_TargetHeading", &[&self.class])
+    }
+
+    /// The target heading, in degrees, between 0° and 360°.
+    pub fn set_target_heading(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "AutoPilot_set_TargetHeading",
+            &[&self.class, &value],
+        )
+    }
+
+    /// The target roll, in degrees. `NaN` if no target roll is set.
+    pub fn get_target_roll(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "AutoPilot_get_TargetRoll", &[&self.class])
+    }
+
+    /// The target roll, in degrees. `NaN` if no target roll is set.
+    pub fn set_target_roll(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "AutoPilot_set_TargetRoll",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Direction vector corresponding to the target pitch and heading.
+    /// This is in the reference frame specified by
+    /// [`AutoPilot::get_reference_frame`].
+    pub fn get_target_direction(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "AutoPilot_get_TargetDirection",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// Direction vector corresponding to the target pitch and heading.
+    /// This is in the reference frame specified by
+    /// [`AutoPilot::get_reference_frame`].
+    pub fn set_target_direction(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: Vector3,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "AutoPilot_set_TargetDirection",
+            &[&self.class, &TupleOf(value)],
+        )
+    }
+
+    /// The state of SAS.
+    ///
+    /// # Note
+    ///
+    /// Equivalent to [`Control::get_sas`](crate::Control::get_sas)
+    pub fn get_sas(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "AutoPilot_get_SAS", &[&self.class])
+    }
+
+    /// The state of SAS.
+    ///
+    /// # Note
+    ///
+    /// Equivalent to [`Control::get_sas`](crate::Control::get_sas)
+    pub fn set_sas(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "AutoPilot_set_SAS", &[&self.class, &value])
+    }
+
+    /// The current `SASMode`. These modes are equivalent to the mode buttons to
+    /// the left of the navball that appear when SAS is enabled.
+    ///
+    /// # Note
+    ///
+    /// Equivalent to [`Control::get_sas_mode`](crate::Control::get_sas_mode)
+    pub fn get_sas_mode(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<SASMode> {
+        krpc.call("SpaceCenter", "AutoPilot_get_SASMode", &[&self.class])
+    }
+
+    /// The current `SASMode`. These modes are equivalent to the mode buttons to
+    /// the left of the navball that appear when SAS is enabled.
+    ///
+    /// # Note
+    ///
+    /// Equivalent to [`Control::get_sas_mode`](crate::Control::get_sas_mode)
+    pub fn set_sas_mode(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: SASMode,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "AutoPilot_set_SASMode",
+            &[&self.class, &value],
+        )
+    }
+
+    /// The threshold at which the autopilot will try to match the target roll
+    /// angle, if any. Defaults to 5 degrees.
+    pub fn get_roll_threshold(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "AutoPilot_get_RollThreshold", &[&self.class])
+    }
+
+    /// The threshold at which the autopilot will try to match the target roll
+    /// angle, if any. Defaults to 5 degrees.
+    pub fn set_roll_threshold(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f64,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "AutoPilot_set_RollThreshold",
+            &[&self.class, &value],
+        )
+    }
+
+    /// The maximum amount of time that the vessel should need to come to a
+    /// complete stop. This determines the maximum angular velocity of the
+    /// vessel. A vector of three stopping times, in seconds, one for each of
+    /// the pitch, roll and yaw axes. Defaults to 0.5 seconds for each axis.
+    pub fn get_stopping_time(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "AutoPilot_get_StoppingTime",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The maximum amount of time that the vessel should need to come to a
+    /// complete stop. This determines the maximum angular velocity of the
+    /// vessel. A vector of three stopping times, in seconds, one for each of
+    /// the pitch, roll and yaw axes. Defaults to 0.5 seconds for each axis.
+    pub fn set_stopping_time(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: Vector3,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "AutoPilot_set_StoppingTime",
+            &[&self.class, &TupleOf(value)],
+        )
+    }
+
+    /// The time the vessel should take to come to a stop pointing in the target
+    /// direction. This determines the angular acceleration used to decelerate
+    /// the vessel. A vector of three times, in seconds, one for each of the
+    /// pitch, roll and yaw axes. Defaults to 5 seconds for each axis.
+    pub fn get_deceleration_time(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "AutoPilot_get_DecelerationTime",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The time the vessel should take to come to a stop pointing in the target
+    /// direction. This determines the angular acceleration used to decelerate
+    /// the vessel. A vector of three times, in seconds, one for each of the
+    /// pitch, roll and yaw axes. Defaults to 5 seconds for each axis.
+    pub fn set_deceleration_time(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: Vector3,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "AutoPilot_set_DecelerationTime",
+            &[&self.class, &TupleOf(value)],
+        )
+    }
+
+    /// The angle at which the autopilot considers the vessel to be pointing
+    /// close to the target. This determines the midpoint of the target velocity
+    /// attenuation function. A vector of three angles, in degrees, one for each
+    /// of the pitch, roll and yaw axes. Defaults to 1° for each axis.
+    pub fn get_attenuation_angle(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "AutoPilot_get_AttenuationAngle",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The angle at which the autopilot considers the vessel to be pointing
+    /// close to the target. This determines the midpoint of the target velocity
+    /// attenuation function. A vector of three angles, in degrees, one for each
+    /// of the pitch, roll and yaw axes. Defaults to 1° for each axis.
+    pub fn set_attenuation_angle(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: Vector3,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "AutoPilot_set_AttenuationAngle",
+            &[&self.class, &TupleOf(value)],
+        )
+    }
+
+    /// Whether the rotation rate controllers PID parameters should be
+    /// automatically tuned using the vessels moment of inertia and available
+    /// torque. Defaults to `true`. See [`AutoPilot::get_time_to_peak`] and
+    /// [`AutoPilot::get_overshoot`].
+    pub fn get_auto_tune(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "AutoPilot_get_AutoTune", &[&self.class])
+    }
+
+    /// Whether the rotation rate controllers PID parameters should be
+    /// automatically tuned using the vessels moment of inertia and available
+    /// torque. Defaults to `true`. See [`AutoPilot::get_time_to_peak`] and
+    /// [`AutoPilot::get_overshoot`].
+    pub fn set_auto_tune(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "AutoPilot_set_AutoTune",
+            &[&self.class, &value],
+        )
+    }
+
+    /// The target time to peak used to autotune the PID controllers. A vector
+    /// of three times, in seconds, for each of the pitch, roll and yaw axes.
+    /// Defaults to 3 seconds for each axis.
+    pub fn get_time_to_peak(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "AutoPilot_get_TimeToPeak",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The target time to peak used to autotune the PID controllers. A vector
+    /// of three times, in seconds, for each of the pitch, roll and yaw axes.
+    /// Defaults to 3 seconds for each axis.
+    pub fn set_time_to_peak(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: Vector3,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "AutoPilot_set_TimeToPeak",
+            &[&self.class, &TupleOf(value)],
+        )
+    }
+
+    /// The target overshoot percentage used to autotune the PID controllers. A
+    /// vector of three values, between 0 and 1, for each of the pitch, roll and
+    /// yaw axes. Defaults to 0.01 for each axis.
+    pub fn get_overshoot(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "AutoPilot_get_Overshoot",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The target overshoot percentage used to autotune the PID controllers. A
+    /// vector of three values, between 0 and 1, for each of the pitch, roll and
+    /// yaw axes. Defaults to 0.01 for each axis.
+    pub fn set_overshoot(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: Vector3,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "AutoPilot_set_Overshoot",
+            &[&self.class, &TupleOf(value)],
+        )
+    }
+
+    /// Gains for the pitch PID controller.
+    ///
+    /// # Note
+    ///
+    /// When [`AutoPilot::get_auto_tune`] is true, these values are updated
+    /// automatically, which will overwrite any manual changes.
+    pub fn get_pitch_pid_gains(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "AutoPilot_get_PitchPIDGains",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// Gains for the pitch PID controller.
+    ///
+    /// # Note
+    ///
+    /// When [`AutoPilot::get_auto_tune`] is true, these values are updated
+    /// automatically, which will overwrite any manual changes.
+    pub fn set_pitch_pid_gains(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: Vector3,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "AutoPilot_set_PitchPIDGains",
+            &[&self.class, &TupleOf(value)],
+        )
+    }
+
+    /// Gains for the roll PID controller.
+    ///
+    /// # Note
+    ///
+    /// When [`AutoPilot::get_auto_tune`] is true, these values are updated
+    /// automatically, which will overwrite any manual changes.
+    pub fn get_roll_pid_gains(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "AutoPilot_get_RollPIDGains",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// Gains for the roll PID controller.
+    ///
+    /// # Note
+    ///
+    /// When [`AutoPilot::get_auto_tune`] is true, these values are updated
+    /// automatically, which will overwrite any manual changes.
+    pub fn set_roll_pid_gains(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: Vector3,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "AutoPilot_set_RollPIDGains",
+            &[&self.class, &TupleOf(value)],
+        )
+    }
+
+    /// Gains for the yaw PID controller.
+    ///
+    /// # Note
+    ///
+    /// When [`AutoPilot::get_auto_tune`] is true, these values are updated
+    /// automatically, which will overwrite any manual changes.
+    pub fn get_yaw_pid_gains(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "AutoPilot_get_YawPIDGains",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// Gains for the yaw PID controller.
+    ///
+    /// # Note
+    ///
+    /// When [`AutoPilot::get_auto_tune`] is true, these values are updated
+    /// automatically, which will overwrite any manual changes.
+    pub fn set_yaw_pid_gains(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: Vector3,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "AutoPilot_set_YawPIDGains",
+            &[&self.class, &TupleOf(value)],
+        )
+    }
+}
@@ -0,0 +1,223 @@
+use crate::{
+    celestial_body::CelestialBody, class::Class, node::Node, values::krpc_enum,
+    vessel::Vessel, CallResult, KrpcConnection,
+};
+
+krpc_enum! {
+    /// See [`Camera::get_mode`].
+    pub enum CameraMode {
+        /// The camera is showing the active vessel, in "auto" mode.
+        Automatic = 0,
+        /// The camera is showing the active vessel, in "free" mode.
+        Free = 1,
+        /// The camera is showing the active vessel, in "chase" mode.
+        Chase = 2,
+        /// The camera is showing the active vessel, in "locked" mode.
+        Locked = 3,
+        /// The camera is showing the active vessel, in "orbital" mode.
+        Orbital = 4,
+        /// The Intra-Vehicular Activity view is being shown.
+        IVA = 5,
+        /// The map view is being shown.
+        Map = 6,
+    }
+}
+
+/// Controls the game's camera. Obtained by calling
+/// [`KrpcConnection::get_camera`](crate::KrpcConnection::get_camera).
+pub struct Camera {
+    pub(crate) class: Class,
+}
+
+impl Camera {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// The current mode of the camera.
+    pub fn get_mode(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<CameraMode> {
+        krpc.call("SpaceCenter", "Camera_get_Mode", &[&self.class])
+    }
+
+    /// The current mode of the camera.
+    pub fn set_mode(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: CameraMode,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Camera_set_Mode", &[&self.class, &value])
+    }
+
+    /// The pitch of the camera, in degrees. A value between
+    /// [`Camera::get_min_pitch`] and [`Camera::get_max_pitch`].
+    pub fn get_pitch(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Camera_get_Pitch", &[&self.class])
+    }
+
+    /// The pitch of the camera, in degrees. A value between
+    /// [`Camera::get_min_pitch`] and [`Camera::get_max_pitch`].
+    pub fn set_pitch(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Camera_set_Pitch", &[&self.class, &value])
+    }
+
+    /// The heading of the camera, in degrees.
+    pub fn get_heading(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Camera_get_Heading", &[&self.class])
+    }
+
+    /// The heading of the camera, in degrees.
+    pub fn set_heading(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Camera_set_Heading", &[&self.class, &value])
+    }
+
+    /// The distance from the camera to the subject, in meters. A value between
+    /// [`Camera::get_min_distance`] and [`Camera::get_max_distance`].
+    pub fn get_distance(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Camera_get_Distance", &[&self.class])
+    }
+
+    /// The distance from the camera to the subject, in meters. A value between
+    /// [`Camera::get_min_distance`] and [`Camera::get_max_distance`].
+    pub fn set_distance(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Camera_set_Distance", &[&self.class, &value])
+    }
+
+    /// The minimum pitch of the camera.
+    pub fn get_min_pitch(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Camera_get_MinPitch", &[&self.class])
+    }
+
+    /// The maximum pitch of the camera.
+    pub fn get_max_pitch(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Camera_get_MaxPitch", &[&self.class])
+    }
+
+    /// Minimum distance from the camera to the subject, in meters.
+    pub fn get_min_distance(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Camera_get_MinDistance", &[&self.class])
+    }
+
+    /// Maximum distance from the camera to the subject, in meters.
+    pub fn get_max_distance(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Camera_get_MaxDistance", &[&self.class])
+    }
+
+    /// Default distance from the camera to the subject, in meters.
+    pub fn get_default_distance(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Camera_get_DefaultDistance", &[&self.class])
+    }
+
+    /// In map mode, the celestial body that the camera is focussed on. Returns
+    /// `None` if the camera is not focussed on a celestial body. Returns an
+    /// error if the camera is not in map mode.
+    pub fn get_focussed_body(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Option<CelestialBody>> {
+        krpc.call::<Class>(
+            "SpaceCenter",
+            "Camera_get_FocussedBody",
+            &[&self.class],
+        )
+        .map(|x| x.into_option().map(CelestialBody::new))
+    }
+
+    /// In map mode, the celestial body that the camera is focussed on. Returns
+    /// `None` if the camera is not focussed on a celestial body. Returns an
+    /// error if the camera is not in map mode.
+    pub fn set_focussed_body(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: &CelestialBody,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Camera_set_FocussedBody",
+            &[&self.class, &value.class],
+        )
+    }
+
+    /// In map mode, the vessel that the camera is focussed on. Returns `None`
+    /// if the camera is not focussed on a vessel. Returns an error if the
+    /// camera is not in map mode.
+    pub fn get_focussed_vessel(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Option<Vessel>> {
+        krpc.call::<Class>(
+            "SpaceCenter",
+            "Camera_get_FocussedVessel",
+            &[&self.class],
+        )
+        .map(|x| x.into_option().map(Vessel::new))
+    }
+
+    /// In map mode, the vessel that the camera is focussed on. Returns `None`
+    /// if the camera is not focussed on a vessel. Returns an error if the
+    /// camera is not in map mode.
+    pub fn set_focussed_vessel(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: &Vessel,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Camera_set_FocussedVessel",
+            &[&self.class, &value.class],
+        )
+    }
+
+    /// In map mode, the maneuver node that the camera is focussed on. Returns
+    /// `None` if the camera is not focussed on a maneuver node. Returns an
+    /// error if the camera is not in map mode.
+    pub fn get_focussed_node(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Option<Node>> {
+        krpc.call::<Class>(
+            "SpaceCenter",
+            "Camera_get_FocussedNode",
+            &[&self.class],
+        )
+        .map(|x| x.into_option().map(Node::new))
+    }
+
+    /// In map mode, the maneuver node that the camera is focussed on. Returns
+    /// `None` if the camera is not focussed on a maneuver node. Returns an
+    /// error if the camera is not in map mode.
+    pub fn set_focussed_node(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: &Node,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Camera_set_FocussedNode",
+            &[&self.class, &value.class],
+        )
+    }
+}
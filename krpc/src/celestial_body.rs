@@ -0,0 +1,695 @@
+use std::collections::HashSet;
+
+use crate::{
+    class::Class,
+    orbit::Orbit,
+    reference_frame::ReferenceFrame,
+    values::{ListOf, Quaternion, TupleOf, Vector3},
+    CallResult, KrpcConnection,
+};
+
+/// Represents a celestial body (such as a planet or moon).
+/// See [`KrpcConnection::bodies`].
+pub struct CelestialBody {
+    pub(crate) class: Class,
+}
+
+impl CelestialBody {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// The height of the surface relative to mean sea level, in meters, at the
+    /// given position. When over water this is equal to 0.
+    ///
+    /// - `latitude`: Latitude in degrees.
+    /// - `longitude`: Longitude in degrees.
+    pub fn surface_height(
+        &self,
+        krpc: &mut KrpcConnection,
+        latitude: f64,
+        longitude: f64,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_SurfaceHeight",
+            &[&self.class, &latitude, &longitude],
+        )
+    }
+
+    /// The height of the surface relative to mean sea level, in meters, at the
+    /// given position. When over water, this is the height of the sea-bed and
+    /// is therefore  negative value.
+    ///
+    /// - `latitude`: Latitude in degrees.
+    /// - `longitude`: Longitude in degrees.
+    pub fn bedrock_height(
+        &self,
+        krpc: &mut KrpcConnection,
+        latitude: f64,
+        longitude: f64,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_BedrockHeight",
+            &[&self.class, &latitude, &longitude],
+        )
+    }
+
+    /// The position at mean sea level at the given latitude and longitude, in
+    /// the given reference frame.
+    ///
+    /// - `latitude`: Latitude in degrees.
+    /// - `longitude`: Longitude in degrees.
+    /// - `reference_frame`: Reference frame for the returned position vector.
+    ///
+    /// # Returns
+    ///
+    /// Position as a vector.
+    pub fn msl_position(
+        &self,
+        krpc: &mut KrpcConnection,
+        latitude: f64,
+        longitude: f64,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "CelestialBody_MSLPosition",
+            &[&self.class, &latitude, &longitude, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The position of the surface at the given latitude and longitude, in the
+    /// given reference frame. When over water, this is the position of the
+    /// surface of the water.
+    ///
+    /// - `latitude`: Latitude in degrees.
+    /// - `longitude`: Longitude in degrees.
+    /// - `reference_frame`: Reference frame for the returned position vector.
+    ///
+    /// # Returns
+    ///
+    /// Position as a vector.
+    pub fn surface_position(
+        &self,
+        krpc: &mut KrpcConnection,
+        latitude: f64,
+        longitude: f64,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "CelestialBody_SurfacePosition",
+            &[&self.class, &latitude, &longitude, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The position of the surface at the given latitude and longitude, in the
+    /// given reference frame. When over water, this is the position at the
+    /// bottom of the sea-bed.
+    ///
+    /// - `latitude`: Latitude in degrees.
+    /// - `longitude`: Longitude in degrees.
+    /// - `reference_frame`: Reference frame for the returned position vector.
+    ///
+    /// # Returns
+    ///
+    /// Position as a vector.
+    pub fn bedrock_position(
+        &self,
+        krpc: &mut KrpcConnection,
+        latitude: f64,
+        longitude: f64,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "CelestialBody_BedrockPosition",
+            &[&self.class, &latitude, &longitude, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The position at the given latitude, longitude and altitude, in the given
+    /// reference frame.
+    ///
+    /// - `latitude`: Latitude in degrees.
+    /// - `longitude`: Longitude in degrees.
+    /// - `altitude`: Altitude in meters above sea level.
+    /// - `reference_frame`: Reference frame for the returned position vector.
+    ///
+    /// # Returns
+    ///
+    /// Position as a vector.
+    pub fn position_at_altitude(
+        &self,
+        krpc: &mut KrpcConnection,
+        latitude: f64,
+        longitude: f64,
+        altitude: f64,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "CelestialBody_PositionAtAltitude",
+            &[
+                &self.class,
+                &latitude,
+                &longitude,
+                &altitude,
+                &reference_frame.class,
+            ],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The latitude of the given position, in the given reference frame.
+    ///
+    /// - `position`: Position as a vector.
+    /// - `reference_frame`: Reference frame for the position vector.
+    pub fn latitude_at_position(
+        &self,
+        krpc: &mut KrpcConnection,
+        position: Vector3,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_LatitudeAtPosition",
+            &[&self.class, &TupleOf(position), &reference_frame.class],
+        )
+    }
+
+    /// The longitude of the given position, in the given reference frame.
+    ///
+    /// - `position`: Position as a vector.
+    /// - `reference_frame`: Reference frame for the position vector.
+    pub fn longitude_at_position(
+        &self,
+        krpc: &mut KrpcConnection,
+        position: Vector3,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_LongitudeAtPosition",
+            &[&self.class, &TupleOf(position), &reference_frame.class],
+        )
+    }
+
+    /// The altitude, in meters, of the given position in the given reference
+    /// frame.
+    ///
+    /// - `position`: Position as a vector.
+    /// - `reference_frame`: Reference frame for the position vector.
+    pub fn altitude_at_position(
+        &self,
+        krpc: &mut KrpcConnection,
+        position: Vector3,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_AltitudeAtPosition",
+            &[&self.class, &TupleOf(position), &reference_frame.class],
+        )
+    }
+
+    /// The atmospheric density at the given position, in `kg/m^3`, in the given
+    /// reference frame.
+    ///
+    /// - `position`: The position vector at which to measure the density.
+    /// - `reference_frame`: Reference frame that the position vector is in.
+    pub fn atmospheric_density_at_position(
+        &self,
+        krpc: &mut KrpcConnection,
+        position: Vector3,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_AtmosphericDensityAtPosition",
+            &[&self.class, &TupleOf(position), &reference_frame.class],
+        )
+    }
+
+    /// The temperature on the body at the given position, in the given
+    /// reference frame.
+    ///
+    /// - `position`: Position as a vector.
+    /// - `reference_frame`: The reference frame that the position is in.
+    ///
+    /// # Note
+    ///
+    /// This calculation is performed using the bodies current position, which
+    /// means that the value could be wrong if you want to know the temperature
+    /// in the far future.
+    pub fn temperature_at(
+        &self,
+        krpc: &mut KrpcConnection,
+        position: Vector3,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_TemperatureAt",
+            &[&self.class, &TupleOf(position), &reference_frame.class],
+        )
+    }
+
+    /// Gets the air density, in `kg/m^3`, for the specified altitude above sea
+    /// level, in meters.
+    ///
+    /// # Note
+    ///
+    /// This is an approximation, because actual calculations, taking sun
+    /// exposure into account to compute air temperature, require us to know the
+    /// exact point on the body where the density is to be computed (knowing the
+    /// altitude is not enough). However, the difference is small for high
+    /// altitudes, so it makes very little difference for trajectory prediction.
+    pub fn density_at(
+        &self,
+        krpc: &mut KrpcConnection,
+        altitude: f64,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_DensityAt",
+            &[&self.class, &altitude],
+        )
+    }
+
+    /// Gets the air pressure, in Pascals, for the specified altitude above sea
+    /// level, in meters.
+    pub fn pressure_at(
+        &self,
+        krpc: &mut KrpcConnection,
+        altitude: f64,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_PressureAt",
+            &[&self.class, &altitude],
+        )
+    }
+
+    /// The biome at the given latitude and longitude, in degrees.
+    pub fn biome_at(
+        &self,
+        krpc: &mut KrpcConnection,
+        latitude: f64,
+        longitude: f64,
+    ) -> CallResult<String> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_BiomeAt",
+            &[&self.class, &latitude, &longitude],
+        )
+    }
+
+    /// The position of the center of the body, in the specified reference
+    /// frame.
+    ///
+    /// - `reference_frame`: The reference frame that the returned position
+    ///   vector is in.
+    ///
+    /// # Returns
+    ///
+    /// The position as a vector.
+    pub fn position(
+        &self,
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "CelestialBody_Position",
+            &[&self.class, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The linear velocity of the body, in the specified reference frame.
+    ///
+    /// - `reference_frame`: The reference frame that the returned velocity
+    ///   vector is in.
+    ///
+    /// # Returns
+    ///
+    /// The velocity as a vector. The vector points in the direction of travel,
+    /// and its magnitude is the speed of the body in meters per second.
+    pub fn velocity(
+        &self,
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "CelestialBody_Velocity",
+            &[&self.class, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The rotation of the body, in the specified reference frame.
+    ///
+    /// - `reference_frame`: The reference frame that the returned rotation is
+    ///   in.
+    ///
+    /// # Returns
+    ///
+    /// The rotation as a quaternion of the form `(x, y, z, w)`.
+    pub fn rotation(
+        &self,
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Quaternion> {
+        krpc.call::<TupleOf<Quaternion>>(
+            "SpaceCenter",
+            "CelestialBody_Rotation",
+            &[&self.class, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The direction in which the north pole of the celestial body is pointing,
+    /// in the specified reference frame.
+    ///
+    /// - `reference_frame`: The reference frame that the returned direction is
+    ///   in.
+    ///
+    /// # Returns
+    ///
+    /// The direction as a unit vector.
+    pub fn direction(
+        &self,
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "CelestialBody_Direction",
+            &[&self.class, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The angular velocity of the body in the specified reference frame.
+    ///
+    /// - `reference_frame`: The reference frame the returned angular velocity
+    ///   is in.
+    ///
+    /// # Returns
+    ///
+    /// The angular velocity as a vector. The magnitude of the vector is the
+    /// rotational speed of the body, in radians per second. The direction of
+    /// the vector indicates the axis of rotation, using the right-hand rule.
+    pub fn angular_velocity(
+        &self,
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "CelestialBody_AngularVelocity",
+            &[&self.class, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The name of the body.
+    pub fn get_name(&self, krpc: &mut KrpcConnection) -> CallResult<String> {
+        krpc.call("SpaceCenter", "CelestialBody_get_Name", &[&self.class])
+    }
+
+    /// A list of celestial bodies that are in orbit around this celestial body.
+    pub fn get_satellites(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<CelestialBody>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "CelestialBody_get_Satellites",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(CelestialBody::new).collect())
+    }
+
+    /// The mass of the body, in kilograms.
+    pub fn get_mass(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "CelestialBody_get_Mass", &[&self.class])
+    }
+
+    /// The standard gravitational parameter of the body in `m^3s^{-2}`.
+    pub fn get_gravitational_parameter(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_get_GravitationalParameter",
+            &[&self.class],
+        )
+    }
+
+    /// The acceleration due to gravity at sea level (mean altitude) on the
+    /// body, in `m/s^2`.
+    pub fn get_surface_gravity(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_get_SurfaceGravity",
+            &[&self.class],
+        )
+    }
+
+    /// The sidereal rotational period of the body, in seconds.
+    pub fn get_rotational_period(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_get_RotationalPeriod",
+            &[&self.class],
+        )
+    }
+
+    /// The rotational speed of the body, in radians per second.
+    pub fn get_rotational_speed(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_get_RotationalSpeed",
+            &[&self.class],
+        )
+    }
+
+    /// The current rotation angle of the body, in radians. A value between 0
+    /// and `2\pi`
+    pub fn get_rotation_angle(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_get_RotationAngle",
+            &[&self.class],
+        )
+    }
+
+    /// The initial rotation angle of the body (at UT 0), in radians. A value
+    /// between 0 and `2\pi`
+    pub fn get_initial_rotation(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_get_InitialRotation",
+            &[&self.class],
+        )
+    }
+
+    /// The equatorial radius of the body, in meters.
+    pub fn get_equatorial_radius(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_get_EquatorialRadius",
+            &[&self.class],
+        )
+    }
+
+    /// The radius of the sphere of influence of the body, in meters.
+    pub fn get_sphere_of_influence(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_get_SphereOfInfluence",
+            &[&self.class],
+        )
+    }
+
+    /// The orbit of the body.
+    pub fn get_orbit(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Option<Orbit>> {
+        krpc.call::<Class>(
+            "SpaceCenter",
+            "CelestialBody_get_Orbit",
+            &[&self.class],
+        )
+        .map(|x| x.into_option().map(Orbit::new))
+    }
+
+    /// `true` if the body has an atmosphere.
+    pub fn get_has_atmosphere(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_get_HasAtmosphere",
+            &[&self.class],
+        )
+    }
+
+    /// The depth of the atmosphere, in meters.
+    pub fn get_atmosphere_depth(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_get_AtmosphereDepth",
+            &[&self.class],
+        )
+    }
+
+    /// `true` if there is oxygen in the atmosphere, required for air-breathing
+    /// engines.
+    pub fn get_has_atmospheric_oxygen(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_get_HasAtmosphericOxygen",
+            &[&self.class],
+        )
+    }
+
+    /// The biomes present on this body.
+    pub fn get_biomes(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<HashSet<String>> {
+        krpc.call::<ListOf<String>>(
+            "SpaceCenter",
+            "CelestialBody_get_Biomes",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().collect())
+    }
+
+    /// The altitude, in meters, above which a vessel is considered to be flying
+    /// "high" when doing science.
+    pub fn get_flying_high_altitude_threshold(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_get_FlyingHighAltitudeThreshold",
+            &[&self.class],
+        )
+    }
+
+    /// The altitude, in meters, above which a vessel is considered to be in
+    /// "high" space when doing science.
+    pub fn get_space_high_altitude_threshold(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_get_SpaceHighAltitudeThreshold",
+            &[&self.class],
+        )
+    }
+
+    /// The reference frame that is fixed relative to the celestial body.
+    ///
+    /// - The origin is at the center of the body.
+    /// - The axes rotate with the body.
+    /// - The x-axis points from the center of the body towards the intersection
+    ///   of the prime meridian and equator (the position at 0° longitude, 0°
+    ///   latitude).
+    /// - The y-axis points from the center of the body towards the north pole.
+    /// - The z-axis points from the center of the body towards the equator at
+    ///   90°E longitude.
+    pub fn get_reference_frame(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<ReferenceFrame> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_get_ReferenceFrame",
+            &[&self.class],
+        )
+        .map(ReferenceFrame::new)
+    }
+
+    /// The reference frame that is fixed relative to this celestial body, and
+    /// orientated in a fixed direction (it does not rotate with the body).
+    ///
+    /// - The origin is at the center of the body.
+    /// - The axes do not rotate.
+    /// - The x-axis points in an arbitrary direction through the equator.
+    /// - The y-axis points from the center of the body towards the north pole.
+    /// - The z-axis points in an arbitrary direction through the equator.
+    pub fn get_non_rotating_reference_frame(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<ReferenceFrame> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_get_NonRotatingReferenceFrame",
+            &[&self.class],
+        )
+        .map(ReferenceFrame::new)
+    }
+
+    /// The reference frame that is fixed relative to this celestial body, but
+    /// orientated with the body's orbital prograde/normal/radial directions.
+    ///
+    /// - The origin is at the center of the body.
+    /// - The axes rotate with the orbital prograde/normal/radial directions.
+    /// - The x-axis points in the orbital anti-radial direction.
+    /// - The y-axis points in the orbital prograde direction.
+    /// - The z-axis points in the orbital normal direction.
+    pub fn get_orbital_reference_frame(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<ReferenceFrame> {
+        krpc.call(
+            "SpaceCenter",
+            "CelestialBody_get_OrbitalReferenceFrame",
+            &[&self.class],
+        )
+        .map(ReferenceFrame::new)
+    }
+}
@@ -1,19 +1,16 @@
-use protobuf_but_worse::encoding::{Decode, Encode, EncodingError, Varint};
+use protobuf_but_worse_derive::{Decode, Encode};
 
-pub struct Class(Varint<u64>);
+#[derive(Encode, Decode)]
+pub struct Class(#[pb(encoding = "varint")] u64);
 
-impl Decode for Class {
-    fn decode<R: std::io::Read>(r: R) -> Result<Self, EncodingError> {
-        <Varint<u64>>::decode(r).map(Class)
-    }
-}
-
-impl Encode for Class {
-    fn size(&self) -> u32 {
-        self.0.size()
-    }
-
-    fn encode<W: std::io::Write>(&self, w: W) -> Result<(), EncodingError> {
-        self.0.encode(w)
+impl Class {
+    /// Handle 0 is a null object, returned and accepted where
+    /// kRPC has `Option<Class>`
+    pub(crate) fn into_option(self) -> Option<Self> {
+        if self.0 == 0 {
+            None
+        } else {
+            Some(self)
+        }
     }
 }
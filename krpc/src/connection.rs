@@ -1,4 +1,5 @@
 use std::{
+    collections::HashMap,
     io::Write,
     net::{SocketAddr, TcpStream, ToSocketAddrs},
     time::Duration,
@@ -11,10 +12,35 @@ use krpc_proto::{
 };
 use protobuf_but_worse::encoding::*;
 
-use crate::{vessel::Vessel, CallResult};
+use crate::{
+    camera::Camera,
+    celestial_body::CelestialBody,
+    class::Class,
+    node::Node,
+    values::{krpc_enum, DictOf, ListOf},
+    vessel::Vessel,
+    CallResult,
+};
+
+krpc_enum! {
+    /// The time warp mode. Returned by [`KrpcConnection::get_warp_mode`].
+    pub enum WarpMode {
+        /// Time warp is active, and in regular "on-rails" mode.
+        Rails = 0,
+        /// Time warp is active, and in physical time warp mode.
+        Physics = 1,
+        /// Time warp is not active.
+        None = 2,
+    }
+}
 
 pub struct KrpcConnection {
     stream: TcpStream,
+    limits: DecodeLimits,
+    decoder: FrameDecoder,
+    nonblocking: bool,
+    // Calls sent with `send_call` whose responses weren't received yet
+    pending: usize,
 }
 
 impl KrpcConnection {
@@ -44,19 +70,42 @@ impl KrpcConnection {
         name: String,
     ) -> Result<Self, EncodingError> {
         let crq = ConnectionRequest {
-            r#type: Some(Type::Rpc),
-            client_name: Some(name),
-            client_identifier: None,
+            r#type: Type::Rpc,
+            client_name: name,
+            client_identifier: vec![],
         };
         crq.encode_with_len(&mut stream)?;
         stream.flush()?;
         let crp = ConnectionResponse::decode_with_len(&mut stream)?;
-        if !matches!(crp.status, Some(Status::Ok) | None) {
+        if crp.status != Status::Ok {
             let io_error = std::io::ErrorKind::ConnectionRefused.into();
-            return Err(EncodingError::Io(io_error)
-                .context(crp.message.unwrap_or_default()));
+            return Err(EncodingError::Io(io_error).context(crp.message));
         }
-        Ok(Self { stream })
+        Ok(Self {
+            stream,
+            limits: DecodeLimits::default(),
+            decoder: FrameDecoder::new(),
+            nonblocking: false,
+            pending: 0,
+        })
+    }
+
+    /// Switches socket to non-blocking mode, for use with
+    /// [`KrpcConnection::try_receive`]. Connecting is always blocking.
+    pub fn set_nonblocking(&mut self, nonblocking: bool) -> EncodingResult<()> {
+        self.stream.set_nonblocking(nonblocking)?;
+        self.nonblocking = nonblocking;
+        Ok(())
+    }
+
+    /// Underlying socket, e.g. for registering with `mio` or `poll`
+    pub fn stream(&self) -> &TcpStream {
+        &self.stream
+    }
+
+    /// Sets limits used when decoding server responses
+    pub fn set_decode_limits(&mut self, limits: DecodeLimits) {
+        self.limits = limits;
     }
 
     pub fn get_status(&mut self) -> CallResult<krpc_proto::Status> {
@@ -80,26 +129,306 @@ impl KrpcConnection {
             .map(Vessel::new)
     }
 
+    /// A dictionary of all celestial bodies (planets, moons, etc.) in the game,
+    /// keyed by the name of the body.
+    pub fn bodies(&mut self) -> CallResult<HashMap<String, CelestialBody>> {
+        self.call::<DictOf<String, Class>>("SpaceCenter", "get_Bodies", &[])
+            .map(|x| {
+                x.0.into_iter()
+                    .map(|(name, body)| (name, CelestialBody::new(body)))
+                    .collect()
+            })
+    }
+
+    /// Returns `true` if regular "on-rails" time warp can be used, at the
+    /// specified warp `factor`. The maximum time warp rate is limited by
+    /// various things, including how close the active vessel is to a planet.
+    /// See the KSP wiki for details.
+    ///
+    /// - `factor`: The warp factor to check.
+    pub fn can_rails_warp_at(&mut self, factor: i32) -> CallResult<bool> {
+        self.call("SpaceCenter", "CanRailsWarpAt", &[&SVarint(factor)])
+    }
+
+    /// Uses time acceleration to warp forward to a time in the future,
+    /// specified by universal time `ut`. This call blocks until the desired
+    /// time is reached. Uses regular "on-rails" or physical time warp as
+    /// appropriate. For example, physical time warp is used when the active
+    /// vessel is traveling through an atmosphere. When using regular "on-rails"
+    /// time warp, the warp rate is limited by `max_rails_rate`, and when using
+    /// physical time warp, the warp rate is limited by `max_physics_rate`.
+    ///
+    /// - `ut`: The universal time to warp to, in seconds.
+    /// - `max_rails_rate`: The maximum warp rate in regular "on-rails" time
+    ///   warp.
+    /// - `max_physics_rate`: The maximum warp rate in physical time warp.
+    pub fn warp_to(
+        &mut self,
+        ut: f64,
+        max_rails_rate: f32,
+        max_physics_rate: f32,
+    ) -> CallResult {
+        self.call(
+            "SpaceCenter",
+            "WarpTo",
+            &[&ut, &max_rails_rate, &max_physics_rate],
+        )
+    }
+
+    /// Warps to `lead_time` seconds before the maneuver `node`, using the
+    /// default kRPC rate limits. Blocks like [`KrpcConnection::warp_to`].
+    pub fn warp_to_node(&mut self, node: &Node, lead_time: f64) -> CallResult {
+        let ut = node.get_ut(self)?;
+        self.warp_to(ut - lead_time, 100_000.0, 2.0)
+    }
+
+    /// The current universal time in seconds.
+    pub fn get_ut(&mut self) -> CallResult<f64> {
+        self.call("SpaceCenter", "get_UT", &[])
+    }
+
+    /// The current time warp mode. Returns [`WarpMode::None`] if time warp is
+    /// not active, [`WarpMode::Rails`] if regular "on-rails" time warp is
+    /// active, or [`WarpMode::Physics`] if physical time warp is active.
+    pub fn get_warp_mode(&mut self) -> CallResult<WarpMode> {
+        self.call("SpaceCenter", "get_WarpMode", &[])
+    }
+
+    /// The current warp rate. This is the rate at which time is passing for
+    /// either on-rails or physical time warp. For example, a value of 10 means
+    /// time is passing 10x faster than normal. Returns 1 if time warp is not
+    /// active.
+    pub fn get_warp_rate(&mut self) -> CallResult<f32> {
+        self.call("SpaceCenter", "get_WarpRate", &[])
+    }
+
+    /// The current warp factor. This is the index of the rate at which time is
+    /// passing for either regular "on-rails" or physical time warp. Returns 0
+    /// if time warp is not active. When in on-rails time warp, this is equal to
+    /// [`KrpcConnection::get_rails_warp_factor`], and in physics time warp,
+    /// this is equal to [`KrpcConnection::get_physics_warp_factor`].
+    pub fn get_warp_factor(&mut self) -> CallResult<f32> {
+        self.call("SpaceCenter", "get_WarpFactor", &[])
+    }
+
+    /// The time warp rate, using regular "on-rails" time warp. A value between
+    /// 0 and 7 inclusive. 0 means no time warp. Returns 0 if physical time warp
+    /// is active.
+    ///
+    /// If requested time warp factor cannot be set, it will be set to the next
+    /// lowest possible value. For example, if the vessel is too close to a
+    /// planet. See the KSP wiki for details.
+    pub fn get_rails_warp_factor(&mut self) -> CallResult<i32> {
+        self.call::<SVarint<i32>>("SpaceCenter", "get_RailsWarpFactor", &[])
+            .map(|x| x.0)
+    }
+
+    /// The time warp rate, using regular "on-rails" time warp. A value between
+    /// 0 and 7 inclusive. 0 means no time warp. Returns 0 if physical time warp
+    /// is active.
+    ///
+    /// If requested time warp factor cannot be set, it will be set to the next
+    /// lowest possible value. For example, if the vessel is too close to a
+    /// planet. See the KSP wiki for details.
+    pub fn set_rails_warp_factor(&mut self, value: i32) -> CallResult {
+        self.call("SpaceCenter", "set_RailsWarpFactor", &[&SVarint(value)])
+    }
+
+    /// The physical time warp rate. A value between 0 and 3 inclusive. 0 means
+    /// no time warp. Returns 0 if regular "on-rails" time warp is active.
+    pub fn get_physics_warp_factor(&mut self) -> CallResult<i32> {
+        self.call::<SVarint<i32>>("SpaceCenter", "get_PhysicsWarpFactor", &[])
+            .map(|x| x.0)
+    }
+
+    /// The physical time warp rate. A value between 0 and 3 inclusive. 0 means
+    /// no time warp. Returns 0 if regular "on-rails" time warp is active.
+    pub fn set_physics_warp_factor(&mut self, value: i32) -> CallResult {
+        self.call("SpaceCenter", "set_PhysicsWarpFactor", &[&SVarint(value)])
+    }
+
+    /// The current maximum regular "on-rails" warp factor that can be set. A
+    /// value between 0 and 7 inclusive. See the KSP wiki for details.
+    pub fn get_maximum_rails_warp_factor(&mut self) -> CallResult<i32> {
+        self.call::<SVarint<i32>>(
+            "SpaceCenter",
+            "get_MaximumRailsWarpFactor",
+            &[],
+        )
+        .map(|x| x.0)
+    }
+
+    /// Returns a list of vessels from the given `craft_directory` that can be
+    /// launched.
+    ///
+    /// - `craft_directory`: Name of the directory in the current saves "Ships"
+    ///   directory. For example `"VAB"` or `"SPH"`.
+    pub fn launchable_vessels(
+        &mut self,
+        craft_directory: &str,
+    ) -> CallResult<Vec<String>> {
+        self.call::<ListOf<String>>(
+            "SpaceCenter",
+            "LaunchableVessels",
+            &[&craft_directory],
+        )
+        .map(|x| x.0)
+    }
+
+    /// Launch a vessel.
+    ///
+    /// - `craft_directory`: Name of the directory in the current saves "Ships"
+    ///   directory, that contains the craft file. For example `"VAB"` or
+    ///   `"SPH"`.
+    /// - `name`: Name of the vessel to launch. This is the name of the ".craft"
+    ///   file in the save directory, without the ".craft" file extension.
+    /// - `launch_site`: Name of the launch site. For example `"LaunchPad"` or
+    ///   `"Runway"`.
+    /// - `recover`: If true and there is a vessel on the launch site, recover
+    ///   it before launching.
+    ///
+    /// # Note
+    ///
+    /// Returns an error if any of the games pre-flight checks fail.
+    pub fn launch_vessel(
+        &mut self,
+        craft_directory: &str,
+        name: &str,
+        launch_site: &str,
+        recover: bool,
+    ) -> CallResult {
+        self.call(
+            "SpaceCenter",
+            "LaunchVessel",
+            &[&craft_directory, &name, &launch_site, &recover],
+        )
+    }
+
+    /// Launch a new vessel from the VAB onto the launchpad.
+    ///
+    /// - `name`: Name of the vessel to launch.
+    /// - `recover`: If true and there is a vessel on the launch pad, recover it
+    ///   before launching.
+    ///
+    /// # Note
+    ///
+    /// This is equivalent to calling [`KrpcConnection::launch_vessel`] with the
+    /// craft directory set to "VAB" and the launch site set to "LaunchPad".
+    /// Returns an error if any of the games pre-flight checks fail.
+    pub fn launch_vessel_from_vab(
+        &mut self,
+        name: &str,
+        recover: bool,
+    ) -> CallResult {
+        self.call("SpaceCenter", "LaunchVesselFromVAB", &[&name, &recover])
+    }
+
+    /// Launch a new vessel from the SPH onto the runway.
+    ///
+    /// - `name`: Name of the vessel to launch.
+    /// - `recover`: If true and there is a vessel on the runway, recover it
+    ///   before launching.
+    ///
+    /// # Note
+    ///
+    /// This is equivalent to calling [`KrpcConnection::launch_vessel`] with the
+    /// craft directory set to "SPH" and the launch site set to "Runway".
+    /// Returns an error if any of the games pre-flight checks fail.
+    pub fn launch_vessel_from_sph(
+        &mut self,
+        name: &str,
+        recover: bool,
+    ) -> CallResult {
+        self.call("SpaceCenter", "LaunchVesselFromSPH", &[&name, &recover])
+    }
+
+    /// Save the game with a given name. This will create a save file called
+    /// `name.sfs` in the folder of the current save game.
+    pub fn save(&mut self, name: &str) -> CallResult {
+        self.call("SpaceCenter", "Save", &[&name])
+    }
+
+    /// Load the game with the given name. This will create a load a save file
+    /// called `name.sfs` from the folder of the current save game.
+    pub fn load(&mut self, name: &str) -> CallResult {
+        self.call("SpaceCenter", "Load", &[&name])
+    }
+
+    /// Save a quicksave.
+    ///
+    /// # Note
+    ///
+    /// This is the same as calling [`KrpcConnection::save`] with the name
+    /// "quicksave".
+    pub fn quicksave(&mut self) -> CallResult {
+        self.call("SpaceCenter", "Quicksave", &[])
+    }
+
+    /// Load a quicksave.
+    ///
+    /// # Note
+    ///
+    /// This is the same as calling [`KrpcConnection::load`] with the name
+    /// "quicksave".
+    pub fn quickload(&mut self) -> CallResult {
+        self.call("SpaceCenter", "Quickload", &[])
+    }
+
+    /// An object that can be used to control the camera.
+    pub fn get_camera(&mut self) -> CallResult<Camera> {
+        self.call("SpaceCenter", "get_Camera", &[]).map(Camera::new)
+    }
+
     /// Performs a remote procedure call
     ///
     /// Returns double Result, because
     /// EncodingError is a connection error and
     /// krpc_proto::Error is a server error
+    ///
+    /// Responses arrive in order, so this fails without sending anything
+    /// while calls sent with [`KrpcConnection::send_call`] are pending.
+    /// Blocks until the response arrives, also with non-blocking socket.
     pub fn call<T: Decode>(
         &mut self,
         service: impl Into<String>,
         procedure: impl Into<String>,
         arguments: &[&dyn EncodeDyn],
     ) -> CallResult<T> {
-        let service = Some(service.into());
-        let procedure = Some(procedure.into());
+        if self.pending != 0 {
+            let msg = "Blocking call while responses are pending";
+            return Err(EncodingError::Io(std::io::Error::other(msg)).into());
+        }
+        self.send_call(service, procedure, arguments)?;
+        // Waits on socket instead of spinning on `WouldBlock`
+        self.blocking(|krpc| loop {
+            match krpc.try_receive() {
+                Ok(Some(value)) => break Ok(value),
+                Ok(None) => (),
+                Err(e) => break Err(e),
+            }
+        })?
+    }
+
+    /// Sends a procedure call without waiting for response,
+    /// which is then received with [`KrpcConnection::try_receive`]
+    ///
+    /// Blocks until the whole request is written, also with non-blocking
+    /// socket, as a partially written one would corrupt the stream.
+    pub fn send_call(
+        &mut self,
+        service: impl Into<String>,
+        procedure: impl Into<String>,
+        arguments: &[&dyn EncodeDyn],
+    ) -> EncodingResult<()> {
+        let service = service.into();
+        let procedure = procedure.into();
         let arguments = arguments
             .iter()
             .enumerate()
             .map(|(i, x)| {
                 Ok(Argument {
-                    position: Some(i as u32),
-                    value: Some(x.encode_to_vec()?),
+                    position: i as u32,
+                    value: x.encode_to_vec()?,
                 })
             })
             .collect::<EncodingResult<_>>()?;
@@ -107,23 +436,171 @@ impl KrpcConnection {
             service,
             procedure,
             arguments,
-            procedure_id: None,
-            service_id: None,
+            procedure_id: 0,
+            service_id: 0,
         };
         let request = Request { calls: vec![call] };
 
-        request.encode_with_len(&mut self.stream)?;
-        self.stream.flush().map_err(EncodingError::from)?;
-        let Response { error, mut results } =
-            Decode::decode_with_len(&mut self.stream)?;
+        let mut bytes = vec![];
+        request.encode_with_len(&mut bytes)?;
+        self.blocking(|krpc| {
+            krpc.stream.write_all(&bytes)?;
+            krpc.stream.flush()
+        })??;
+        self.pending += 1;
+        Ok(())
+    }
+
+    // Runs `f` with blocking socket, then restores non-blocking mode
+    fn blocking<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> T,
+    ) -> EncodingResult<T> {
+        if self.nonblocking {
+            self.stream.set_nonblocking(false)?;
+        }
+        let res = f(self);
+        if self.nonblocking {
+            self.stream.set_nonblocking(true)?;
+        }
+        Ok(res)
+    }
+
+    /// Reads available bytes and returns result of earliest call
+    /// sent with [`KrpcConnection::send_call`], if it fully arrived.
+    ///
+    /// With non-blocking socket (see [`KrpcConnection::set_nonblocking`])
+    /// this never blocks, and can be polled from an event loop.
+    pub fn try_receive<T: Decode>(&mut self) -> CallResult<Option<T>> {
+        let limits = self.limits;
+        let decoder = &mut self.decoder;
+        let mut response = limits.scope(|| decoder.decode::<Response>())?;
+        if response.is_none() {
+            Self::read_available(decoder, &mut self.stream)?;
+            response = limits.scope(|| decoder.decode::<Response>())?;
+        }
+        let response = match response {
+            Some(response) => response,
+            None => return Ok(None),
+        };
+        self.pending = self.pending.saturating_sub(1);
 
-            if let Some(error) = error {
+        let Response { error, mut results } = response;
+        if let Some(error) = error {
             return Err(error.into());
         }
+        if results.is_empty() {
+            let msg = "Response without results";
+            return Err(EncodingError::Io(std::io::Error::other(msg)).into());
+        }
         let result = results.remove(0);
         if let Some(error) = result.error {
             return Err(error.into());
         }
-        Ok(T::decode(result.value.as_deref().unwrap_or_default())?)
+        Ok(Some(limits.scope(|| T::decode(result.value.as_slice()))?))
+    }
+
+    // Does one read into decoder, treating `WouldBlock` as no data
+    fn read_available(
+        decoder: &mut FrameDecoder,
+        stream: &mut TcpStream,
+    ) -> EncodingResult<()> {
+        match decoder.read_from(stream) {
+            Ok(0) => {
+                let eof = std::io::ErrorKind::UnexpectedEof.into();
+                Err(EncodingError::Io(eof))
+            }
+            Ok(_) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+
+    use krpc_proto::ProcedureResult;
+
+    use super::*;
+
+    // Answers every call with its procedure name,
+    // starting to read calls after `delay`
+    fn serve(listener: TcpListener, delay: std::time::Duration) {
+        let (mut stream, _) = listener.accept().unwrap();
+        ConnectionRequest::decode_with_len(&mut stream).unwrap();
+        let crp = ConnectionResponse {
+            status: Status::Ok,
+            ..ConnectionResponse::default()
+        };
+        crp.encode_with_len(&mut stream).unwrap();
+        std::thread::sleep(delay);
+        while let Ok(request) = Request::decode_with_len(&mut stream) {
+            let results = request
+                .calls
+                .into_iter()
+                .map(|call| ProcedureResult {
+                    error: None,
+                    value: call.procedure.encode_to_vec().unwrap(),
+                })
+                .collect();
+            let response = Response {
+                error: None,
+                results,
+            };
+            response.encode_with_len(&mut stream).unwrap();
+        }
+    }
+
+    #[test]
+    fn pending_calls() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server =
+            std::thread::spawn(move || serve(listener, Default::default()));
+
+        let mut krpc = KrpcConnection::connect(addr, "test").unwrap();
+        krpc.set_nonblocking(true).unwrap();
+        assert_eq!(krpc.call::<String>("A", "First", &[]).unwrap(), "First");
+
+        krpc.send_call("A", "Second", &[]).unwrap();
+        assert!(krpc.call::<String>("A", "Third", &[]).is_err());
+        let second = loop {
+            if let Some(x) = krpc.try_receive::<String>().unwrap() {
+                break x;
+            }
+        };
+        assert_eq!(second, "Second");
+        assert_eq!(krpc.call::<String>("A", "Fourth", &[]).unwrap(), "Fourth");
+        assert!(krpc.try_receive::<String>().unwrap().is_none());
+
+        drop(krpc);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn full_send_buffer() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let delay = std::time::Duration::from_millis(200);
+        let server = std::thread::spawn(move || serve(listener, delay));
+
+        let mut krpc = KrpcConnection::connect(addr, "test").unwrap();
+        krpc.set_nonblocking(true).unwrap();
+        // Much more than socket buffers take while server isn't reading
+        let big = vec![0u8; 16 << 20];
+        krpc.send_call("A", "Big", &[&big]).unwrap();
+        krpc.send_call("A", "Small", &[]).unwrap();
+        let mut results = vec![];
+        while results.len() < 2 {
+            if let Some(x) = krpc.try_receive::<String>().unwrap() {
+                results.push(x);
+            }
+        }
+        assert_eq!(results, ["Big", "Small"]);
+
+        drop(krpc);
+        server.join().unwrap();
     }
 }
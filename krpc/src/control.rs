@@ -1,7 +1,86 @@
 use krpc_proto::List;
-use protobuf_but_worse::encoding::Varint;
+use protobuf_but_worse::encoding::{SVarint, Varint};
 
-use crate::{class::Class, CallResult, KrpcConnection};
+use crate::{
+    class::Class,
+    node::Node,
+    values::{krpc_enum, ListOf},
+    CallResult, KrpcConnection,
+};
+
+krpc_enum! {
+    /// The behavior of the SAS auto-pilot. See [`Control::get_sas_mode`].
+    pub enum SASMode {
+        /// Stability assist mode. Dampen out any rotation.
+        StabilityAssist = 0,
+        /// Point in the burn direction of the next maneuver node.
+        Maneuver = 1,
+        /// Point in the prograde direction.
+        Prograde = 2,
+        /// Point in the retrograde direction.
+        Retrograde = 3,
+        /// Point in the orbit normal direction.
+        Normal = 4,
+        /// Point in the orbit anti-normal direction.
+        AntiNormal = 5,
+        /// Point in the orbit radial direction.
+        Radial = 6,
+        /// Point in the orbit anti-radial direction.
+        AntiRadial = 7,
+        /// Point in the direction of the current target.
+        Target = 8,
+        /// Point away from the current target.
+        AntiTarget = 9,
+    }
+}
+
+krpc_enum! {
+    /// The mode of the speed reported in the navball.
+    /// See [`Control::get_speed_mode`].
+    pub enum SpeedMode {
+        /// Speed is relative to the vessel's orbit.
+        Orbit = 0,
+        /// Speed is relative to the surface of the body being orbited.
+        Surface = 1,
+        /// Speed is relative to the current target.
+        Target = 2,
+    }
+}
+
+krpc_enum! {
+    /// See [`Control::get_input_mode`].
+    pub enum ControlInputMode {
+        /// Control inputs are added to the vessels current control inputs.
+        Additive = 0,
+        /// Control inputs (when they are non-zero) override the vessels
+        /// current control inputs.
+        Override = 1,
+    }
+}
+
+krpc_enum! {
+    /// The control state of a vessel. See [`Control::get_state`].
+    pub enum ControlState {
+        /// Full controllable.
+        Full = 0,
+        /// Partially controllable.
+        Partial = 1,
+        /// Not controllable.
+        None = 2,
+    }
+}
+
+krpc_enum! {
+    /// The control source of a vessel. See [`Control::get_source`].
+    pub enum ControlSource {
+        /// Vessel is controlled by a Kerbal.
+        Kerbal = 0,
+        /// Vessel is controlled by a probe core.
+        Probe = 1,
+        /// Vessel is not controlled.
+        None = 2,
+    }
+}
 
 pub struct Control {
     class: Class,
@@ -30,7 +109,7 @@ impl Control {
         krpc.call("SpaceCenter", "Control_ActivateNextStage", &[&self.class])
     }
 
-    // Toggles the state of the given action group.
+    /// Toggles the state of the given action group.
     ///
     /// `group` is a number between 0 and 9 inclusive,
     /// or between 0 and 250 inclusive when the Extended Action Groups mod is
@@ -78,4 +157,653 @@ impl Control {
     ) -> CallResult {
         krpc.call("SpaceCenter", "Control_set_Gear", &[&self.class, &value])
     }
+
+    /// Returns `true` if the given action group is enabled.
+    ///
+    /// `group` is a number between 0 and 9 inclusive,
+    /// or between 0 and 250 inclusive when the Extended Action Groups mod is
+    /// installed.
+    pub fn get_action_group(
+        &self,
+        krpc: &mut KrpcConnection,
+        group: u32,
+    ) -> CallResult<bool> {
+        krpc.call(
+            "SpaceCenter",
+            "Control_GetActionGroup",
+            &[&self.class, &Varint(group)],
+        )
+    }
+
+    /// Sets the state of the given action group.
+    ///
+    /// `group` is a number between 0 and 9 inclusive,
+    /// or between 0 and 250 inclusive when the Extended Action Groups mod is
+    /// installed.
+    pub fn set_action_group(
+        &self,
+        krpc: &mut KrpcConnection,
+        group: u32,
+        state: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Control_SetActionGroup",
+            &[&self.class, &Varint(group), &state],
+        )
+    }
+
+    /// Creates a maneuver node at the given universal time, and returns a
+    /// `Node` object that can be used to modify it. Optionally sets the
+    /// magnitude of the delta-v for the maneuver node in the prograde, normal
+    /// and radial directions.
+    ///
+    /// - `ut`: Universal time of the maneuver node.
+    /// - `prograde`: Delta-v in the prograde direction.
+    /// - `normal`: Delta-v in the normal direction.
+    /// - `radial`: Delta-v in the radial direction.
+    pub fn add_node(
+        &self,
+        krpc: &mut KrpcConnection,
+        ut: f64,
+        prograde: f32,
+        normal: f32,
+        radial: f32,
+    ) -> CallResult<Node> {
+        krpc.call(
+            "SpaceCenter",
+            "Control_AddNode",
+            &[&self.class, &ut, &prograde, &normal, &radial],
+        )
+        .map(Node::new)
+    }
+
+    /// Remove all maneuver nodes.
+    pub fn remove_nodes(&self, krpc: &mut KrpcConnection) -> CallResult {
+        krpc.call("SpaceCenter", "Control_RemoveNodes", &[&self.class])
+    }
+
+    /// The control state of the vessel.
+    pub fn get_state(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<ControlState> {
+        krpc.call("SpaceCenter", "Control_get_State", &[&self.class])
+    }
+
+    /// The source of the vessels control, for example by a kerbal or a probe
+    /// core.
+    pub fn get_source(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<ControlSource> {
+        krpc.call("SpaceCenter", "Control_get_Source", &[&self.class])
+    }
+
+    /// The state of SAS.
+    ///
+    /// # Note
+    ///
+    /// Equivalent to `AutoPilot.SAS`
+    pub fn get_sas(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Control_get_SAS", &[&self.class])
+    }
+
+    /// The state of SAS.
+    ///
+    /// # Note
+    ///
+    /// Equivalent to `AutoPilot.SAS`
+    pub fn set_sas(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Control_set_SAS", &[&self.class, &value])
+    }
+
+    /// The current `SASMode`. These modes are equivalent to the mode buttons to
+    /// the left of the navball that appear when SAS is enabled.
+    ///
+    /// # Note
+    ///
+    /// Equivalent to `AutoPilot.SASMode`
+    pub fn get_sas_mode(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<SASMode> {
+        krpc.call("SpaceCenter", "Control_get_SASMode", &[&self.class])
+    }
+
+    /// The current `SASMode`. These modes are equivalent to the mode buttons to
+    /// the left of the navball that appear when SAS is enabled.
+    ///
+    /// # Note
+    ///
+    /// Equivalent to `AutoPilot.SASMode`
+    pub fn set_sas_mode(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: SASMode,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Control_set_SASMode", &[&self.class, &value])
+    }
+
+    /// The current `SpeedMode` of the navball. This is the mode displayed next
+    /// to the speed at the top of the navball.
+    pub fn get_speed_mode(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<SpeedMode> {
+        krpc.call("SpaceCenter", "Control_get_SpeedMode", &[&self.class])
+    }
+
+    /// The current `SpeedMode` of the navball. This is the mode displayed next
+    /// to the speed at the top of the navball.
+    pub fn set_speed_mode(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: SpeedMode,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Control_set_SpeedMode",
+            &[&self.class, &value],
+        )
+    }
+
+    /// The state of RCS.
+    pub fn get_rcs(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Control_get_RCS", &[&self.class])
+    }
+
+    /// The state of RCS.
+    pub fn set_rcs(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Control_set_RCS", &[&self.class, &value])
+    }
+
+    /// Returns whether all reactive wheels on the vessel are active, and sets
+    /// the active state of all reaction wheels. See `ReactionWheel.Active`.
+    pub fn get_reaction_wheels(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Control_get_ReactionWheels", &[&self.class])
+    }
+
+    /// Returns whether all reactive wheels on the vessel are active, and sets
+    /// the active state of all reaction wheels. See `ReactionWheel.Active`.
+    pub fn set_reaction_wheels(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Control_set_ReactionWheels",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Returns whether all landing legs on the vessel are deployed, and sets
+    /// the deployment state of all landing legs. Does not include wheels (for
+    /// example landing gear). See
+    /// [`Leg::get_deployed`](crate::Leg::get_deployed).
+    pub fn get_legs(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Control_get_Legs", &[&self.class])
+    }
+
+    /// Returns whether all landing legs on the vessel are deployed, and sets
+    /// the deployment state of all landing legs. Does not include wheels (for
+    /// example landing gear). See
+    /// [`Leg::get_deployed`](crate::Leg::get_deployed).
+    pub fn set_legs(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Control_set_Legs", &[&self.class, &value])
+    }
+
+    /// Returns whether all wheels on the vessel are deployed, and sets the
+    /// deployment state of all wheels. Does not include landing legs. See
+    /// `Wheel.Deployed`.
+    pub fn get_wheels(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Control_get_Wheels", &[&self.class])
+    }
+
+    /// Returns whether all wheels on the vessel are deployed, and sets the
+    /// deployment state of all wheels. Does not include landing legs. See
+    /// `Wheel.Deployed`.
+    pub fn set_wheels(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Control_set_Wheels", &[&self.class, &value])
+    }
+
+    /// The state of the lights.
+    pub fn get_lights(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Control_get_Lights", &[&self.class])
+    }
+
+    /// The state of the lights.
+    pub fn set_lights(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Control_set_Lights", &[&self.class, &value])
+    }
+
+    /// The state of the wheel brakes.
+    pub fn get_brakes(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Control_get_Brakes", &[&self.class])
+    }
+
+    /// The state of the wheel brakes.
+    pub fn set_brakes(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Control_set_Brakes", &[&self.class, &value])
+    }
+
+    /// Returns whether all antennas on the vessel are deployed, and sets the
+    /// deployment state of all antennas. See `Antenna.Deployed`.
+    pub fn get_antennas(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Control_get_Antennas", &[&self.class])
+    }
+
+    /// Returns whether all antennas on the vessel are deployed, and sets the
+    /// deployment state of all antennas. See `Antenna.Deployed`.
+    pub fn set_antennas(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Control_set_Antennas",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Returns whether any of the cargo bays on the vessel are open, and sets
+    /// the open state of all cargo bays. See `CargoBay.Open`.
+    pub fn get_cargo_bays(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Control_get_CargoBays", &[&self.class])
+    }
+
+    /// Returns whether any of the cargo bays on the vessel are open, and sets
+    /// the open state of all cargo bays. See `CargoBay.Open`.
+    pub fn set_cargo_bays(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Control_set_CargoBays",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Returns whether all of the air intakes on the vessel are open, and sets
+    /// the open state of all air intakes. See `Intake.Open`.
+    pub fn get_intakes(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Control_get_Intakes", &[&self.class])
+    }
+
+    /// Returns whether all of the air intakes on the vessel are open, and sets
+    /// the open state of all air intakes. See `Intake.Open`.
+    pub fn set_intakes(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Control_set_Intakes", &[&self.class, &value])
+    }
+
+    /// Returns whether all parachutes on the vessel are deployed, and sets the
+    /// deployment state of all parachutes. Cannot be set to `false`. See
+    /// `Parachute.Deployed`.
+    pub fn get_parachutes(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Control_get_Parachutes", &[&self.class])
+    }
+
+    /// Returns whether all parachutes on the vessel are deployed, and sets the
+    /// deployment state of all parachutes. Cannot be set to `false`. See
+    /// `Parachute.Deployed`.
+    pub fn set_parachutes(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Control_set_Parachutes",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Returns whether all radiators on the vessel are deployed, and sets the
+    /// deployment state of all radiators. See `Radiator.Deployed`.
+    pub fn get_radiators(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Control_get_Radiators", &[&self.class])
+    }
+
+    /// Returns whether all radiators on the vessel are deployed, and sets the
+    /// deployment state of all radiators. See `Radiator.Deployed`.
+    pub fn set_radiators(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Control_set_Radiators",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Returns whether all of the resource harvesters on the vessel are
+    /// deployed, and sets the deployment state of all resource harvesters. See
+    /// `ResourceHarvester.Deployed`.
+    pub fn get_resource_harvesters(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call(
+            "SpaceCenter",
+            "Control_get_ResourceHarvesters",
+            &[&self.class],
+        )
+    }
+
+    /// Returns whether all of the resource harvesters on the vessel are
+    /// deployed, and sets the deployment state of all resource harvesters. See
+    /// `ResourceHarvester.Deployed`.
+    pub fn set_resource_harvesters(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Control_set_ResourceHarvesters",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Returns whether any of the resource harvesters on the vessel are active,
+    /// and sets the active state of all resource harvesters. See
+    /// `ResourceHarvester.Active`.
+    pub fn get_resource_harvesters_active(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call(
+            "SpaceCenter",
+            "Control_get_ResourceHarvestersActive",
+            &[&self.class],
+        )
+    }
+
+    /// Returns whether any of the resource harvesters on the vessel are active,
+    /// and sets the active state of all resource harvesters. See
+    /// `ResourceHarvester.Active`.
+    pub fn set_resource_harvesters_active(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Control_set_ResourceHarvestersActive",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Returns whether all solar panels on the vessel are deployed, and sets
+    /// the deployment state of all solar panels. See `SolarPanel.Deployed`.
+    pub fn get_solar_panels(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Control_get_SolarPanels", &[&self.class])
+    }
+
+    /// Returns whether all solar panels on the vessel are deployed, and sets
+    /// the deployment state of all solar panels. See `SolarPanel.Deployed`.
+    pub fn set_solar_panels(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Control_set_SolarPanels",
+            &[&self.class, &value],
+        )
+    }
+
+    /// The state of the abort action group.
+    pub fn get_abort(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Control_get_Abort", &[&self.class])
+    }
+
+    /// The state of the abort action group.
+    pub fn set_abort(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Control_set_Abort", &[&self.class, &value])
+    }
+
+    /// Sets the behavior of the pitch, yaw, roll and translation control
+    /// inputs. When set to additive, these inputs are added to the vessels
+    /// current inputs. This mode is the default. When set to override, these
+    /// inputs (if non-zero) override the vessels inputs. This mode prevents
+    /// keyboard control, or SAS, from interfering with the controls when they
+    /// are set.
+    pub fn get_input_mode(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<ControlInputMode> {
+        krpc.call("SpaceCenter", "Control_get_InputMode", &[&self.class])
+    }
+
+    /// Sets the behavior of the pitch, yaw, roll and translation control
+    /// inputs. When set to additive, these inputs are added to the vessels
+    /// current inputs. This mode is the default. When set to override, these
+    /// inputs (if non-zero) override the vessels inputs. This mode prevents
+    /// keyboard control, or SAS, from interfering with the controls when they
+    /// are set.
+    pub fn set_input_mode(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: ControlInputMode,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Control_set_InputMode",
+            &[&self.class, &value],
+        )
+    }
+
+    /// The state of the pitch control. A value between -1 and 1. Equivalent to
+    /// the w and s keys.
+    pub fn get_pitch(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Control_get_Pitch", &[&self.class])
+    }
+
+    /// The state of the pitch control. A value between -1 and 1. Equivalent to
+    /// the w and s keys.
+    pub fn set_pitch(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Control_set_Pitch", &[&self.class, &value])
+    }
+
+    /// The state of the yaw control. A value between -1 and 1. Equivalent to
+    /// the a and d keys.
+    pub fn get_yaw(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Control_get_Yaw", &[&self.class])
+    }
+
+    /// The state of the yaw control. A value between -1 and 1. Equivalent to
+    /// the a and d keys.
+    pub fn set_yaw(&self, krpc: &mut KrpcConnection, value: f32) -> CallResult {
+        krpc.call("SpaceCenter", "Control_set_Yaw", &[&self.class, &value])
+    }
+
+    /// The state of the roll control. A value between -1 and 1. Equivalent to
+    /// the q and e keys.
+    pub fn get_roll(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Control_get_Roll", &[&self.class])
+    }
+
+    /// The state of the roll control. A value between -1 and 1. Equivalent to
+    /// the q and e keys.
+    pub fn set_roll(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Control_set_Roll", &[&self.class, &value])
+    }
+
+    /// The state of the forward translational control. A value between -1 and
+    /// 1. Equivalent to the h and n keys.
+    pub fn get_forward(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Control_get_Forward", &[&self.class])
+    }
+
+    /// The state of the forward translational control. A value between -1 and
+    /// 1. Equivalent to the h and n keys.
+    pub fn set_forward(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Control_set_Forward", &[&self.class, &value])
+    }
+
+    /// The state of the up translational control. A value between -1 and 1.
+    /// Equivalent to the i and k keys.
+    pub fn get_up(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Control_get_Up", &[&self.class])
+    }
+
+    /// The state of the up translational control. A value between -1 and 1.
+    /// Equivalent to the i and k keys.
+    pub fn set_up(&self, krpc: &mut KrpcConnection, value: f32) -> CallResult {
+        krpc.call("SpaceCenter", "Control_set_Up", &[&self.class, &value])
+    }
+
+    /// The state of the right translational control. A value between -1 and 1.
+    /// Equivalent to the j and l keys.
+    pub fn get_right(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Control_get_Right", &[&self.class])
+    }
+
+    /// The state of the right translational control. A value between -1 and 1.
+    /// Equivalent to the j and l keys.
+    pub fn set_right(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Control_set_Right", &[&self.class, &value])
+    }
+
+    /// The state of the wheel throttle. A value between -1 and 1. A value of 1
+    /// rotates the wheels forwards, a value of -1 rotates the wheels backwards.
+    pub fn get_wheel_throttle(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Control_get_WheelThrottle", &[&self.class])
+    }
+
+    /// The state of the wheel throttle. A value between -1 and 1. A value of 1
+    /// rotates the wheels forwards, a value of -1 rotates the wheels backwards.
+    pub fn set_wheel_throttle(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Control_set_WheelThrottle",
+            &[&self.class, &value],
+        )
+    }
+
+    /// The state of the wheel steering. A value between -1 and 1. A value of 1
+    /// steers to the left, and a value of -1 steers to the right.
+    pub fn get_wheel_steering(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Control_get_WheelSteering", &[&self.class])
+    }
+
+    /// The state of the wheel steering. A value between -1 and 1. A value of 1
+    /// steers to the left, and a value of -1 steers to the right.
+    pub fn set_wheel_steering(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Control_set_WheelSteering",
+            &[&self.class, &value],
+        )
+    }
+
+    /// The current stage of the vessel. Corresponds to the stage number in the
+    /// in-game UI.
+    pub fn get_current_stage(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<i32> {
+        krpc.call::<SVarint<i32>>(
+            "SpaceCenter",
+            "Control_get_CurrentStage",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// Returns a list of all existing maneuver nodes, ordered by time from
+    /// first to last.
+    pub fn get_nodes(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<Node>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Control_get_Nodes",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(Node::new).collect())
+    }
 }
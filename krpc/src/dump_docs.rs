@@ -5,8 +5,8 @@ use krpc_proto::{
     Services, Type,
 };
 
-fn clean_doc(doc: &Option<String>) -> String {
-    let mut doc = doc.as_deref().unwrap_or_default().to_string();
+fn clean_doc(doc: &str) -> String {
+    let mut doc = doc.to_string();
     doc = doc.replace("<doc>", "").replace("</doc>", "");
     doc = doc.replace("<summary>", "").replace("</summary>", "");
     doc = doc
@@ -35,23 +35,21 @@ fn clean_doc(doc: &Option<String>) -> String {
 fn print_class(class: &Class) -> String {
     let mut res = String::new();
     res += &clean_doc(&class.documentation);
-    writeln!(res, "struct {};", class.name.as_ref().unwrap()).unwrap();
+    writeln!(res, "struct {};", class.name).unwrap();
     res
 }
 
 fn print_enumeration_value(value: &EnumerationValue) -> String {
     let mut res = String::new();
     res += &clean_doc(&value.documentation);
-    let name = value.name.as_deref().unwrap();
-    let value = value.value.unwrap_or(0);
-    writeln!(res, "{} = {},", name, value).unwrap();
+    writeln!(res, "{} = {},", value.name, value.value).unwrap();
     res
 }
 
 fn print_enumeration(enumeration: &Enumeration) -> String {
     let mut res = String::new();
     res += &clean_doc(&enumeration.documentation);
-    writeln!(res, "enum {} {{", enumeration.name.as_ref().unwrap()).unwrap();
+    writeln!(res, "enum {} {{", enumeration.name).unwrap();
     for value in &enumeration.values {
         for line in print_enumeration_value(value).lines() {
             writeln!(res, "    {}", line).unwrap();
@@ -63,8 +61,7 @@ fn print_enumeration(enumeration: &Enumeration) -> String {
 
 fn print_type(r#type: &Type) -> String {
     let mut res = String::new();
-    let code = r#type.code.as_ref().unwrap();
-    match code {
+    match r#type.code {
         krpc_proto::r#type::TypeCode::Tuple => {
             assert!(r#type.types.len() >= 1);
             write!(res, "(").unwrap();
@@ -107,7 +104,7 @@ fn print_procedure(procedure: &Procedure) -> String {
 
     res += &clean_doc(&procedure.documentation);
 
-    let mut name = procedure.name.as_deref().unwrap();
+    let mut name = procedure.name.as_str();
     writeln!(res, "/// real name: {}", name).unwrap();
 
     // We have to do method name stripping here,
@@ -123,7 +120,7 @@ fn print_procedure(procedure: &Procedure) -> String {
         if has_this_parameter(procedure) {
             write!(res, "&self").unwrap();
         } else {
-            let name = match param.name.as_deref().unwrap().trim() {
+            let name = match param.name.trim() {
                 "type" => "r#type",
                 n => n,
             };
@@ -136,7 +133,7 @@ fn print_procedure(procedure: &Procedure) -> String {
             .unwrap();
         }
         for param in &procedure.parameters[1..] {
-            let name = match param.name.as_deref().unwrap().trim() {
+            let name = match param.name.trim() {
                 "type" => "r#type",
                 n => n,
             };
@@ -153,7 +150,7 @@ fn print_procedure(procedure: &Procedure) -> String {
 
     if let Some(ret) = procedure.return_type.as_ref() {
         let ret = print_type(&ret);
-        if matches!(procedure.return_is_nullable, Some(true)) {
+        if procedure.return_is_nullable {
             write!(res, " -> Option<{}>", ret).unwrap();
         } else {
             write!(res, " -> {}", ret).unwrap();
@@ -176,15 +173,14 @@ fn has_this_parameter(p: &Procedure) -> bool {
     p.parameters
         .first()
         .filter(|f| {
-            let name = f.name.as_deref();
-            let code = f.r#type.as_ref().and_then(|t| t.code);
-            name == Some("this") && code == Some(TypeCode::Class)
+            let code = f.r#type.as_ref().map(|t| t.code);
+            f.name == "this" && code == Some(TypeCode::Class)
         })
         .is_some()
 }
 
 fn is_static_method(p: &Procedure) -> bool {
-    p.name.as_deref().unwrap().contains("_static_")
+    p.name.contains("_static_")
 }
 
 // Turns list of procedures into list of class impl's + list of free procedures
@@ -196,7 +192,7 @@ fn declasser(
 
     for p in procedures {
         if has_this_parameter(p) || is_static_method(p) {
-            let name = p.name.as_ref().unwrap();
+            let name = &p.name;
             let (class_name, _) = name.split_at(name.find("_").unwrap());
             map.entry(class_name).or_default().push(p);
         } else {
@@ -249,7 +245,7 @@ pub fn dump_services_info(services: &Services) -> String {
     let mut res = String::new();
     for service in &services.services {
         res += &clean_doc(&service.documentation);
-        let service_name = service.name.as_deref().unwrap();
+        let service_name = &service.name;
         writeln!(res, "mod {} {{", service_name).unwrap();
         for proc in &service.classes {
             let text = print_class(proc);
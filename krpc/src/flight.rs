@@ -0,0 +1,587 @@
+use crate::{
+    celestial_body::CelestialBody,
+    class::Class,
+    values::{Quaternion, TupleOf, Vector3},
+    CallResult, KrpcConnection,
+};
+
+/// Used to get flight telemetry for a vessel, by calling
+/// [`Vessel::flight`]. All of the information returned by this class is given
+/// in the reference frame passed to that method.
+///
+/// # Note
+///
+/// To get orbital information, such as the apoapsis or inclination, see
+/// `Orbit`.
+///
+/// [`Vessel::flight`]: crate::Vessel::flight
+pub struct Flight {
+    class: Class,
+}
+
+impl Flight {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// Simulate and return the total aerodynamic forces acting on the vessel,
+    /// if it where to be traveling with the given velocity at the given
+    /// position in the atmosphere of the given celestial body.
+    ///
+    /// # Returns
+    ///
+    /// A vector pointing in the direction that the force acts, with its
+    /// magnitude equal to the strength of the force in Newtons.
+    pub fn simulate_aerodynamic_force_at(
+        &self,
+        krpc: &mut KrpcConnection,
+        body: &CelestialBody,
+        position: Vector3,
+        velocity: Vector3,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Flight_SimulateAerodynamicForceAt",
+            &[
+                &self.class,
+                &body.class,
+                &TupleOf(position),
+                &TupleOf(velocity),
+            ],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The current G force acting on the vessel in `g`.
+    pub fn get_g_force(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Flight_get_GForce", &[&self.class])
+    }
+
+    /// The altitude above sea level, in meters. Measured from the center of
+    /// mass of the vessel.
+    pub fn get_mean_altitude(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Flight_get_MeanAltitude", &[&self.class])
+    }
+
+    /// The altitude above the surface of the body or sea level, whichever is
+    /// closer, in meters. Measured from the center of mass of the vessel.
+    pub fn get_surface_altitude(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Flight_get_SurfaceAltitude", &[&self.class])
+    }
+
+    /// The altitude above the surface of the body, in meters. When over water,
+    /// this is the altitude above the sea floor. Measured from the center of
+    /// mass of the vessel.
+    pub fn get_bedrock_altitude(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Flight_get_BedrockAltitude", &[&self.class])
+    }
+
+    /// The elevation of the terrain under the vessel, in meters. This is the
+    /// height of the terrain above sea level, and is negative when the vessel
+    /// is over the sea.
+    pub fn get_elevation(&self, krpc: &mut KrpcConnection) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Flight_get_Elevation", &[&self.class])
+    }
+
+    /// The latitude of the vessel for the body being orbited, in degrees.
+    pub fn get_latitude(&self, krpc: &mut KrpcConnection) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Flight_get_Latitude", &[&self.class])
+    }
+
+    /// The longitude of the vessel for the body being orbited, in degrees.
+    pub fn get_longitude(&self, krpc: &mut KrpcConnection) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Flight_get_Longitude", &[&self.class])
+    }
+
+    /// The velocity of the vessel, in the reference frame passed to
+    /// [`Vessel::flight`](crate::Vessel::flight).
+    ///
+    /// # Returns
+    ///
+    /// The velocity as a vector. The vector points in the direction of travel,
+    /// and its magnitude is the speed of the vessel in meters per second.
+    pub fn get_velocity(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Flight_get_Velocity",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The speed of the vessel in meters per second, in the reference frame
+    /// passed to [`Vessel::flight`](crate::Vessel::flight).
+    pub fn get_speed(&self, krpc: &mut KrpcConnection) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Flight_get_Speed", &[&self.class])
+    }
+
+    /// The horizontal speed of the vessel in meters per second, in the
+    /// reference frame passed to [`Vessel::flight`](crate::Vessel::flight).
+    pub fn get_horizontal_speed(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Flight_get_HorizontalSpeed", &[&self.class])
+    }
+
+    /// The vertical speed of the vessel in meters per second, in the reference
+    /// frame passed to [`Vessel::flight`](crate::Vessel::flight).
+    pub fn get_vertical_speed(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Flight_get_VerticalSpeed", &[&self.class])
+    }
+
+    /// The position of the center of mass of the vessel, in the reference frame
+    /// passed to [`Vessel::flight`](crate::Vessel::flight)
+    ///
+    /// # Returns
+    ///
+    /// The position as a vector.
+    pub fn get_center_of_mass(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Flight_get_CenterOfMass",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The rotation of the vessel, in the reference frame passed to
+    /// [`Vessel::flight`](crate::Vessel::flight)
+    ///
+    /// # Returns
+    ///
+    /// The rotation as a quaternion of the form `(x, y, z, w)`.
+    pub fn get_rotation(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Quaternion> {
+        krpc.call::<TupleOf<Quaternion>>(
+            "SpaceCenter",
+            "Flight_get_Rotation",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The direction that the vessel is pointing in, in the reference frame
+    /// passed to [`Vessel::flight`](crate::Vessel::flight).
+    ///
+    /// # Returns
+    ///
+    /// The direction as a unit vector.
+    pub fn get_direction(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Flight_get_Direction",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The pitch of the vessel relative to the horizon, in degrees. A value
+    /// between -90° and +90°.
+    pub fn get_pitch(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Flight_get_Pitch", &[&self.class])
+    }
+
+    /// The heading of the vessel (its angle relative to north), in degrees. A
+    /// value between 0° and 360°.
+    pub fn get_heading(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Flight_get_Heading", &[&self.class])
+    }
+
+    /// The roll of the vessel relative to the horizon, in degrees. A value
+    /// between -180° and +180°.
+    pub fn get_roll(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Flight_get_Roll", &[&self.class])
+    }
+
+    /// The prograde direction of the vessels orbit, in the reference frame
+    /// passed to [`Vessel::flight`](crate::Vessel::flight).
+    ///
+    /// # Returns
+    ///
+    /// The direction as a unit vector.
+    pub fn get_prograde(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Flight_get_Prograde",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The retrograde direction of the vessels orbit, in the reference frame
+    /// passed to [`Vessel::flight`](crate::Vessel::flight).
+    ///
+    /// # Returns
+    ///
+    /// The direction as a unit vector.
+    pub fn get_retrograde(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Flight_get_Retrograde",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The direction normal to the vessels orbit, in the reference frame passed
+    /// to [`Vessel::flight`](crate::Vessel::flight).
+    ///
+    /// # Returns
+    ///
+    /// The direction as a unit vector.
+    pub fn get_normal(&self, krpc: &mut KrpcConnection) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Flight_get_Normal",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The direction opposite to the normal of the vessels orbit, in the
+    /// reference frame passed to [`Vessel::flight`](crate::Vessel::flight).
+    ///
+    /// # Returns
+    ///
+    /// The direction as a unit vector.
+    pub fn get_anti_normal(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Flight_get_AntiNormal",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The radial direction of the vessels orbit, in the reference frame passed
+    /// to [`Vessel::flight`](crate::Vessel::flight).
+    ///
+    /// # Returns
+    ///
+    /// The direction as a unit vector.
+    pub fn get_radial(&self, krpc: &mut KrpcConnection) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Flight_get_Radial",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The direction opposite to the radial direction of the vessels orbit, in
+    /// the reference frame passed to [`Vessel::flight`](crate::Vessel::flight).
+    ///
+    /// # Returns
+    ///
+    /// The direction as a unit vector.
+    pub fn get_anti_radial(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Flight_get_AntiRadial",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The current density of the atmosphere around the vessel, in `kg/m^3`.
+    pub fn get_atmosphere_density(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Flight_get_AtmosphereDensity",
+            &[&self.class],
+        )
+    }
+
+    /// The dynamic pressure acting on the vessel, in Pascals. This is a measure
+    /// of the strength of the aerodynamic forces. It is equal to
+    /// `1/2 * air density * velocity^2`. It is commonly denoted `Q`.
+    pub fn get_dynamic_pressure(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Flight_get_DynamicPressure", &[&self.class])
+    }
+
+    /// The static atmospheric pressure at mean sea level, in Pascals.
+    pub fn get_static_pressure_at_msl(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Flight_get_StaticPressureAtMSL",
+            &[&self.class],
+        )
+    }
+
+    /// The static atmospheric pressure acting on the vessel, in Pascals.
+    pub fn get_static_pressure(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Flight_get_StaticPressure", &[&self.class])
+    }
+
+    /// The total aerodynamic forces acting on the vessel, in reference frame
+    /// passed to [`Vessel::flight`](crate::Vessel::flight).
+    ///
+    /// # Returns
+    ///
+    /// A vector pointing in the direction that the force acts, with its
+    /// magnitude equal to the strength of the force in Newtons.
+    pub fn get_aerodynamic_force(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Flight_get_AerodynamicForce",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The aerodynamic lift currently acting on the vessel.
+    ///
+    /// # Returns
+    ///
+    /// A vector pointing in the direction that the force acts, with its
+    /// magnitude equal to the strength of the force in Newtons.
+    pub fn get_lift(&self, krpc: &mut KrpcConnection) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Flight_get_Lift",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The aerodynamic drag currently acting on the vessel.
+    ///
+    /// # Returns
+    ///
+    /// A vector pointing in the direction of the force, with its magnitude
+    /// equal to the strength of the force in Newtons.
+    pub fn get_drag(&self, krpc: &mut KrpcConnection) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Flight_get_Drag",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The speed of sound, in the atmosphere around the vessel, in `m/s`.
+    pub fn get_speed_of_sound(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Flight_get_SpeedOfSound", &[&self.class])
+    }
+
+    /// The speed of the vessel, in multiples of the speed of sound.
+    pub fn get_mach(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Flight_get_Mach", &[&self.class])
+    }
+
+    /// The vessels Reynolds number.
+    ///
+    /// # Note
+    ///
+    /// Requires Ferram Aerospace Research.
+    pub fn get_reynolds_number(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Flight_get_ReynoldsNumber", &[&self.class])
+    }
+
+    /// The true air speed of the vessel, in meters per second.
+    pub fn get_true_air_speed(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Flight_get_TrueAirSpeed", &[&self.class])
+    }
+
+    /// The equivalent air speed of the vessel, in meters per second.
+    pub fn get_equivalent_air_speed(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Flight_get_EquivalentAirSpeed",
+            &[&self.class],
+        )
+    }
+
+    /// An estimate of the current terminal velocity of the vessel, in meters
+    /// per second. This is the speed at which the drag forces cancel out the
+    /// force of gravity.
+    pub fn get_terminal_velocity(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Flight_get_TerminalVelocity", &[&self.class])
+    }
+
+    /// The pitch angle between the orientation of the vessel and its velocity
+    /// vector, in degrees.
+    pub fn get_angle_of_attack(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Flight_get_AngleOfAttack", &[&self.class])
+    }
+
+    /// The yaw angle between the orientation of the vessel and its velocity
+    /// vector, in degrees.
+    pub fn get_sideslip_angle(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Flight_get_SideslipAngle", &[&self.class])
+    }
+
+    /// The total air temperature of the atmosphere around the vessel, in
+    /// Kelvin. This includes the [`Flight::get_static_air_temperature`] and the
+    /// vessel's kinetic energy.
+    pub fn get_total_air_temperature(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Flight_get_TotalAirTemperature",
+            &[&self.class],
+        )
+    }
+
+    /// The static (ambient) temperature of the atmosphere around the vessel, in
+    /// Kelvin.
+    pub fn get_static_air_temperature(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Flight_get_StaticAirTemperature",
+            &[&self.class],
+        )
+    }
+
+    /// The current amount of stall, between 0 and 1. A value greater than 0.005
+    /// indicates a minor stall and a value greater than 0.5 indicates a large-
+    /// scale stall.
+    ///
+    /// # Note
+    ///
+    /// Requires Ferram Aerospace Research.
+    pub fn get_stall_fraction(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Flight_get_StallFraction", &[&self.class])
+    }
+
+    /// The coefficient of drag. This is the amount of drag produced by the
+    /// vessel. It depends on air speed, air density and wing area.
+    ///
+    /// # Note
+    ///
+    /// Requires Ferram Aerospace Research.
+    pub fn get_drag_coefficient(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Flight_get_DragCoefficient", &[&self.class])
+    }
+
+    /// The coefficient of lift. This is the amount of lift produced by the
+    /// vessel, and depends on air speed, air density and wing area.
+    ///
+    /// # Note
+    ///
+    /// Requires Ferram Aerospace Research.
+    pub fn get_lift_coefficient(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Flight_get_LiftCoefficient", &[&self.class])
+    }
+
+    /// The ballistic coefficient.
+    ///
+    /// # Note
+    ///
+    /// Requires Ferram Aerospace Research.
+    pub fn get_ballistic_coefficient(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Flight_get_BallisticCoefficient",
+            &[&self.class],
+        )
+    }
+
+    /// The thrust specific fuel consumption for the jet engines on the vessel.
+    /// This is a measure of the efficiency of the engines, with a lower value
+    /// indicating a more efficient vessel. This value is the number of Newtons
+    /// of fuel that are burned, per hour, to produce one newton of thrust.
+    ///
+    /// # Note
+    ///
+    /// Requires Ferram Aerospace Research.
+    pub fn get_thrust_specific_fuel_consumption(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Flight_get_ThrustSpecificFuelConsumption",
+            &[&self.class],
+        )
+    }
+}
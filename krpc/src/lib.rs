@@ -1,14 +1,40 @@
+mod auto_pilot;
+mod camera;
+mod celestial_body;
 mod class;
 mod connection;
 mod control;
 mod dump_docs;
+mod flight;
+mod node;
+mod orbit;
+mod parts;
+mod reference_frame;
+mod resources;
+mod values;
 mod vessel;
 
 use krpc_proto::Error as ProtoError;
 use protobuf_but_worse::encoding::EncodingError;
 
-pub use connection::KrpcConnection;
-pub use control::Control;
+pub use auto_pilot::AutoPilot;
+pub use camera::{Camera, CameraMode};
+pub use celestial_body::CelestialBody;
+pub use connection::{KrpcConnection, WarpMode};
+pub use control::{
+    Control, ControlInputMode, ControlSource, ControlState, SASMode, SpeedMode,
+};
+pub use flight::Flight;
+pub use node::Node;
+pub use orbit::Orbit;
+pub use parts::{
+    ControlSurface, Decoupler, DockingPort, DockingPortState, Engine, Fairing,
+    Leg, LegState, Light, Module, MotorState, Parachute, ParachuteState, Part,
+    Parts, ReactionWheel, SolarPanel, SolarPanelState, Wheel, WheelState, RCS,
+};
+pub use reference_frame::ReferenceFrame;
+pub use resources::{Resource, ResourceFlowMode, ResourceTransfer, Resources};
+pub use values::{Quaternion, Vector3};
 pub use vessel::Vessel;
 
 pub use dump_docs::dump_services_info;
@@ -0,0 +1,261 @@
+use crate::{
+    class::Class,
+    orbit::Orbit,
+    reference_frame::ReferenceFrame,
+    values::{TupleOf, Vector3},
+    CallResult, KrpcConnection,
+};
+
+/// Represents a maneuver node. Can be created using [`Control::add_node`].
+///
+/// [`Control::add_node`]: crate::Control::add_node
+pub struct Node {
+    pub(crate) class: Class,
+}
+
+impl Node {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// Returns the burn vector for the maneuver node.
+    ///
+    /// - `reference_frame`: The reference frame that the returned vector is in.
+    ///   Defaults to [`Vessel::get_orbital_reference_frame`](
+    ///   crate::Vessel::get_orbital_reference_frame).
+    ///
+    /// # Returns
+    ///
+    /// A vector whose direction is the direction of the maneuver node burn, and
+    /// magnitude is the delta-v of the burn in meters per second.
+    ///
+    /// # Note
+    ///
+    /// Does not change when executing the maneuver node. See
+    /// [`Node::remaining_burn_vector`].
+    pub fn burn_vector(
+        &self,
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Node_BurnVector",
+            &[&self.class, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// Returns the remaining burn vector for the maneuver node.
+    ///
+    /// - `reference_frame`: The reference frame that the returned vector is in.
+    ///   Defaults to [`Vessel::get_orbital_reference_frame`](
+    ///   crate::Vessel::get_orbital_reference_frame).
+    ///
+    /// # Returns
+    ///
+    /// A vector whose direction is the direction of the maneuver node burn, and
+    /// magnitude is the delta-v of the burn in meters per second.
+    ///
+    /// # Note
+    ///
+    /// Changes as the maneuver node is executed. See [`Node::burn_vector`].
+    pub fn remaining_burn_vector(
+        &self,
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Node_RemainingBurnVector",
+            &[&self.class, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// Removes the maneuver node.
+    pub fn remove(&self, krpc: &mut KrpcConnection) -> CallResult {
+        krpc.call("SpaceCenter", "Node_Remove", &[&self.class])
+    }
+
+    /// The position vector of the maneuver node in the given reference frame.
+    ///
+    /// - `reference_frame`: The reference frame that the returned position
+    ///   vector is in.
+    ///
+    /// # Returns
+    ///
+    /// The position as a vector.
+    pub fn position(
+        &self,
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Node_Position",
+            &[&self.class, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The direction of the maneuver nodes burn.
+    ///
+    /// - `reference_frame`: The reference frame that the returned direction is
+    ///   in.
+    ///
+    /// # Returns
+    ///
+    /// The direction as a unit vector.
+    pub fn direction(
+        &self,
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Node_Direction",
+            &[&self.class, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The magnitude of the maneuver nodes delta-v in the prograde direction,
+    /// in meters per second.
+    pub fn get_prograde(&self, krpc: &mut KrpcConnection) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Node_get_Prograde", &[&self.class])
+    }
+
+    /// The magnitude of the maneuver nodes delta-v in the prograde direction,
+    /// in meters per second.
+    pub fn set_prograde(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f64,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Node_set_Prograde", &[&self.class, &value])
+    }
+
+    /// The magnitude of the maneuver nodes delta-v in the normal direction, in
+    /// meters per second.
+    pub fn get_normal(&self, krpc: &mut KrpcConnection) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Node_get_Normal", &[&self.class])
+    }
+
+    /// The magnitude of the maneuver nodes delta-v in the normal direction, in
+    /// meters per second.
+    pub fn set_normal(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f64,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Node_set_Normal", &[&self.class, &value])
+    }
+
+    /// The magnitude of the maneuver nodes delta-v in the radial direction, in
+    /// meters per second.
+    pub fn get_radial(&self, krpc: &mut KrpcConnection) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Node_get_Radial", &[&self.class])
+    }
+
+    /// The magnitude of the maneuver nodes delta-v in the radial direction, in
+    /// meters per second.
+    pub fn set_radial(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f64,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Node_set_Radial", &[&self.class, &value])
+    }
+
+    /// The delta-v of the maneuver node, in meters per second.
+    ///
+    /// # Note
+    ///
+    /// Does not change when executing the maneuver node. See
+    /// [`Node::get_remaining_delta_v`].
+    pub fn get_delta_v(&self, krpc: &mut KrpcConnection) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Node_get_DeltaV", &[&self.class])
+    }
+
+    /// The delta-v of the maneuver node, in meters per second.
+    ///
+    /// # Note
+    ///
+    /// Does not change when executing the maneuver node. See
+    /// [`Node::get_remaining_delta_v`].
+    pub fn set_delta_v(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f64,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Node_set_DeltaV", &[&self.class, &value])
+    }
+
+    /// Gets the remaining delta-v of the maneuver node, in meters per second.
+    /// Changes as the node is executed. This is equivalent to the delta-v
+    /// reported in-game.
+    pub fn get_remaining_delta_v(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Node_get_RemainingDeltaV", &[&self.class])
+    }
+
+    /// The universal time at which the maneuver will occur, in seconds.
+    pub fn get_ut(&self, krpc: &mut KrpcConnection) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Node_get_UT", &[&self.class])
+    }
+
+    /// The universal time at which the maneuver will occur, in seconds.
+    pub fn set_ut(&self, krpc: &mut KrpcConnection, value: f64) -> CallResult {
+        krpc.call("SpaceCenter", "Node_set_UT", &[&self.class, &value])
+    }
+
+    /// The time until the maneuver node will be encountered, in seconds.
+    pub fn get_time_to(&self, krpc: &mut KrpcConnection) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Node_get_TimeTo", &[&self.class])
+    }
+
+    /// The orbit that results from executing the maneuver node.
+    pub fn get_orbit(&self, krpc: &mut KrpcConnection) -> CallResult<Orbit> {
+        krpc.call("SpaceCenter", "Node_get_Orbit", &[&self.class])
+            .map(Orbit::new)
+    }
+
+    /// The reference frame that is fixed relative to the maneuver node's burn.
+    ///
+    /// - The origin is at the position of the maneuver node.
+    /// - The y-axis points in the direction of the burn.
+    /// - The x-axis and z-axis point in arbitrary but fixed directions.
+    pub fn get_reference_frame(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<ReferenceFrame> {
+        krpc.call("SpaceCenter", "Node_get_ReferenceFrame", &[&self.class])
+            .map(ReferenceFrame::new)
+    }
+
+    /// The reference frame that is fixed relative to the maneuver node, and
+    /// orientated with the orbital prograde/normal/radial directions of the
+    /// original orbit at the maneuver node's position.
+    ///
+    /// - The origin is at the position of the maneuver node.
+    /// - The x-axis points in the orbital anti-radial direction of the original
+    ///   orbit, at the position of the maneuver node.
+    /// - The y-axis points in the orbital prograde direction of the original
+    ///   orbit, at the position of the maneuver node.
+    /// - The z-axis points in the orbital normal direction of the original
+    ///   orbit, at the position of the maneuver node.
+    pub fn get_orbital_reference_frame(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<ReferenceFrame> {
+        krpc.call(
+            "SpaceCenter",
+            "Node_get_OrbitalReferenceFrame",
+            &[&self.class],
+        )
+        .map(ReferenceFrame::new)
+    }
+}
@@ -0,0 +1,525 @@
+use protobuf_but_worse::encoding::SVarint;
+
+use crate::{
+    celestial_body::CelestialBody,
+    class::Class,
+    reference_frame::ReferenceFrame,
+    values::{ListOf, TupleOf, Vector3},
+    CallResult, KrpcConnection,
+};
+
+/// Describes an orbit. For example, the orbit of a vessel, obtained by
+/// calling [`Vessel::get_orbit`], or a celestial body, obtained by calling
+/// [`CelestialBody::get_orbit`].
+///
+/// [`Vessel::get_orbit`]: crate::Vessel::get_orbit
+pub struct Orbit {
+    pub(crate) class: Class,
+}
+
+impl Orbit {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// The direction that is normal to the orbits reference plane, in the given
+    /// reference frame. The reference plane is the plane from which the orbits
+    /// inclination is measured.
+    ///
+    /// - `reference_frame`: The reference frame that the returned direction is
+    ///   in.
+    ///
+    /// # Returns
+    ///
+    /// The direction as a unit vector.
+    pub fn reference_plane_normal(
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Orbit_static_ReferencePlaneNormal",
+            &[&reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The direction from which the orbits longitude of ascending node is
+    /// measured, in the given reference frame.
+    ///
+    /// - `reference_frame`: The reference frame that the returned direction is
+    ///   in.
+    ///
+    /// # Returns
+    ///
+    /// The direction as a unit vector.
+    pub fn reference_plane_direction(
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Orbit_static_ReferencePlaneDirection",
+            &[&reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The mean anomaly at the given time.
+    ///
+    /// - `ut`: The universal time in seconds.
+    pub fn mean_anomaly_at_ut(
+        &self,
+        krpc: &mut KrpcConnection,
+        ut: f64,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_MeanAnomalyAtUT", &[&self.class, &ut])
+    }
+
+    /// The orbital radius at the point in the orbit given by the true anomaly.
+    ///
+    /// - `true_anomaly`: The true anomaly.
+    pub fn radius_at_true_anomaly(
+        &self,
+        krpc: &mut KrpcConnection,
+        true_anomaly: f64,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "Orbit_RadiusAtTrueAnomaly",
+            &[&self.class, &true_anomaly],
+        )
+    }
+
+    /// The true anomaly at the given orbital radius.
+    ///
+    /// - `radius`: The orbital radius in meters.
+    pub fn true_anomaly_at_radius(
+        &self,
+        krpc: &mut KrpcConnection,
+        radius: f64,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "Orbit_TrueAnomalyAtRadius",
+            &[&self.class, &radius],
+        )
+    }
+
+    /// The true anomaly at the given time.
+    ///
+    /// - `ut`: The universal time in seconds.
+    pub fn true_anomaly_at_ut(
+        &self,
+        krpc: &mut KrpcConnection,
+        ut: f64,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_TrueAnomalyAtUT", &[&self.class, &ut])
+    }
+
+    /// The universal time, in seconds, corresponding to the given true anomaly.
+    ///
+    /// - `true_anomaly`: True anomaly.
+    pub fn ut_at_true_anomaly(
+        &self,
+        krpc: &mut KrpcConnection,
+        true_anomaly: f64,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "Orbit_UTAtTrueAnomaly",
+            &[&self.class, &true_anomaly],
+        )
+    }
+
+    /// The eccentric anomaly at the given universal time.
+    ///
+    /// - `ut`: The universal time, in seconds.
+    pub fn eccentric_anomaly_at_ut(
+        &self,
+        krpc: &mut KrpcConnection,
+        ut: f64,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "Orbit_EccentricAnomalyAtUT",
+            &[&self.class, &ut],
+        )
+    }
+
+    /// The orbital speed at the given time, in meters per second.
+    ///
+    /// - `time`: Time from now, in seconds.
+    pub fn orbital_speed_at(
+        &self,
+        krpc: &mut KrpcConnection,
+        time: f64,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_OrbitalSpeedAt", &[&self.class, &time])
+    }
+
+    /// The orbital radius at the given time, in meters.
+    ///
+    /// - `ut`: The universal time to measure the radius at.
+    pub fn radius_at(
+        &self,
+        krpc: &mut KrpcConnection,
+        ut: f64,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_RadiusAt", &[&self.class, &ut])
+    }
+
+    /// The position at a given time, in the specified reference frame.
+    ///
+    /// - `ut`: The universal time to measure the position at.
+    /// - `reference_frame`: The reference frame that the returned position
+    ///   vector is in.
+    ///
+    /// # Returns
+    ///
+    /// The position as a vector.
+    pub fn position_at(
+        &self,
+        krpc: &mut KrpcConnection,
+        ut: f64,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Orbit_PositionAt",
+            &[&self.class, &ut, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// Estimates and returns the time at closest approach to a target orbit.
+    ///
+    /// - `target`: Target orbit.
+    ///
+    /// # Returns
+    ///
+    /// The universal time at closest approach, in seconds.
+    pub fn time_of_closest_approach(
+        &self,
+        krpc: &mut KrpcConnection,
+        target: &Orbit,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "Orbit_TimeOfClosestApproach",
+            &[&self.class, &target.class],
+        )
+    }
+
+    /// Estimates and returns the distance at closest approach to a target
+    /// orbit, in meters.
+    ///
+    /// - `target`: Target orbit.
+    pub fn distance_at_closest_approach(
+        &self,
+        krpc: &mut KrpcConnection,
+        target: &Orbit,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "Orbit_DistanceAtClosestApproach",
+            &[&self.class, &target.class],
+        )
+    }
+
+    /// Returns the times at closest approach and corresponding distances, to a
+    /// target orbit.
+    ///
+    /// - `target`: Target orbit.
+    /// - `orbits`: The number of future orbits to search.
+    ///
+    /// # Returns
+    ///
+    /// A list of two lists. The first is a list of times at closest approach,
+    /// as universal times in seconds. The second is a list of corresponding
+    /// distances at closest approach, in meters.
+    pub fn list_closest_approaches(
+        &self,
+        krpc: &mut KrpcConnection,
+        target: &Orbit,
+        orbits: i32,
+    ) -> CallResult<Vec<Vec<f64>>> {
+        krpc.call::<ListOf<ListOf<f64>>>(
+            "SpaceCenter",
+            "Orbit_ListClosestApproaches",
+            &[&self.class, &target.class, &SVarint(orbits)],
+        )
+        .map(|x| x.0.into_iter().map(|x| x.0).collect())
+    }
+
+    /// The true anomaly of the ascending node with the given target orbit.
+    ///
+    /// - `target`: Target orbit.
+    pub fn true_anomaly_at_an(
+        &self,
+        krpc: &mut KrpcConnection,
+        target: &Orbit,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "Orbit_TrueAnomalyAtAN",
+            &[&self.class, &target.class],
+        )
+    }
+
+    /// The true anomaly of the descending node with the given target orbit.
+    ///
+    /// - `target`: Target orbit.
+    pub fn true_anomaly_at_dn(
+        &self,
+        krpc: &mut KrpcConnection,
+        target: &Orbit,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "Orbit_TrueAnomalyAtDN",
+            &[&self.class, &target.class],
+        )
+    }
+
+    /// Relative inclination of this orbit and the target orbit, in radians.
+    ///
+    /// - `target`: Target orbit.
+    pub fn relative_inclination(
+        &self,
+        krpc: &mut KrpcConnection,
+        target: &Orbit,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "Orbit_RelativeInclination",
+            &[&self.class, &target.class],
+        )
+    }
+
+    /// The celestial body (e.g. planet or moon) around which the object is
+    /// orbiting.
+    pub fn get_body(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<CelestialBody> {
+        krpc.call("SpaceCenter", "Orbit_get_Body", &[&self.class])
+            .map(CelestialBody::new)
+    }
+
+    /// Gets the apoapsis of the orbit, in meters, from the center of mass of
+    /// the body being orbited.
+    ///
+    /// # Note
+    ///
+    /// For the apoapsis altitude reported on the in-game map view, use
+    /// [`Orbit::get_apoapsis_altitude`].
+    pub fn get_apoapsis(&self, krpc: &mut KrpcConnection) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_get_Apoapsis", &[&self.class])
+    }
+
+    /// The periapsis of the orbit, in meters, from the center of mass of the
+    /// body being orbited.
+    ///
+    /// # Note
+    ///
+    /// For the periapsis altitude reported on the in-game map view, use
+    /// [`Orbit::get_periapsis_altitude`].
+    pub fn get_periapsis(&self, krpc: &mut KrpcConnection) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_get_Periapsis", &[&self.class])
+    }
+
+    /// The apoapsis of the orbit, in meters, above the sea level of the body
+    /// being orbited.
+    ///
+    /// # Note
+    ///
+    /// This is equal to [`Orbit::get_apoapsis`] minus the equatorial radius of
+    /// the body.
+    pub fn get_apoapsis_altitude(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_get_ApoapsisAltitude", &[&self.class])
+    }
+
+    /// The periapsis of the orbit, in meters, above the sea level of the body
+    /// being orbited.
+    ///
+    /// # Note
+    ///
+    /// This is equal to [`Orbit::get_periapsis`] minus the equatorial radius of
+    /// the body.
+    pub fn get_periapsis_altitude(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_get_PeriapsisAltitude", &[&self.class])
+    }
+
+    /// The semi-major axis of the orbit, in meters.
+    pub fn get_semi_major_axis(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_get_SemiMajorAxis", &[&self.class])
+    }
+
+    /// The semi-minor axis of the orbit, in meters.
+    pub fn get_semi_minor_axis(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_get_SemiMinorAxis", &[&self.class])
+    }
+
+    /// The current radius of the orbit, in meters. This is the distance between
+    /// the center of mass of the object in orbit, and the center of mass of the
+    /// body around which it is orbiting.
+    ///
+    /// # Note
+    ///
+    /// This value will change over time if the orbit is elliptical.
+    pub fn get_radius(&self, krpc: &mut KrpcConnection) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_get_Radius", &[&self.class])
+    }
+
+    /// The current orbital speed of the object in meters per second.
+    ///
+    /// # Note
+    ///
+    /// This value will change over time if the orbit is elliptical.
+    pub fn get_speed(&self, krpc: &mut KrpcConnection) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_get_Speed", &[&self.class])
+    }
+
+    /// The orbital period, in seconds.
+    pub fn get_period(&self, krpc: &mut KrpcConnection) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_get_Period", &[&self.class])
+    }
+
+    /// The time until the object reaches apoapsis, in seconds.
+    pub fn get_time_to_apoapsis(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_get_TimeToApoapsis", &[&self.class])
+    }
+
+    /// The time until the object reaches periapsis, in seconds.
+    pub fn get_time_to_periapsis(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_get_TimeToPeriapsis", &[&self.class])
+    }
+
+    /// The eccentricity of the orbit.
+    pub fn get_eccentricity(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_get_Eccentricity", &[&self.class])
+    }
+
+    /// The inclination of the orbit, in radians.
+    pub fn get_inclination(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_get_Inclination", &[&self.class])
+    }
+
+    /// The longitude of the ascending node, in radians.
+    pub fn get_longitude_of_ascending_node(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "Orbit_get_LongitudeOfAscendingNode",
+            &[&self.class],
+        )
+    }
+
+    /// The argument of periapsis, in radians.
+    pub fn get_argument_of_periapsis(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "Orbit_get_ArgumentOfPeriapsis",
+            &[&self.class],
+        )
+    }
+
+    /// The mean anomaly at epoch.
+    pub fn get_mean_anomaly_at_epoch(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call(
+            "SpaceCenter",
+            "Orbit_get_MeanAnomalyAtEpoch",
+            &[&self.class],
+        )
+    }
+
+    /// The time since the epoch (the point at which the mean anomaly at epoch
+    /// was measured, in seconds.
+    pub fn get_epoch(&self, krpc: &mut KrpcConnection) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_get_Epoch", &[&self.class])
+    }
+
+    /// The mean anomaly.
+    pub fn get_mean_anomaly(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_get_MeanAnomaly", &[&self.class])
+    }
+
+    /// The eccentric anomaly.
+    pub fn get_eccentric_anomaly(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_get_EccentricAnomaly", &[&self.class])
+    }
+
+    /// The true anomaly.
+    pub fn get_true_anomaly(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_get_TrueAnomaly", &[&self.class])
+    }
+
+    /// If the object is going to change sphere of influence in the future,
+    /// returns the new orbit after the change. Otherwise returns `None`.
+    pub fn get_next_orbit(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Option<Orbit>> {
+        krpc.call::<Class>("SpaceCenter", "Orbit_get_NextOrbit", &[&self.class])
+            .map(|x| x.into_option().map(Orbit::new))
+    }
+
+    /// The time until the object changes sphere of influence, in seconds.
+    /// Returns `NaN` if the object is not going to change sphere of influence.
+    pub fn get_time_to_soi_change(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_get_TimeToSOIChange", &[&self.class])
+    }
+
+    /// The current orbital speed in meters per second.
+    pub fn get_orbital_speed(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Orbit_get_OrbitalSpeed", &[&self.class])
+    }
+}
@@ -0,0 +1,343 @@
+mod control_surface;
+mod decoupler;
+mod docking_port;
+mod engine;
+mod fairing;
+mod leg;
+mod light;
+mod module;
+mod parachute;
+mod part;
+mod rcs;
+mod reaction_wheel;
+mod solar_panel;
+mod wheel;
+
+use protobuf_but_worse::encoding::SVarint;
+
+use crate::{class::Class, values::ListOf, CallResult, KrpcConnection};
+
+pub use control_surface::ControlSurface;
+pub use decoupler::Decoupler;
+pub use docking_port::{DockingPort, DockingPortState};
+pub use engine::Engine;
+pub use fairing::Fairing;
+pub use leg::{Leg, LegState};
+pub use light::Light;
+pub use module::Module;
+pub use parachute::{Parachute, ParachuteState};
+pub use part::Part;
+pub use rcs::RCS;
+pub use reaction_wheel::ReactionWheel;
+pub use solar_panel::{SolarPanel, SolarPanelState};
+pub use wheel::{MotorState, Wheel, WheelState};
+
+/// Instances of this class are used to interact with the parts of a vessel. An
+/// instance can be obtained by calling
+/// [`Vessel::get_parts`](crate::Vessel::get_parts).
+pub struct Parts {
+    pub(crate) class: Class,
+}
+
+impl Parts {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// A list of parts whose [`Part::get_name`](crate::Part::get_name) is
+    /// `name`.
+    pub fn with_name(
+        &self,
+        krpc: &mut KrpcConnection,
+        name: &str,
+    ) -> CallResult<Vec<Part>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_WithName",
+            &[&self.class, &name],
+        )
+        .map(|x| x.0.into_iter().map(Part::new).collect())
+    }
+
+    /// A list of all parts whose [`Part::get_title`](crate::Part::get_title) is
+    /// `title`.
+    pub fn with_title(
+        &self,
+        krpc: &mut KrpcConnection,
+        title: &str,
+    ) -> CallResult<Vec<Part>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_WithTitle",
+            &[&self.class, &title],
+        )
+        .map(|x| x.0.into_iter().map(Part::new).collect())
+    }
+
+    /// A list of all parts whose [`Part::get_tag`](crate::Part::get_tag) is
+    /// `tag`.
+    pub fn with_tag(
+        &self,
+        krpc: &mut KrpcConnection,
+        tag: &str,
+    ) -> CallResult<Vec<Part>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_WithTag",
+            &[&self.class, &tag],
+        )
+        .map(|x| x.0.into_iter().map(Part::new).collect())
+    }
+
+    /// A list of all parts that contain a [`Module`](crate::Module) whose
+    /// [`Module::get_name`](crate::Module::get_name) is `module_name`.
+    pub fn with_module(
+        &self,
+        krpc: &mut KrpcConnection,
+        module_name: &str,
+    ) -> CallResult<Vec<Part>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_WithModule",
+            &[&self.class, &module_name],
+        )
+        .map(|x| x.0.into_iter().map(Part::new).collect())
+    }
+
+    /// A list of all parts that are activated in the given `stage`.
+    pub fn in_stage(
+        &self,
+        krpc: &mut KrpcConnection,
+        stage: i32,
+    ) -> CallResult<Vec<Part>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_InStage",
+            &[&self.class, &SVarint(stage)],
+        )
+        .map(|x| x.0.into_iter().map(Part::new).collect())
+    }
+
+    /// A list of all parts that are decoupled in the given `stage`.
+    pub fn in_decouple_stage(
+        &self,
+        krpc: &mut KrpcConnection,
+        stage: i32,
+    ) -> CallResult<Vec<Part>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_InDecoupleStage",
+            &[&self.class, &SVarint(stage)],
+        )
+        .map(|x| x.0.into_iter().map(Part::new).collect())
+    }
+
+    /// A list of modules (combined across all parts in the vessel) whose
+    /// [`Module::get_name`](crate::Module::get_name) is `module_name`.
+    pub fn modules_with_name(
+        &self,
+        krpc: &mut KrpcConnection,
+        module_name: &str,
+    ) -> CallResult<Vec<Module>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_ModulesWithName",
+            &[&self.class, &module_name],
+        )
+        .map(|x| x.0.into_iter().map(Module::new).collect())
+    }
+
+    /// A list of all of the vessels parts.
+    pub fn get_all(&self, krpc: &mut KrpcConnection) -> CallResult<Vec<Part>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_get_All",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(Part::new).collect())
+    }
+
+    /// The vessels root part.
+    pub fn get_root(&self, krpc: &mut KrpcConnection) -> CallResult<Part> {
+        krpc.call("SpaceCenter", "Parts_get_Root", &[&self.class])
+            .map(Part::new)
+    }
+
+    /// The part from which the vessel is controlled.
+    pub fn get_controlling(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Part> {
+        krpc.call("SpaceCenter", "Parts_get_Controlling", &[&self.class])
+            .map(Part::new)
+    }
+
+    /// The part from which the vessel is controlled.
+    pub fn set_controlling(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: &Part,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Parts_set_Controlling",
+            &[&self.class, &value.class],
+        )
+    }
+
+    /// A list of all control surfaces in the vessel.
+    pub fn get_control_surfaces(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<ControlSurface>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_get_ControlSurfaces",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(ControlSurface::new).collect())
+    }
+
+    /// A list of all decouplers in the vessel.
+    pub fn get_decouplers(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<Decoupler>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_get_Decouplers",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(Decoupler::new).collect())
+    }
+
+    /// A list of all docking ports in the vessel.
+    pub fn get_docking_ports(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<DockingPort>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_get_DockingPorts",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(DockingPort::new).collect())
+    }
+
+    /// A list of all engines in the vessel.
+    ///
+    /// # Note
+    ///
+    /// This includes any part that generates thrust. This covers many different
+    /// types of engine, including liquid fuel rockets, solid rocket boosters,
+    /// jet engines and RCS thrusters.
+    pub fn get_engines(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<Engine>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_get_Engines",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(Engine::new).collect())
+    }
+
+    /// A list of all fairings in the vessel.
+    pub fn get_fairings(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<Fairing>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_get_Fairings",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(Fairing::new).collect())
+    }
+
+    /// A list of all landing legs attached to the vessel.
+    pub fn get_legs(&self, krpc: &mut KrpcConnection) -> CallResult<Vec<Leg>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_get_Legs",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(Leg::new).collect())
+    }
+
+    /// A list of all lights in the vessel.
+    pub fn get_lights(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<Light>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_get_Lights",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(Light::new).collect())
+    }
+
+    /// A list of all parachutes in the vessel.
+    pub fn get_parachutes(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<Parachute>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_get_Parachutes",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(Parachute::new).collect())
+    }
+
+    /// A list of all RCS blocks/thrusters in the vessel.
+    pub fn get_rcs(&self, krpc: &mut KrpcConnection) -> CallResult<Vec<RCS>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_get_RCS",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(RCS::new).collect())
+    }
+
+    /// A list of all reaction wheels in the vessel.
+    pub fn get_reaction_wheels(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<ReactionWheel>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_get_ReactionWheels",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(ReactionWheel::new).collect())
+    }
+
+    /// A list of all solar panels in the vessel.
+    pub fn get_solar_panels(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<SolarPanel>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_get_SolarPanels",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(SolarPanel::new).collect())
+    }
+
+    /// A list of all wheels in the vessel.
+    pub fn get_wheels(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<Wheel>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Parts_get_Wheels",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(Wheel::new).collect())
+    }
+}
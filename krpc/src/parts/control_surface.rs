@@ -0,0 +1,190 @@
+use crate::{
+    class::Class,
+    parts::Part,
+    values::{TupleOf, Vector3},
+    CallResult, KrpcConnection,
+};
+
+/// An aerodynamic control surface. Obtained by calling
+/// [`Part::get_control_surface`](crate::Part::get_control_surface).
+pub struct ControlSurface {
+    pub(crate) class: Class,
+}
+
+impl ControlSurface {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// The part object for this control surface.
+    pub fn get_part(&self, krpc: &mut KrpcConnection) -> CallResult<Part> {
+        krpc.call("SpaceCenter", "ControlSurface_get_Part", &[&self.class])
+            .map(Part::new)
+    }
+
+    /// Whether the control surface has pitch control enabled.
+    pub fn get_pitch_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call(
+            "SpaceCenter",
+            "ControlSurface_get_PitchEnabled",
+            &[&self.class],
+        )
+    }
+
+    /// Whether the control surface has pitch control enabled.
+    pub fn set_pitch_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "ControlSurface_set_PitchEnabled",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Whether the control surface has yaw control enabled.
+    pub fn get_yaw_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call(
+            "SpaceCenter",
+            "ControlSurface_get_YawEnabled",
+            &[&self.class],
+        )
+    }
+
+    /// Whether the control surface has yaw control enabled.
+    pub fn set_yaw_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "ControlSurface_set_YawEnabled",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Whether the control surface has roll control enabled.
+    pub fn get_roll_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call(
+            "SpaceCenter",
+            "ControlSurface_get_RollEnabled",
+            &[&self.class],
+        )
+    }
+
+    /// Whether the control surface has roll control enabled.
+    pub fn set_roll_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "ControlSurface_set_RollEnabled",
+            &[&self.class, &value],
+        )
+    }
+
+    /// The authority limiter for the control surface, which controls how far
+    /// the control surface will move.
+    pub fn get_authority_limiter(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "ControlSurface_get_AuthorityLimiter",
+            &[&self.class],
+        )
+    }
+
+    /// The authority limiter for the control surface, which controls how far
+    /// the control surface will move.
+    pub fn set_authority_limiter(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "ControlSurface_set_AuthorityLimiter",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Whether the control surface movement is inverted.
+    pub fn get_inverted(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "ControlSurface_get_Inverted", &[&self.class])
+    }
+
+    /// Whether the control surface movement is inverted.
+    pub fn set_inverted(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "ControlSurface_set_Inverted",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Whether the control surface has been fully deployed.
+    pub fn get_deployed(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "ControlSurface_get_Deployed", &[&self.class])
+    }
+
+    /// Whether the control surface has been fully deployed.
+    pub fn set_deployed(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "ControlSurface_set_Deployed",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Surface area of the control surface in `m^2`.
+    pub fn get_surface_area(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "ControlSurface_get_SurfaceArea",
+            &[&self.class],
+        )
+    }
+
+    /// The available torque, in Newton meters, that can be produced by this
+    /// control surface, in the positive and negative pitch, roll and yaw axes
+    /// of the vessel. These axes correspond to the coordinate axes of the
+    /// [`Vessel::get_reference_frame`](crate::Vessel::get_reference_frame).
+    pub fn get_available_torque(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<(Vector3, Vector3)> {
+        krpc.call::<TupleOf<(TupleOf<Vector3>, TupleOf<Vector3>)>>(
+            "SpaceCenter",
+            "ControlSurface_get_AvailableTorque",
+            &[&self.class],
+        )
+        .map(|TupleOf((a, b))| (a.0, b.0))
+    }
+}
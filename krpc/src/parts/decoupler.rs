@@ -0,0 +1,54 @@
+use crate::{
+    class::Class, parts::Part, vessel::Vessel, CallResult, KrpcConnection,
+};
+
+/// A decoupler. Obtained by calling
+/// [`Part::get_decoupler`](crate::Part::get_decoupler)
+pub struct Decoupler {
+    pub(crate) class: Class,
+}
+
+impl Decoupler {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// Fires the decoupler. Returns the new vessel created when the decoupler
+    /// fires. Throws an exception if the decoupler has already fired.
+    ///
+    /// # Note
+    ///
+    /// When called, the active vessel may change. It is therefore possible
+    /// that, after calling this function, the object(s) returned by previous
+    /// call(s) to [`KrpcConnection::get_active_vessel`] no longer refer to the
+    /// active vessel.
+    ///
+    /// [`KrpcConnection::get_active_vessel`]:
+    /// crate::KrpcConnection::get_active_vessel
+    pub fn decouple(&self, krpc: &mut KrpcConnection) -> CallResult<Vessel> {
+        krpc.call("SpaceCenter", "Decoupler_Decouple", &[&self.class])
+            .map(Vessel::new)
+    }
+
+    /// The part object for this decoupler.
+    pub fn get_part(&self, krpc: &mut KrpcConnection) -> CallResult<Part> {
+        krpc.call("SpaceCenter", "Decoupler_get_Part", &[&self.class])
+            .map(Part::new)
+    }
+
+    /// Whether the decoupler has fired.
+    pub fn get_decoupled(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Decoupler_get_Decoupled", &[&self.class])
+    }
+
+    /// Whether the decoupler is enabled in the staging sequence.
+    pub fn get_staged(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Decoupler_get_Staged", &[&self.class])
+    }
+
+    /// The impulse that the decoupler imparts when it is fired, in Newton
+    /// seconds.
+    pub fn get_impulse(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Decoupler_get_Impulse", &[&self.class])
+    }
+}
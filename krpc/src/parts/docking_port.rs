@@ -0,0 +1,227 @@
+use crate::{
+    class::Class,
+    parts::Part,
+    reference_frame::ReferenceFrame,
+    values::{krpc_enum, Quaternion, TupleOf, Vector3},
+    vessel::Vessel,
+    CallResult, KrpcConnection,
+};
+
+krpc_enum! {
+    /// The state of a docking port. See [`DockingPort::get_state`].
+    pub enum DockingPortState {
+        /// The docking port is ready to dock to another docking port.
+        Ready = 0,
+        /// The docking port is docked to another docking port, or docked to
+        /// another part (from the VAB/SPH).
+        Docked = 1,
+        /// The docking port is very close to another docking port, but has not
+        /// docked. It is using magnetic force to acquire a solid dock.
+        Docking = 2,
+        /// The docking port has just been undocked from another docking port,
+        /// and is disabled until it moves away by a sufficient distance
+        /// ([`DockingPort::get_reengage_distance`]).
+        Undocking = 3,
+        /// The docking port has a shield, and the shield is closed.
+        Shielded = 4,
+        /// The docking ports shield is currently opening/closing.
+        Moving = 5,
+    }
+}
+
+/// A docking port. Obtained by calling
+/// [`Part::get_docking_port`](crate::Part::get_docking_port)
+pub struct DockingPort {
+    pub(crate) class: Class,
+}
+
+impl DockingPort {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// Undocks the docking port and returns the new [`Vessel`](crate::Vessel)
+    /// that is created. This method can be called for either docking port in a
+    /// docked pair. Throws an exception if the docking port is not docked to
+    /// anything.
+    ///
+    /// # Note
+    ///
+    /// When called, the active vessel may change. It is therefore possible
+    /// that, after calling this function, the object(s) returned by previous
+    /// call(s) to [`KrpcConnection::get_active_vessel`] no longer refer to the
+    /// active vessel.
+    ///
+    /// [`KrpcConnection::get_active_vessel`]:
+    /// crate::KrpcConnection::get_active_vessel
+    pub fn undock(&self, krpc: &mut KrpcConnection) -> CallResult<Vessel> {
+        krpc.call("SpaceCenter", "DockingPort_Undock", &[&self.class])
+            .map(Vessel::new)
+    }
+
+    /// The position of the docking port, in the given reference frame.
+    ///
+    /// - `reference_frame`: The reference frame that the returned position
+    ///   vector is in.
+    ///
+    /// # Returns
+    ///
+    /// The position as a vector.
+    pub fn position(
+        &self,
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "DockingPort_Position",
+            &[&self.class, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The direction that docking port points in, in the given reference frame.
+    ///
+    /// - `reference_frame`: The reference frame that the returned direction is
+    ///   in.
+    ///
+    /// # Returns
+    ///
+    /// The direction as a unit vector.
+    pub fn direction(
+        &self,
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "DockingPort_Direction",
+            &[&self.class, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The rotation of the docking port, in the given reference frame.
+    ///
+    /// - `reference_frame`: The reference frame that the returned rotation is
+    ///   in.
+    ///
+    /// # Returns
+    ///
+    /// The rotation as a quaternion of the form `(x, y, z, w)`.
+    pub fn rotation(
+        &self,
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Quaternion> {
+        krpc.call::<TupleOf<Quaternion>>(
+            "SpaceCenter",
+            "DockingPort_Rotation",
+            &[&self.class, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The part object for this docking port.
+    pub fn get_part(&self, krpc: &mut KrpcConnection) -> CallResult<Part> {
+        krpc.call("SpaceCenter", "DockingPort_get_Part", &[&self.class])
+            .map(Part::new)
+    }
+
+    /// The current state of the docking port.
+    pub fn get_state(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<DockingPortState> {
+        krpc.call("SpaceCenter", "DockingPort_get_State", &[&self.class])
+    }
+
+    /// The part that this docking port is docked to. Returns `None` if this
+    /// docking port is not docked to anything.
+    pub fn get_docked_part(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Option<Part>> {
+        krpc.call::<Class>(
+            "SpaceCenter",
+            "DockingPort_get_DockedPart",
+            &[&self.class],
+        )
+        .map(|x| x.into_option().map(Part::new))
+    }
+
+    /// The distance a docking port must move away when it undocks before it
+    /// becomes ready to dock with another port, in meters.
+    pub fn get_reengage_distance(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "DockingPort_get_ReengageDistance",
+            &[&self.class],
+        )
+    }
+
+    /// Whether the docking port has a shield.
+    pub fn get_has_shield(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "DockingPort_get_HasShield", &[&self.class])
+    }
+
+    /// The state of the docking ports shield, if it has one.
+    ///
+    /// Returns `true` if the docking port has a shield, and the shield is
+    /// closed. Otherwise returns `false`. When set to `true`, the shield is
+    /// closed, and when set to `false` the shield is opened. If the docking
+    /// port does not have a shield, setting this attribute has no effect.
+    pub fn get_shielded(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "DockingPort_get_Shielded", &[&self.class])
+    }
+
+    /// The state of the docking ports shield, if it has one.
+    ///
+    /// Returns `true` if the docking port has a shield, and the shield is
+    /// closed. Otherwise returns `false`. When set to `true`, the shield is
+    /// closed, and when set to `false` the shield is opened. If the docking
+    /// port does not have a shield, setting this attribute has no effect.
+    pub fn set_shielded(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "DockingPort_set_Shielded",
+            &[&self.class, &value],
+        )
+    }
+
+    /// The reference frame that is fixed relative to this docking port, and
+    /// oriented with the port.
+    ///
+    /// - The origin is at the position of the docking port.
+    /// - The axes rotate with the docking port.
+    /// - The x-axis points out to the right side of the docking port.
+    /// - The y-axis points in the direction the docking port is facing.
+    /// - The z-axis points out of the bottom off the docking port.
+    ///
+    /// # Note
+    ///
+    /// This reference frame is not necessarily equivalent to the reference
+    /// frame for the part, returned by
+    /// [`Part::get_reference_frame`](crate::Part::get_reference_frame).
+    pub fn get_reference_frame(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<ReferenceFrame> {
+        krpc.call(
+            "SpaceCenter",
+            "DockingPort_get_ReferenceFrame",
+            &[&self.class],
+        )
+        .map(ReferenceFrame::new)
+    }
+}
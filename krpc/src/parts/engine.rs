@@ -0,0 +1,356 @@
+use std::collections::HashMap;
+
+use crate::{
+    class::Class,
+    parts::Part,
+    values::{DictOf, ListOf, TupleOf, Vector3},
+    CallResult, KrpcConnection,
+};
+
+/// An engine, including ones of various types. For example liquid fuelled
+/// gimballed engines, solid rocket boosters and jet engines. Obtained by
+/// calling [`Part::get_engine`](crate::Part::get_engine).
+///
+/// # Note
+///
+/// For RCS thrusters [`Part::get_rcs`](crate::Part::get_rcs).
+pub struct Engine {
+    pub(crate) class: Class,
+}
+
+impl Engine {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// Toggle the current engine mode.
+    pub fn toggle_mode(&self, krpc: &mut KrpcConnection) -> CallResult {
+        krpc.call("SpaceCenter", "Engine_ToggleMode", &[&self.class])
+    }
+
+    /// The part object for this engine.
+    pub fn get_part(&self, krpc: &mut KrpcConnection) -> CallResult<Part> {
+        krpc.call("SpaceCenter", "Engine_get_Part", &[&self.class])
+            .map(Part::new)
+    }
+
+    /// Whether the engine is active. Setting this attribute may have no effect,
+    /// depending on [`Engine::get_can_shutdown`] and
+    /// [`Engine::get_can_restart`].
+    pub fn get_active(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Engine_get_Active", &[&self.class])
+    }
+
+    /// Whether the engine is active. Setting this attribute may have no effect,
+    /// depending on [`Engine::get_can_shutdown`] and
+    /// [`Engine::get_can_restart`].
+    pub fn set_active(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Engine_set_Active", &[&self.class, &value])
+    }
+
+    /// The current amount of thrust being produced by the engine, in Newtons.
+    pub fn get_thrust(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Engine_get_Thrust", &[&self.class])
+    }
+
+    /// The amount of thrust, in Newtons, that would be produced by the engine
+    /// when activated and with its throttle set to 100%. Returns zero if the
+    /// engine does not have any fuel. Takes the engine's current
+    /// [`Engine::get_thrust_limit`] and atmospheric conditions into account.
+    pub fn get_available_thrust(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Engine_get_AvailableThrust", &[&self.class])
+    }
+
+    /// The amount of thrust, in Newtons, that would be produced by the engine
+    /// when activated and fueled, with its throttle and throttle limiter set to
+    /// 100%.
+    pub fn get_max_thrust(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Engine_get_MaxThrust", &[&self.class])
+    }
+
+    /// The maximum amount of thrust that can be produced by the engine in a
+    /// vacuum, in Newtons. This is the amount of thrust produced by the engine
+    /// when activated, [`Engine::get_thrust_limit`] is set to 100%, the main
+    /// vessel's throttle is set to 100% and the engine is in a vacuum.
+    pub fn get_max_vacuum_thrust(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Engine_get_MaxVacuumThrust", &[&self.class])
+    }
+
+    /// The thrust limiter of the engine. A value between 0 and 1. Setting this
+    /// attribute may have no effect, for example the thrust limit for a solid
+    /// rocket booster cannot be changed in flight.
+    pub fn get_thrust_limit(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Engine_get_ThrustLimit", &[&self.class])
+    }
+
+    /// The thrust limiter of the engine. A value between 0 and 1. Setting this
+    /// attribute may have no effect, for example the thrust limit for a solid
+    /// rocket booster cannot be changed in flight.
+    pub fn set_thrust_limit(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Engine_set_ThrustLimit",
+            &[&self.class, &value],
+        )
+    }
+
+    /// The current specific impulse of the engine, in seconds. Returns zero if
+    /// the engine is not active.
+    pub fn get_specific_impulse(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Engine_get_SpecificImpulse", &[&self.class])
+    }
+
+    /// The vacuum specific impulse of the engine, in seconds.
+    pub fn get_vacuum_specific_impulse(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Engine_get_VacuumSpecificImpulse",
+            &[&self.class],
+        )
+    }
+
+    /// The specific impulse of the engine at sea level on Kerbin, in seconds.
+    pub fn get_kerbin_sea_level_specific_impulse(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Engine_get_KerbinSeaLevelSpecificImpulse",
+            &[&self.class],
+        )
+    }
+
+    /// The names of the propellants that the engine consumes.
+    pub fn get_propellant_names(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<String>> {
+        krpc.call::<ListOf<String>>(
+            "SpaceCenter",
+            "Engine_get_PropellantNames",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The ratio of resources that the engine consumes. A dictionary mapping
+    /// resource names to the ratio at which they are consumed by the engine.
+    ///
+    /// # Note
+    ///
+    /// For example, if the ratios are 0.6 for LiquidFuel and 0.4 for Oxidizer,
+    /// then for every 0.6 units of LiquidFuel that the engine burns, it will
+    /// burn 0.4 units of Oxidizer.
+    pub fn get_propellant_ratios(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<HashMap<String, f32>> {
+        krpc.call::<DictOf<String, f32>>(
+            "SpaceCenter",
+            "Engine_get_PropellantRatios",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// Whether the engine has any fuel available.
+    ///
+    /// # Note
+    ///
+    /// The engine must be activated for this property to update correctly.
+    pub fn get_has_fuel(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Engine_get_HasFuel", &[&self.class])
+    }
+
+    /// The current throttle setting for the engine. A value between 0 and 1.
+    /// This is not necessarily the same as the vessel's main throttle setting,
+    /// as some engines take time to adjust their throttle (such as jet
+    /// engines).
+    pub fn get_throttle(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Engine_get_Throttle", &[&self.class])
+    }
+
+    /// Whether the [`Control::get_throttle`](crate::Control::get_throttle)
+    /// affects the engine. For example, this is `true` for liquid fueled
+    /// rockets, and `false` for solid rocket boosters.
+    pub fn get_throttle_locked(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Engine_get_ThrottleLocked", &[&self.class])
+    }
+
+    /// Whether the engine can be restarted once shutdown. If the engine cannot
+    /// be shutdown, returns `false`. For example, this is `true` for liquid
+    /// fueled rockets and `false` for solid rocket boosters.
+    pub fn get_can_restart(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Engine_get_CanRestart", &[&self.class])
+    }
+
+    /// Whether the engine can be shutdown once activated. For example, this is
+    /// `true` for liquid fueled rockets and `false` for solid rocket boosters.
+    pub fn get_can_shutdown(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Engine_get_CanShutdown", &[&self.class])
+    }
+
+    /// Whether the engine has multiple modes of operation.
+    pub fn get_has_modes(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Engine_get_HasModes", &[&self.class])
+    }
+
+    /// The name of the current engine mode.
+    pub fn get_mode(&self, krpc: &mut KrpcConnection) -> CallResult<String> {
+        krpc.call("SpaceCenter", "Engine_get_Mode", &[&self.class])
+    }
+
+    /// The name of the current engine mode.
+    pub fn set_mode(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: &str,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Engine_set_Mode", &[&self.class, &value])
+    }
+
+    /// The available modes for the engine. A dictionary mapping mode names to
+    /// [`Engine`] objects.
+    pub fn get_modes(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<HashMap<String, Engine>> {
+        krpc.call::<DictOf<String, Class>>(
+            "SpaceCenter",
+            "Engine_get_Modes",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(|(k, v)| (k, Engine::new(v))).collect())
+    }
+
+    /// Whether the engine will automatically switch modes.
+    pub fn get_auto_mode_switch(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Engine_get_AutoModeSwitch", &[&self.class])
+    }
+
+    /// Whether the engine will automatically switch modes.
+    pub fn set_auto_mode_switch(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Engine_set_AutoModeSwitch",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Whether the engine is gimballed.
+    pub fn get_gimballed(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Engine_get_Gimballed", &[&self.class])
+    }
+
+    /// The range over which the gimbal can move, in degrees. Returns 0 if the
+    /// engine is not gimballed.
+    pub fn get_gimbal_range(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Engine_get_GimbalRange", &[&self.class])
+    }
+
+    /// Whether the engines gimbal is locked in place. Setting this attribute
+    /// has no effect if the engine is not gimballed.
+    pub fn get_gimbal_locked(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Engine_get_GimbalLocked", &[&self.class])
+    }
+
+    /// Whether the engines gimbal is locked in place. Setting this attribute
+    /// has no effect if the engine is not gimballed.
+    pub fn set_gimbal_locked(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Engine_set_GimbalLocked",
+            &[&self.class, &value],
+        )
+    }
+
+    /// The gimbal limiter of the engine. A value between 0 and 1. Returns 0 if
+    /// the gimbal is locked.
+    pub fn get_gimbal_limit(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Engine_get_GimbalLimit", &[&self.class])
+    }
+
+    /// The gimbal limiter of the engine. A value between 0 and 1. Returns 0 if
+    /// the gimbal is locked.
+    pub fn set_gimbal_limit(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Engine_set_GimbalLimit",
+            &[&self.class, &value],
+        )
+    }
+
+    /// The available torque, in Newton meters, that can be produced by this
+    /// engine, in the positive and negative pitch, roll and yaw axes of the
+    /// vessel. These axes correspond to the coordinate axes of the
+    /// [`Vessel::get_reference_frame`](crate::Vessel::get_reference_frame).
+    /// Returns zero if the engine is inactive, or not gimballed.
+    pub fn get_available_torque(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<(Vector3, Vector3)> {
+        krpc.call::<TupleOf<(TupleOf<Vector3>, TupleOf<Vector3>)>>(
+            "SpaceCenter",
+            "Engine_get_AvailableTorque",
+            &[&self.class],
+        )
+        .map(|TupleOf((a, b))| (a.0, b.0))
+    }
+}
@@ -0,0 +1,32 @@
+use crate::{class::Class, parts::Part, CallResult, KrpcConnection};
+
+/// A fairing. Obtained by calling
+/// [`Part::get_fairing`](crate::Part::get_fairing).
+pub struct Fairing {
+    pub(crate) class: Class,
+}
+
+impl Fairing {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// Jettison the fairing. Has no effect if it has already been jettisoned.
+    pub fn jettison(&self, krpc: &mut KrpcConnection) -> CallResult {
+        krpc.call("SpaceCenter", "Fairing_Jettison", &[&self.class])
+    }
+
+    /// The part object for this fairing.
+    pub fn get_part(&self, krpc: &mut KrpcConnection) -> CallResult<Part> {
+        krpc.call("SpaceCenter", "Fairing_get_Part", &[&self.class])
+            .map(Part::new)
+    }
+
+    /// Whether the fairing has been jettisoned.
+    pub fn get_jettisoned(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Fairing_get_Jettisoned", &[&self.class])
+    }
+}
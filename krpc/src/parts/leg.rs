@@ -0,0 +1,81 @@
+use crate::{
+    class::Class, parts::Part, values::krpc_enum, CallResult, KrpcConnection,
+};
+
+krpc_enum! {
+    /// The state of a landing leg. See [`Leg::get_state`].
+    pub enum LegState {
+        /// Landing leg is fully deployed.
+        Deployed = 0,
+        /// Landing leg is fully retracted.
+        Retracted = 1,
+        /// Landing leg is being deployed.
+        Deploying = 2,
+        /// Landing leg is being retracted.
+        Retracting = 3,
+        /// Landing leg is broken.
+        Broken = 4,
+    }
+}
+
+/// A landing leg. Obtained by calling [`Part::get_leg`](crate::Part::get_leg).
+pub struct Leg {
+    pub(crate) class: Class,
+}
+
+impl Leg {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// The part object for this landing leg.
+    pub fn get_part(&self, krpc: &mut KrpcConnection) -> CallResult<Part> {
+        krpc.call("SpaceCenter", "Leg_get_Part", &[&self.class])
+            .map(Part::new)
+    }
+
+    /// The current state of the landing leg.
+    pub fn get_state(&self, krpc: &mut KrpcConnection) -> CallResult<LegState> {
+        krpc.call("SpaceCenter", "Leg_get_State", &[&self.class])
+    }
+
+    /// Whether the leg is deployable.
+    pub fn get_deployable(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Leg_get_Deployable", &[&self.class])
+    }
+
+    /// Whether the landing leg is deployed.
+    ///
+    /// # Note
+    ///
+    /// Fixed landing legs are always deployed. Returns an error if you try to
+    /// deploy fixed landing gear.
+    pub fn get_deployed(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Leg_get_Deployed", &[&self.class])
+    }
+
+    /// Whether the landing leg is deployed.
+    ///
+    /// # Note
+    ///
+    /// Fixed landing legs are always deployed. Returns an error if you try to
+    /// deploy fixed landing gear.
+    pub fn set_deployed(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Leg_set_Deployed", &[&self.class, &value])
+    }
+
+    /// Returns whether the leg is touching the ground.
+    pub fn get_is_grounded(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Leg_get_IsGrounded", &[&self.class])
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{
+    class::Class, parts::Part, values::TupleOf, CallResult, KrpcConnection,
+};
+
+/// A light. Obtained by calling [`Part::get_light`](crate::Part::get_light).
+pub struct Light {
+    pub(crate) class: Class,
+}
+
+impl Light {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// The part object for this light.
+    pub fn get_part(&self, krpc: &mut KrpcConnection) -> CallResult<Part> {
+        krpc.call("SpaceCenter", "Light_get_Part", &[&self.class])
+            .map(Part::new)
+    }
+
+    /// Whether the light is switched on.
+    pub fn get_active(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Light_get_Active", &[&self.class])
+    }
+
+    /// Whether the light is switched on.
+    pub fn set_active(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Light_set_Active", &[&self.class, &value])
+    }
+
+    /// The color of the light, as an RGB triple.
+    pub fn get_color(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<(f32, f32, f32)> {
+        krpc.call::<TupleOf<(f32, f32, f32)>>(
+            "SpaceCenter",
+            "Light_get_Color",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The color of the light, as an RGB triple.
+    pub fn set_color(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: (f32, f32, f32),
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Light_set_Color",
+            &[&self.class, &TupleOf(value)],
+        )
+    }
+
+    /// The current power usage, in units of charge per second.
+    pub fn get_power_usage(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Light_get_PowerUsage", &[&self.class])
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+use protobuf_but_worse::encoding::SVarint;
+
+use crate::{
+    class::Class,
+    parts::Part,
+    values::{DictOf, ListOf},
+    CallResult, KrpcConnection,
+};
+
+/// This can be used to interact with a specific part module. This includes part
+/// modules in stock KSP, and those added by mods.
+///
+/// In KSP, each part has zero or more PartModules associated with it. Each one
+/// contains some of the functionality of the part. For example, an engine has a
+/// "ModuleEngines" part module that contains all the functionality of an
+/// engine.
+pub struct Module {
+    pub(crate) class: Class,
+}
+
+impl Module {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// Returns `true` if the module has a field with the given name.
+    ///
+    /// - `name`: Name of the field.
+    pub fn has_field(
+        &self,
+        krpc: &mut KrpcConnection,
+        name: &str,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Module_HasField", &[&self.class, &name])
+    }
+
+    /// Returns the value of a field.
+    ///
+    /// - `name`: Name of the field.
+    pub fn get_field(
+        &self,
+        krpc: &mut KrpcConnection,
+        name: &str,
+    ) -> CallResult<String> {
+        krpc.call("SpaceCenter", "Module_GetField", &[&self.class, &name])
+    }
+
+    /// Set the value of a field to the given integer number.
+    ///
+    /// - `name`: Name of the field.
+    /// - `value`: Value to set.
+    pub fn set_field_int(
+        &self,
+        krpc: &mut KrpcConnection,
+        name: &str,
+        value: i32,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Module_SetFieldInt",
+            &[&self.class, &name, &SVarint(value)],
+        )
+    }
+
+    /// Set the value of a field to the given floating point number.
+    ///
+    /// - `name`: Name of the field.
+    /// - `value`: Value to set.
+    pub fn set_field_float(
+        &self,
+        krpc: &mut KrpcConnection,
+        name: &str,
+        value: f32,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Module_SetFieldFloat",
+            &[&self.class, &name, &value],
+        )
+    }
+
+    /// Set the value of a field to the given string.
+    ///
+    /// - `name`: Name of the field.
+    /// - `value`: Value to set.
+    pub fn set_field_string(
+        &self,
+        krpc: &mut KrpcConnection,
+        name: &str,
+        value: &str,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Module_SetFieldString",
+            &[&self.class, &name, &value],
+        )
+    }
+
+    /// Set the value of a field to its original value.
+    ///
+    /// - `name`: Name of the field.
+    pub fn reset_field(
+        &self,
+        krpc: &mut KrpcConnection,
+        name: &str,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Module_ResetField", &[&self.class, &name])
+    }
+
+    /// `true` if the module has an event with the given name.
+    pub fn has_event(
+        &self,
+        krpc: &mut KrpcConnection,
+        name: &str,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Module_HasEvent", &[&self.class, &name])
+    }
+
+    /// Trigger the named event. Equivalent to clicking the button in the right-
+    /// click menu of the part.
+    pub fn trigger_event(
+        &self,
+        krpc: &mut KrpcConnection,
+        name: &str,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Module_TriggerEvent", &[&self.class, &name])
+    }
+
+    /// `true` if the part has an action with the given name.
+    pub fn has_action(
+        &self,
+        krpc: &mut KrpcConnection,
+        name: &str,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Module_HasAction", &[&self.class, &name])
+    }
+
+    /// Set the value of an action with the given name.
+    pub fn set_action(
+        &self,
+        krpc: &mut KrpcConnection,
+        name: &str,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Module_SetAction",
+            &[&self.class, &name, &value],
+        )
+    }
+
+    /// Name of the PartModule. For example, "ModuleEngines".
+    pub fn get_name(&self, krpc: &mut KrpcConnection) -> CallResult<String> {
+        krpc.call("SpaceCenter", "Module_get_Name", &[&self.class])
+    }
+
+    /// The part that contains this module.
+    pub fn get_part(&self, krpc: &mut KrpcConnection) -> CallResult<Part> {
+        krpc.call("SpaceCenter", "Module_get_Part", &[&self.class])
+            .map(Part::new)
+    }
+
+    /// The modules field names and their associated values, as a dictionary.
+    /// These are the values visible in the right-click menu of the part.
+    pub fn get_fields(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<HashMap<String, String>> {
+        krpc.call::<DictOf<String, String>>(
+            "SpaceCenter",
+            "Module_get_Fields",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// A list of the names of all of the modules events. Events are the
+    /// clickable buttons visible in the right-click menu of the part.
+    pub fn get_events(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<String>> {
+        krpc.call::<ListOf<String>>(
+            "SpaceCenter",
+            "Module_get_Events",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// A list of all the names of the modules actions. These are the parts
+    /// actions that can be assigned to action groups in the in-game editor.
+    pub fn get_actions(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<String>> {
+        krpc.call::<ListOf<String>>(
+            "SpaceCenter",
+            "Module_get_Actions",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+}
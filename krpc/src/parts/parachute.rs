@@ -0,0 +1,126 @@
+use crate::{
+    class::Class, parts::Part, values::krpc_enum, CallResult, KrpcConnection,
+};
+
+krpc_enum! {
+    /// The state of a parachute. See [`Parachute::get_state`].
+    pub enum ParachuteState {
+        /// The parachute is safely tucked away inside its housing.
+        Stowed = 0,
+        /// The parachute is armed for deployment. (RealChutes only)
+        Armed = 1,
+        /// The parachute is still stowed, but ready to semi-deploy. (Stock
+        /// parachutes only)
+        Active = 2,
+        /// The parachute has been deployed and is providing some drag, but is
+        /// not fully deployed yet. (Stock parachutes only)
+        SemiDeployed = 3,
+        /// The parachute is fully deployed.
+        Deployed = 4,
+        /// The parachute has been cut.
+        Cut = 5,
+    }
+}
+
+/// A parachute. Obtained by calling
+/// [`Part::get_parachute`](crate::Part::get_parachute).
+pub struct Parachute {
+    pub(crate) class: Class,
+}
+
+impl Parachute {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// Deploys the parachute. This has no effect if the parachute has already
+    /// been deployed.
+    pub fn deploy(&self, krpc: &mut KrpcConnection) -> CallResult {
+        krpc.call("SpaceCenter", "Parachute_Deploy", &[&self.class])
+    }
+
+    /// Deploys the parachute. This has no effect if the parachute has already
+    /// been armed or deployed. Only applicable to RealChutes parachutes.
+    pub fn arm(&self, krpc: &mut KrpcConnection) -> CallResult {
+        krpc.call("SpaceCenter", "Parachute_Arm", &[&self.class])
+    }
+
+    /// The part object for this parachute.
+    pub fn get_part(&self, krpc: &mut KrpcConnection) -> CallResult<Part> {
+        krpc.call("SpaceCenter", "Parachute_get_Part", &[&self.class])
+            .map(Part::new)
+    }
+
+    /// Whether the parachute has been deployed.
+    pub fn get_deployed(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Parachute_get_Deployed", &[&self.class])
+    }
+
+    /// Whether the parachute has been armed or deployed. Only applicable to
+    /// RealChutes parachutes.
+    pub fn get_armed(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Parachute_get_Armed", &[&self.class])
+    }
+
+    /// The current state of the parachute.
+    pub fn get_state(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<ParachuteState> {
+        krpc.call("SpaceCenter", "Parachute_get_State", &[&self.class])
+    }
+
+    /// The altitude at which the parachute will full deploy, in meters. Only
+    /// applicable to stock parachutes.
+    pub fn get_deploy_altitude(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Parachute_get_DeployAltitude",
+            &[&self.class],
+        )
+    }
+
+    /// The altitude at which the parachute will full deploy, in meters. Only
+    /// applicable to stock parachutes.
+    pub fn set_deploy_altitude(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Parachute_set_DeployAltitude",
+            &[&self.class, &value],
+        )
+    }
+
+    /// The minimum pressure at which the parachute will semi-deploy, in
+    /// atmospheres. Only applicable to stock parachutes.
+    pub fn get_deploy_min_pressure(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Parachute_get_DeployMinPressure",
+            &[&self.class],
+        )
+    }
+
+    /// The minimum pressure at which the parachute will semi-deploy, in
+    /// atmospheres. Only applicable to stock parachutes.
+    pub fn set_deploy_min_pressure(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Parachute_set_DeployMinPressure",
+            &[&self.class, &value],
+        )
+    }
+}
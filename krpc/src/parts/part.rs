@@ -0,0 +1,808 @@
+use protobuf_but_worse::encoding::SVarint;
+
+use crate::{
+    class::Class,
+    parts::{
+        ControlSurface, Decoupler, DockingPort, Engine, Fairing, Leg, Light,
+        Module, Parachute, ReactionWheel, SolarPanel, Wheel, RCS,
+    },
+    reference_frame::ReferenceFrame,
+    resources::Resources,
+    values::{ListOf, Quaternion, TupleOf, Vector3},
+    vessel::Vessel,
+    CallResult, KrpcConnection,
+};
+
+/// Represents an individual part. Vessels are made up of multiple parts.
+/// Instances of this class can be obtained by several methods in
+/// [`Parts`](crate::Parts).
+pub struct Part {
+    pub(crate) class: Class,
+}
+
+impl Part {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// The position of the part in the given reference frame.
+    ///
+    /// - `reference_frame`: The reference frame that the returned position
+    ///   vector is in.
+    ///
+    /// # Returns
+    ///
+    /// The position as a vector.
+    ///
+    /// # Note
+    ///
+    /// This is a fixed position in the part, defined by the parts model. It s
+    /// not necessarily the same as the parts center of mass. Use
+    /// [`Part::center_of_mass`] to get the parts center of mass.
+    pub fn position(
+        &self,
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Part_Position",
+            &[&self.class, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The position of the parts center of mass in the given reference frame.
+    /// If the part is physicsless, this is equivalent to [`Part::position`].
+    ///
+    /// - `reference_frame`: The reference frame that the returned position
+    ///   vector is in.
+    ///
+    /// # Returns
+    ///
+    /// The position as a vector.
+    pub fn center_of_mass(
+        &self,
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Part_CenterOfMass",
+            &[&self.class, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The axis-aligned bounding box of the part in the given reference frame.
+    ///
+    /// - `reference_frame`: The reference frame that the returned position
+    ///   vectors are in.
+    ///
+    /// # Returns
+    ///
+    /// The positions of the minimum and maximum vertices of the box, as
+    /// position vectors.
+    ///
+    /// # Note
+    ///
+    /// This is computed from the collision mesh of the part. If the part is not
+    /// collidable, the box has zero volume and is centered on the
+    /// [`Part::position`] of the part.
+    pub fn bounding_box(
+        &self,
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<(Vector3, Vector3)> {
+        krpc.call::<TupleOf<(TupleOf<Vector3>, TupleOf<Vector3>)>>(
+            "SpaceCenter",
+            "Part_BoundingBox",
+            &[&self.class, &reference_frame.class],
+        )
+        .map(|TupleOf((a, b))| (a.0, b.0))
+    }
+
+    /// The direction the part points in, in the given reference frame.
+    ///
+    /// - `reference_frame`: The reference frame that the returned direction is
+    ///   in.
+    ///
+    /// # Returns
+    ///
+    /// The direction as a unit vector.
+    pub fn direction(
+        &self,
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Part_Direction",
+            &[&self.class, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The linear velocity of the part in the given reference frame.
+    ///
+    /// - `reference_frame`: The reference frame that the returned velocity
+    ///   vector is in.
+    ///
+    /// # Returns
+    ///
+    /// The velocity as a vector. The vector points in the direction of travel,
+    /// and its magnitude is the speed of the body in meters per second.
+    pub fn velocity(
+        &self,
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Part_Velocity",
+            &[&self.class, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The rotation of the part, in the given reference frame.
+    ///
+    /// - `reference_frame`: The reference frame that the returned rotation is
+    ///   in.
+    ///
+    /// # Returns
+    ///
+    /// The rotation as a quaternion of the form `(x, y, z, w)`.
+    pub fn rotation(
+        &self,
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Quaternion> {
+        krpc.call::<TupleOf<Quaternion>>(
+            "SpaceCenter",
+            "Part_Rotation",
+            &[&self.class, &reference_frame.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// Exert an instantaneous force on the part, acting at the given position.
+    ///
+    /// - `force`: A vector pointing in the direction that the force acts, with
+    ///   its magnitude equal to the strength of the force in Newtons.
+    /// - `position`: The position at which the force acts, as a vector.
+    /// - `reference_frame`: The reference frame that the force and position are
+    ///   in.
+    ///
+    /// # Note
+    ///
+    /// The force is applied instantaneously in a single physics update.
+    pub fn instantaneous_force(
+        &self,
+        krpc: &mut KrpcConnection,
+        force: Vector3,
+        position: Vector3,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Part_InstantaneousForce",
+            &[
+                &self.class,
+                &TupleOf(force),
+                &TupleOf(position),
+                &reference_frame.class,
+            ],
+        )
+    }
+
+    /// Internal name of the part, as used in part cfg files. For example
+    /// "Mark1-2Pod".
+    pub fn get_name(&self, krpc: &mut KrpcConnection) -> CallResult<String> {
+        krpc.call("SpaceCenter", "Part_get_Name", &[&self.class])
+    }
+
+    /// Title of the part, as shown when the part is right clicked in-game. For
+    /// example "Mk1-2 Command Pod".
+    pub fn get_title(&self, krpc: &mut KrpcConnection) -> CallResult<String> {
+        krpc.call("SpaceCenter", "Part_get_Title", &[&self.class])
+    }
+
+    /// The name tag for the part. Can be set to a custom string using the in-
+    /// game user interface.
+    ///
+    /// # Note
+    ///
+    /// This string is shared with kOS if it is installed.
+    pub fn get_tag(&self, krpc: &mut KrpcConnection) -> CallResult<String> {
+        krpc.call("SpaceCenter", "Part_get_Tag", &[&self.class])
+    }
+
+    /// The name tag for the part. Can be set to a custom string using the in-
+    /// game user interface.
+    ///
+    /// # Note
+    ///
+    /// This string is shared with kOS if it is installed.
+    pub fn set_tag(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: &str,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Part_set_Tag", &[&self.class, &value])
+    }
+
+    /// Whether the part is highlighted.
+    pub fn get_highlighted(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Part_get_Highlighted", &[&self.class])
+    }
+
+    /// Whether the part is highlighted.
+    pub fn set_highlighted(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Part_set_Highlighted",
+            &[&self.class, &value],
+        )
+    }
+
+    /// The color used to highlight the part, as an RGB triple.
+    pub fn get_highlight_color(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Part_get_HighlightColor",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The color used to highlight the part, as an RGB triple.
+    pub fn set_highlight_color(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: Vector3,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Part_set_HighlightColor",
+            &[&self.class, &TupleOf(value)],
+        )
+    }
+
+    /// The cost of the part, in units of funds.
+    pub fn get_cost(&self, krpc: &mut KrpcConnection) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Part_get_Cost", &[&self.class])
+    }
+
+    /// The vessel that contains this part.
+    pub fn get_vessel(&self, krpc: &mut KrpcConnection) -> CallResult<Vessel> {
+        krpc.call("SpaceCenter", "Part_get_Vessel", &[&self.class])
+            .map(Vessel::new)
+    }
+
+    /// The parts parent. Returns `None` if the part does not have a parent.
+    /// This, in combination with [`Part::get_children`], can be used to
+    /// traverse the vessels parts tree.
+    pub fn get_parent(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Option<Part>> {
+        krpc.call::<Class>("SpaceCenter", "Part_get_Parent", &[&self.class])
+            .map(|x| x.into_option().map(Part::new))
+    }
+
+    /// The parts children. Returns an empty list if the part has no children.
+    /// This, in combination with [`Part::get_parent`], can be used to traverse
+    /// the vessels parts tree.
+    pub fn get_children(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<Part>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Part_get_Children",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(Part::new).collect())
+    }
+
+    /// Whether the part is axially attached to its parent, i.e. on the top or
+    /// bottom of its parent. If the part has no parent, returns `false`.
+    pub fn get_axially_attached(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Part_get_AxiallyAttached", &[&self.class])
+    }
+
+    /// Whether the part is radially attached to its parent, i.e. on the side of
+    /// its parent. If the part has no parent, returns `false`.
+    pub fn get_radially_attached(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Part_get_RadiallyAttached", &[&self.class])
+    }
+
+    /// The stage in which this part will be activated. Returns -1 if the part
+    /// is not activated by staging.
+    pub fn get_stage(&self, krpc: &mut KrpcConnection) -> CallResult<i32> {
+        krpc.call::<SVarint<i32>>(
+            "SpaceCenter",
+            "Part_get_Stage",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The stage in which this part will be decoupled. Returns -1 if the part
+    /// is never decoupled from the vessel.
+    pub fn get_decouple_stage(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<i32> {
+        krpc.call::<SVarint<i32>>(
+            "SpaceCenter",
+            "Part_get_DecoupleStage",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// Whether the part is massless.
+    pub fn get_massless(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Part_get_Massless", &[&self.class])
+    }
+
+    /// The current mass of the part, including resources it contains, in
+    /// kilograms. Returns zero if the part is massless.
+    pub fn get_mass(&self, krpc: &mut KrpcConnection) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Part_get_Mass", &[&self.class])
+    }
+
+    /// The mass of the part, not including any resources it contains, in
+    /// kilograms. Returns zero if the part is massless.
+    pub fn get_dry_mass(&self, krpc: &mut KrpcConnection) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Part_get_DryMass", &[&self.class])
+    }
+
+    /// Whether the part is shielded from the exterior of the vessel, for
+    /// example by a fairing.
+    pub fn get_shielded(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Part_get_Shielded", &[&self.class])
+    }
+
+    /// The dynamic pressure acting on the part, in Pascals.
+    pub fn get_dynamic_pressure(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Part_get_DynamicPressure", &[&self.class])
+    }
+
+    /// The impact tolerance of the part, in meters per second.
+    pub fn get_impact_tolerance(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Part_get_ImpactTolerance", &[&self.class])
+    }
+
+    /// Temperature of the part, in Kelvin.
+    pub fn get_temperature(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Part_get_Temperature", &[&self.class])
+    }
+
+    /// Temperature of the skin of the part, in Kelvin.
+    pub fn get_skin_temperature(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Part_get_SkinTemperature", &[&self.class])
+    }
+
+    /// Maximum temperature that the part can survive, in Kelvin.
+    pub fn get_max_temperature(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Part_get_MaxTemperature", &[&self.class])
+    }
+
+    /// Maximum temperature that the skin of the part can survive, in Kelvin.
+    pub fn get_max_skin_temperature(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f64> {
+        krpc.call("SpaceCenter", "Part_get_MaxSkinTemperature", &[&self.class])
+    }
+
+    /// A measure of how much energy it takes to increase the internal
+    /// temperature of the part, in Joules per Kelvin.
+    pub fn get_thermal_mass(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Part_get_ThermalMass", &[&self.class])
+    }
+
+    /// A measure of how much energy it takes to increase the skin temperature
+    /// of the part, in Joules per Kelvin.
+    pub fn get_thermal_skin_mass(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Part_get_ThermalSkinMass", &[&self.class])
+    }
+
+    /// A measure of how much energy it takes to increase the temperature of the
+    /// resources contained in the part, in Joules per Kelvin.
+    pub fn get_thermal_resource_mass(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Part_get_ThermalResourceMass",
+            &[&self.class],
+        )
+    }
+
+    /// The rate at which heat energy is begin generated by the part. For
+    /// example, some engines generate heat by combusting fuel. Measured in
+    /// energy per unit time, or power, in Watts. A positive value means the
+    /// part is gaining heat energy, and negative means it is losing heat
+    /// energy.
+    pub fn get_thermal_internal_flux(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Part_get_ThermalInternalFlux",
+            &[&self.class],
+        )
+    }
+
+    /// The rate at which heat energy is conducting into or out of the part via
+    /// contact with other parts. Measured in energy per unit time, or power, in
+    /// Watts. A positive value means the part is gaining heat energy, and
+    /// negative means it is losing heat energy.
+    pub fn get_thermal_conduction_flux(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Part_get_ThermalConductionFlux",
+            &[&self.class],
+        )
+    }
+
+    /// The rate at which heat energy is convecting into or out of the part from
+    /// the surrounding atmosphere. Measured in energy per unit time, or power,
+    /// in Watts. A positive value means the part is gaining heat energy, and
+    /// negative means it is losing heat energy.
+    pub fn get_thermal_convection_flux(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Part_get_ThermalConvectionFlux",
+            &[&self.class],
+        )
+    }
+
+    /// The rate at which heat energy is radiating into or out of the part from
+    /// the surrounding environment. Measured in energy per unit time, or power,
+    /// in Watts. A positive value means the part is gaining heat energy, and
+    /// negative means it is losing heat energy.
+    pub fn get_thermal_radiation_flux(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Part_get_ThermalRadiationFlux",
+            &[&self.class],
+        )
+    }
+
+    /// The rate at which heat energy is transferring between the part's skin
+    /// and its internals. Measured in energy per unit time, or power, in Watts.
+    /// A positive value means the part's internals are gaining heat energy, and
+    /// negative means its skin is gaining heat energy.
+    pub fn get_thermal_skin_to_internal_flux(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Part_get_ThermalSkinToInternalFlux",
+            &[&self.class],
+        )
+    }
+
+    /// A [`Resources`](crate::Resources) object for the part.
+    pub fn get_resources(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Resources> {
+        krpc.call("SpaceCenter", "Part_get_Resources", &[&self.class])
+            .map(Resources::new)
+    }
+
+    /// Whether this part is crossfeed capable.
+    pub fn get_crossfeed(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Part_get_Crossfeed", &[&self.class])
+    }
+
+    /// Whether this part is a fuel line.
+    pub fn get_is_fuel_line(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Part_get_IsFuelLine", &[&self.class])
+    }
+
+    /// The parts that are connected to this part via fuel lines, where the
+    /// direction of the fuel line is into this part.
+    pub fn get_fuel_lines_from(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<Part>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Part_get_FuelLinesFrom",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(Part::new).collect())
+    }
+
+    /// The parts that are connected to this part via fuel lines, where the
+    /// direction of the fuel line is out of this part.
+    pub fn get_fuel_lines_to(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<Part>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Part_get_FuelLinesTo",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(Part::new).collect())
+    }
+
+    /// The modules for this part.
+    pub fn get_modules(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<Module>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Part_get_Modules",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(Module::new).collect())
+    }
+
+    /// A [`ControlSurface`](crate::ControlSurface) if the part is an
+    /// aerodynamic control surface, otherwise `None`.
+    pub fn get_control_surface(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Option<ControlSurface>> {
+        krpc.call::<Class>(
+            "SpaceCenter",
+            "Part_get_ControlSurface",
+            &[&self.class],
+        )
+        .map(|x| x.into_option().map(ControlSurface::new))
+    }
+
+    /// A [`Decoupler`](crate::Decoupler) if the part is a decoupler, otherwise
+    /// `None`.
+    pub fn get_decoupler(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Option<Decoupler>> {
+        krpc.call::<Class>("SpaceCenter", "Part_get_Decoupler", &[&self.class])
+            .map(|x| x.into_option().map(Decoupler::new))
+    }
+
+    /// A [`DockingPort`](crate::DockingPort) if the part is a docking port,
+    /// otherwise `None`.
+    pub fn get_docking_port(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Option<DockingPort>> {
+        krpc.call::<Class>(
+            "SpaceCenter",
+            "Part_get_DockingPort",
+            &[&self.class],
+        )
+        .map(|x| x.into_option().map(DockingPort::new))
+    }
+
+    /// An [`Engine`](crate::Engine) if the part is an engine, otherwise `None`.
+    pub fn get_engine(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Option<Engine>> {
+        krpc.call::<Class>("SpaceCenter", "Part_get_Engine", &[&self.class])
+            .map(|x| x.into_option().map(Engine::new))
+    }
+
+    /// A [`Fairing`](crate::Fairing) if the part is a fairing, otherwise
+    /// `None`.
+    pub fn get_fairing(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Option<Fairing>> {
+        krpc.call::<Class>("SpaceCenter", "Part_get_Fairing", &[&self.class])
+            .map(|x| x.into_option().map(Fairing::new))
+    }
+
+    /// A [`Leg`](crate::Leg) if the part is a landing leg, otherwise `None`.
+    pub fn get_leg(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Option<Leg>> {
+        krpc.call::<Class>("SpaceCenter", "Part_get_Leg", &[&self.class])
+            .map(|x| x.into_option().map(Leg::new))
+    }
+
+    /// A [`Light`](crate::Light) if the part is a light, otherwise `None`.
+    pub fn get_light(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Option<Light>> {
+        krpc.call::<Class>("SpaceCenter", "Part_get_Light", &[&self.class])
+            .map(|x| x.into_option().map(Light::new))
+    }
+
+    /// A [`Parachute`](crate::Parachute) if the part is a parachute, otherwise
+    /// `None`.
+    pub fn get_parachute(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Option<Parachute>> {
+        krpc.call::<Class>("SpaceCenter", "Part_get_Parachute", &[&self.class])
+            .map(|x| x.into_option().map(Parachute::new))
+    }
+
+    /// A [`RCS`](crate::RCS) if the part is an RCS block/thruster, otherwise
+    /// `None`.
+    pub fn get_rcs(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Option<RCS>> {
+        krpc.call::<Class>("SpaceCenter", "Part_get_RCS", &[&self.class])
+            .map(|x| x.into_option().map(RCS::new))
+    }
+
+    /// A [`ReactionWheel`](crate::ReactionWheel) if the part is a reaction
+    /// wheel, otherwise `None`.
+    pub fn get_reaction_wheel(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Option<ReactionWheel>> {
+        krpc.call::<Class>(
+            "SpaceCenter",
+            "Part_get_ReactionWheel",
+            &[&self.class],
+        )
+        .map(|x| x.into_option().map(ReactionWheel::new))
+    }
+
+    /// A [`SolarPanel`](crate::SolarPanel) if the part is a solar panel,
+    /// otherwise `None`.
+    pub fn get_solar_panel(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Option<SolarPanel>> {
+        krpc.call::<Class>("SpaceCenter", "Part_get_SolarPanel", &[&self.class])
+            .map(|x| x.into_option().map(SolarPanel::new))
+    }
+
+    /// A [`Wheel`](crate::Wheel) if the part is a wheel, otherwise `None`.
+    pub fn get_wheel(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Option<Wheel>> {
+        krpc.call::<Class>("SpaceCenter", "Part_get_Wheel", &[&self.class])
+            .map(|x| x.into_option().map(Wheel::new))
+    }
+
+    /// The moment of inertia of the part in `kg.m^2` around its center of mass
+    /// in the parts reference frame
+    /// ([`ReferenceFrame`](crate::ReferenceFrame)).
+    pub fn get_moment_of_inertia(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vector3> {
+        krpc.call::<TupleOf<Vector3>>(
+            "SpaceCenter",
+            "Part_get_MomentOfInertia",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The inertia tensor of the part in the parts reference frame
+    /// ([`ReferenceFrame`](crate::ReferenceFrame)). Returns the 3x3 matrix as a
+    /// list of elements, in row-major order.
+    pub fn get_inertia_tensor(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<f64>> {
+        krpc.call::<ListOf<f64>>(
+            "SpaceCenter",
+            "Part_get_InertiaTensor",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The reference frame that is fixed relative to this part, and centered on
+    /// a fixed position within the part, defined by the parts model.
+    ///
+    /// - The origin is at the position of the part, as returned by
+    ///   [`Part::position`].
+    /// - The axes rotate with the part.
+    /// - The x, y and z axis directions depend on the design of the part.
+    ///
+    /// # Note
+    ///
+    /// For docking port parts, this reference frame is not necessarily
+    /// equivalent to the reference frame for the docking port, returned by
+    /// [`DockingPort::get_reference_frame`](
+    /// crate::DockingPort::get_reference_frame).
+    pub fn get_reference_frame(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<ReferenceFrame> {
+        krpc.call("SpaceCenter", "Part_get_ReferenceFrame", &[&self.class])
+            .map(ReferenceFrame::new)
+    }
+
+    /// The reference frame that is fixed relative to this part, and centered on
+    /// its center of mass.
+    ///
+    /// - The origin is at the center of mass of the part, as returned by
+    ///   [`Part::center_of_mass`].
+    /// - The axes rotate with the part.
+    /// - The x, y and z axis directions depend on the design of the part.
+    ///
+    /// # Note
+    ///
+    /// For docking port parts, this reference frame is not necessarily
+    /// equivalent to the reference frame for the docking port, returned by
+    /// [`DockingPort::get_reference_frame`](
+    /// crate::DockingPort::get_reference_frame).
+    pub fn get_center_of_mass_reference_frame(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<ReferenceFrame> {
+        krpc.call(
+            "SpaceCenter",
+            "Part_get_CenterOfMassReferenceFrame",
+            &[&self.class],
+        )
+        .map(ReferenceFrame::new)
+    }
+}
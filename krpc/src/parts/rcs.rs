@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+
+use crate::{
+    class::Class,
+    parts::Part,
+    values::{DictOf, ListOf, TupleOf, Vector3},
+    CallResult, KrpcConnection,
+};
+
+/// An RCS block or thruster. Obtained by calling
+/// [`Part::get_rcs`](crate::Part::get_rcs).
+pub struct RCS {
+    pub(crate) class: Class,
+}
+
+impl RCS {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// The part object for this RCS.
+    pub fn get_part(&self, krpc: &mut KrpcConnection) -> CallResult<Part> {
+        krpc.call("SpaceCenter", "RCS_get_Part", &[&self.class])
+            .map(Part::new)
+    }
+
+    /// Whether the RCS thrusters are active. An RCS thruster is inactive if the
+    /// RCS action group is disabled
+    /// ([`Control::get_rcs`](crate::Control::get_rcs)), the RCS thruster itself
+    /// is not enabled ([`RCS::get_enabled`]) or it is covered by a fairing
+    /// ([`Part::get_shielded`](crate::Part::get_shielded)).
+    pub fn get_active(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "RCS_get_Active", &[&self.class])
+    }
+
+    /// Whether the RCS thrusters are enabled.
+    pub fn get_enabled(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "RCS_get_Enabled", &[&self.class])
+    }
+
+    /// Whether the RCS thrusters are enabled.
+    pub fn set_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "RCS_set_Enabled", &[&self.class, &value])
+    }
+
+    /// Whether the RCS thruster will fire when pitch control input is given.
+    pub fn get_pitch_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "RCS_get_PitchEnabled", &[&self.class])
+    }
+
+    /// Whether the RCS thruster will fire when pitch control input is given.
+    pub fn set_pitch_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "RCS_set_PitchEnabled",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Whether the RCS thruster will fire when yaw control input is given.
+    pub fn get_yaw_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "RCS_get_YawEnabled", &[&self.class])
+    }
+
+    /// Whether the RCS thruster will fire when yaw control input is given.
+    pub fn set_yaw_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "RCS_set_YawEnabled", &[&self.class, &value])
+    }
+
+    /// Whether the RCS thruster will fire when roll control input is given.
+    pub fn get_roll_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "RCS_get_RollEnabled", &[&self.class])
+    }
+
+    /// Whether the RCS thruster will fire when roll control input is given.
+    pub fn set_roll_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "RCS_set_RollEnabled", &[&self.class, &value])
+    }
+
+    /// Whether the RCS thruster will fire when pitch control input is given.
+    pub fn get_forward_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "RCS_get_ForwardEnabled", &[&self.class])
+    }
+
+    /// Whether the RCS thruster will fire when pitch control input is given.
+    pub fn set_forward_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "RCS_set_ForwardEnabled",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Whether the RCS thruster will fire when yaw control input is given.
+    pub fn get_up_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "RCS_get_UpEnabled", &[&self.class])
+    }
+
+    /// Whether the RCS thruster will fire when yaw control input is given.
+    pub fn set_up_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "RCS_set_UpEnabled", &[&self.class, &value])
+    }
+
+    /// Whether the RCS thruster will fire when roll control input is given.
+    pub fn get_right_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "RCS_get_RightEnabled", &[&self.class])
+    }
+
+    /// Whether the RCS thruster will fire when roll control input is given.
+    pub fn set_right_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "RCS_set_RightEnabled",
+            &[&self.class, &value],
+        )
+    }
+
+    /// The available torque, in Newton meters, that can be produced by this
+    /// RCS, in the positive and negative pitch, roll and yaw axes of the
+    /// vessel. These axes correspond to the coordinate axes of the
+    /// [`Vessel::get_reference_frame`](crate::Vessel::get_reference_frame).
+    /// Returns zero if RCS is disable.
+    pub fn get_available_torque(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<(Vector3, Vector3)> {
+        krpc.call::<TupleOf<(TupleOf<Vector3>, TupleOf<Vector3>)>>(
+            "SpaceCenter",
+            "RCS_get_AvailableTorque",
+            &[&self.class],
+        )
+        .map(|TupleOf((a, b))| (a.0, b.0))
+    }
+
+    /// The maximum amount of thrust that can be produced by the RCS thrusters
+    /// when active, in Newtons.
+    pub fn get_max_thrust(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "RCS_get_MaxThrust", &[&self.class])
+    }
+
+    /// The maximum amount of thrust that can be produced by the RCS thrusters
+    /// when active in a vacuum, in Newtons.
+    pub fn get_max_vacuum_thrust(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "RCS_get_MaxVacuumThrust", &[&self.class])
+    }
+
+    /// The current specific impulse of the RCS, in seconds. Returns zero if the
+    /// RCS is not active.
+    pub fn get_specific_impulse(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "RCS_get_SpecificImpulse", &[&self.class])
+    }
+
+    /// The vacuum specific impulse of the RCS, in seconds.
+    pub fn get_vacuum_specific_impulse(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "RCS_get_VacuumSpecificImpulse",
+            &[&self.class],
+        )
+    }
+
+    /// The specific impulse of the RCS at sea level on Kerbin, in seconds.
+    pub fn get_kerbin_sea_level_specific_impulse(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "RCS_get_KerbinSeaLevelSpecificImpulse",
+            &[&self.class],
+        )
+    }
+
+    /// The names of resources that the RCS consumes.
+    pub fn get_propellants(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<String>> {
+        krpc.call::<ListOf<String>>(
+            "SpaceCenter",
+            "RCS_get_Propellants",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// The ratios of resources that the RCS consumes. A dictionary mapping
+    /// resource names to the ratios at which they are consumed by the RCS.
+    pub fn get_propellant_ratios(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<HashMap<String, f32>> {
+        krpc.call::<DictOf<String, f32>>(
+            "SpaceCenter",
+            "RCS_get_PropellantRatios",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// Whether the RCS has fuel available.
+    ///
+    /// # Note
+    ///
+    /// The RCS thruster must be activated for this property to update
+    /// correctly.
+    pub fn get_has_fuel(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "RCS_get_HasFuel", &[&self.class])
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{
+    class::Class,
+    parts::Part,
+    values::{TupleOf, Vector3},
+    CallResult, KrpcConnection,
+};
+
+/// A reaction wheel. Obtained by calling
+/// [`Part::get_reaction_wheel`](crate::Part::get_reaction_wheel).
+pub struct ReactionWheel {
+    pub(crate) class: Class,
+}
+
+impl ReactionWheel {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// The part object for this reaction wheel.
+    pub fn get_part(&self, krpc: &mut KrpcConnection) -> CallResult<Part> {
+        krpc.call("SpaceCenter", "ReactionWheel_get_Part", &[&self.class])
+            .map(Part::new)
+    }
+
+    /// Whether the reaction wheel is active.
+    pub fn get_active(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "ReactionWheel_get_Active", &[&self.class])
+    }
+
+    /// Whether the reaction wheel is active.
+    pub fn set_active(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "ReactionWheel_set_Active",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Whether the reaction wheel is broken.
+    pub fn get_broken(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "ReactionWheel_get_Broken", &[&self.class])
+    }
+
+    /// The available torque, in Newton meters, that can be produced by this
+    /// reaction wheel, in the positive and negative pitch, roll and yaw axes of
+    /// the vessel. These axes correspond to the coordinate axes of the
+    /// [`Vessel::get_reference_frame`](crate::Vessel::get_reference_frame).
+    /// Returns zero if the reaction wheel is inactive or broken.
+    pub fn get_available_torque(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<(Vector3, Vector3)> {
+        krpc.call::<TupleOf<(TupleOf<Vector3>, TupleOf<Vector3>)>>(
+            "SpaceCenter",
+            "ReactionWheel_get_AvailableTorque",
+            &[&self.class],
+        )
+        .map(|TupleOf((a, b))| (a.0, b.0))
+    }
+
+    /// The maximum torque, in Newton meters, that can be produced by this
+    /// reaction wheel, when it is active, in the positive and negative pitch,
+    /// roll and yaw axes of the vessel. These axes correspond to the coordinate
+    /// axes of the
+    /// [`Vessel::get_reference_frame`](crate::Vessel::get_reference_frame).
+    pub fn get_max_torque(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<(Vector3, Vector3)> {
+        krpc.call::<TupleOf<(TupleOf<Vector3>, TupleOf<Vector3>)>>(
+            "SpaceCenter",
+            "ReactionWheel_get_MaxTorque",
+            &[&self.class],
+        )
+        .map(|TupleOf((a, b))| (a.0, b.0))
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{
+    class::Class, parts::Part, values::krpc_enum, CallResult, KrpcConnection,
+};
+
+krpc_enum! {
+    /// The state of a solar panel. See [`SolarPanel::get_state`].
+    pub enum SolarPanelState {
+        /// Solar panel is fully extended.
+        Extended = 0,
+        /// Solar panel is fully retracted.
+        Retracted = 1,
+        /// Solar panel is being extended.
+        Extending = 2,
+        /// Solar panel is being retracted.
+        Retracting = 3,
+        /// Solar panel is broken.
+        Broken = 4,
+    }
+}
+
+/// A solar panel. Obtained by calling
+/// [`Part::get_solar_panel`](crate::Part::get_solar_panel).
+pub struct SolarPanel {
+    pub(crate) class: Class,
+}
+
+impl SolarPanel {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// The part object for this solar panel.
+    pub fn get_part(&self, krpc: &mut KrpcConnection) -> CallResult<Part> {
+        krpc.call("SpaceCenter", "SolarPanel_get_Part", &[&self.class])
+            .map(Part::new)
+    }
+
+    /// Whether the solar panel is deployable.
+    pub fn get_deployable(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "SolarPanel_get_Deployable", &[&self.class])
+    }
+
+    /// Whether the solar panel is extended.
+    pub fn get_deployed(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "SolarPanel_get_Deployed", &[&self.class])
+    }
+
+    /// Whether the solar panel is extended.
+    pub fn set_deployed(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "SolarPanel_set_Deployed",
+            &[&self.class, &value],
+        )
+    }
+
+    /// The current state of the solar panel.
+    pub fn get_state(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<SolarPanelState> {
+        krpc.call("SpaceCenter", "SolarPanel_get_State", &[&self.class])
+    }
+
+    /// The current amount of energy being generated by the solar panel, in
+    /// units of charge per second.
+    pub fn get_energy_flow(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "SolarPanel_get_EnergyFlow", &[&self.class])
+    }
+
+    /// The current amount of sunlight that is incident on the solar panel, as a
+    /// percentage. A value between 0 and 1.
+    pub fn get_sun_exposure(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "SolarPanel_get_SunExposure", &[&self.class])
+    }
+}
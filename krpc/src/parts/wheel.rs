@@ -0,0 +1,433 @@
+use crate::{
+    class::Class, parts::Part, values::krpc_enum, CallResult, KrpcConnection,
+};
+
+krpc_enum! {
+    /// The state of a wheel. See [`Wheel::get_state`].
+    pub enum WheelState {
+        /// Wheel is fully deployed.
+        Deployed = 0,
+        /// Wheel is fully retracted.
+        Retracted = 1,
+        /// Wheel is being deployed.
+        Deploying = 2,
+        /// Wheel is being retracted.
+        Retracting = 3,
+        /// Wheel is broken.
+        Broken = 4,
+    }
+}
+
+krpc_enum! {
+    /// The state of the motor on a powered wheel. See
+    /// [`Wheel::get_motor_state`].
+    pub enum MotorState {
+        /// The motor is idle.
+        Idle = 0,
+        /// The motor is running.
+        Running = 1,
+        /// The motor is disabled.
+        Disabled = 2,
+        /// The motor is inoperable.
+        Inoperable = 3,
+        /// The motor does not have enough resources to run.
+        NotEnoughResources = 4,
+    }
+}
+
+/// A wheel. Includes landing gear and rover wheels. Obtained by calling
+/// [`Part::get_wheel`](crate::Part::get_wheel). Can be used to control the
+/// motors, steering and deployment of wheels, among other things.
+pub struct Wheel {
+    pub(crate) class: Class,
+}
+
+impl Wheel {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// The part object for this wheel.
+    pub fn get_part(&self, krpc: &mut KrpcConnection) -> CallResult<Part> {
+        krpc.call("SpaceCenter", "Wheel_get_Part", &[&self.class])
+            .map(Part::new)
+    }
+
+    /// The current state of the wheel.
+    pub fn get_state(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<WheelState> {
+        krpc.call("SpaceCenter", "Wheel_get_State", &[&self.class])
+    }
+
+    /// Radius of the wheel, in meters.
+    pub fn get_radius(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Wheel_get_Radius", &[&self.class])
+    }
+
+    /// Whether the wheel is touching the ground.
+    pub fn get_grounded(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Wheel_get_Grounded", &[&self.class])
+    }
+
+    /// Whether the wheel has brakes.
+    pub fn get_has_brakes(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Wheel_get_HasBrakes", &[&self.class])
+    }
+
+    /// The braking force, as a percentage of maximum, when the brakes are
+    /// applied.
+    pub fn get_brakes(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Wheel_get_Brakes", &[&self.class])
+    }
+
+    /// The braking force, as a percentage of maximum, when the brakes are
+    /// applied.
+    pub fn set_brakes(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Wheel_set_Brakes", &[&self.class, &value])
+    }
+
+    /// Whether automatic friction control is enabled.
+    pub fn get_auto_friction_control(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call(
+            "SpaceCenter",
+            "Wheel_get_AutoFrictionControl",
+            &[&self.class],
+        )
+    }
+
+    /// Whether automatic friction control is enabled.
+    pub fn set_auto_friction_control(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Wheel_set_AutoFrictionControl",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Manual friction control value. Only has an effect if automatic friction
+    /// control is disabled. A value between 0 and 5 inclusive.
+    pub fn get_manual_friction_control(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Wheel_get_ManualFrictionControl",
+            &[&self.class],
+        )
+    }
+
+    /// Manual friction control value. Only has an effect if automatic friction
+    /// control is disabled. A value between 0 and 5 inclusive.
+    pub fn set_manual_friction_control(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Wheel_set_ManualFrictionControl",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Whether the wheel is deployable.
+    pub fn get_deployable(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Wheel_get_Deployable", &[&self.class])
+    }
+
+    /// Whether the wheel is deployed.
+    pub fn get_deployed(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Wheel_get_Deployed", &[&self.class])
+    }
+
+    /// Whether the wheel is deployed.
+    pub fn set_deployed(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call("SpaceCenter", "Wheel_set_Deployed", &[&self.class, &value])
+    }
+
+    /// Whether the wheel is powered by a motor.
+    pub fn get_powered(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Wheel_get_Powered", &[&self.class])
+    }
+
+    /// Whether the motor is enabled.
+    pub fn get_motor_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Wheel_get_MotorEnabled", &[&self.class])
+    }
+
+    /// Whether the motor is enabled.
+    pub fn set_motor_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Wheel_set_MotorEnabled",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Whether the direction of the motor is inverted.
+    pub fn get_motor_inverted(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Wheel_get_MotorInverted", &[&self.class])
+    }
+
+    /// Whether the direction of the motor is inverted.
+    pub fn set_motor_inverted(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Wheel_set_MotorInverted",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Whether the direction of the motor is inverted.
+    pub fn get_motor_state(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<MotorState> {
+        krpc.call("SpaceCenter", "Wheel_get_MotorState", &[&self.class])
+    }
+
+    /// The output of the motor. This is the torque currently being generated,
+    /// in Newton meters.
+    pub fn get_motor_output(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Wheel_get_MotorOutput", &[&self.class])
+    }
+
+    /// Whether automatic traction control is enabled. A wheel only has traction
+    /// control if it is powered.
+    pub fn get_traction_control_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call(
+            "SpaceCenter",
+            "Wheel_get_TractionControlEnabled",
+            &[&self.class],
+        )
+    }
+
+    /// Whether automatic traction control is enabled. A wheel only has traction
+    /// control if it is powered.
+    pub fn set_traction_control_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Wheel_set_TractionControlEnabled",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Setting for the traction control. Only takes effect if the wheel has
+    /// automatic traction control enabled. A value between 0 and 5 inclusive.
+    pub fn get_traction_control(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Wheel_get_TractionControl", &[&self.class])
+    }
+
+    /// Setting for the traction control. Only takes effect if the wheel has
+    /// automatic traction control enabled. A value between 0 and 5 inclusive.
+    pub fn set_traction_control(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Wheel_set_TractionControl",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Manual setting for the motor limiter. Only takes effect if the wheel has
+    /// automatic traction control disabled. A value between 0 and 100
+    /// inclusive.
+    pub fn get_drive_limiter(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Wheel_get_DriveLimiter", &[&self.class])
+    }
+
+    /// Manual setting for the motor limiter. Only takes effect if the wheel has
+    /// automatic traction control disabled. A value between 0 and 100
+    /// inclusive.
+    pub fn set_drive_limiter(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: f32,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Wheel_set_DriveLimiter",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Whether the wheel has steering.
+    pub fn get_steerable(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Wheel_get_Steerable", &[&self.class])
+    }
+
+    /// Whether the wheel steering is enabled.
+    pub fn get_steering_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Wheel_get_SteeringEnabled", &[&self.class])
+    }
+
+    /// Whether the wheel steering is enabled.
+    pub fn set_steering_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Wheel_set_SteeringEnabled",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Whether the wheel steering is inverted.
+    pub fn get_steering_inverted(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Wheel_get_SteeringInverted", &[&self.class])
+    }
+
+    /// Whether the wheel steering is inverted.
+    pub fn set_steering_inverted(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Wheel_set_SteeringInverted",
+            &[&self.class, &value],
+        )
+    }
+
+    /// Whether the wheel has suspension.
+    pub fn get_has_suspension(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Wheel_get_HasSuspension", &[&self.class])
+    }
+
+    /// Suspension spring strength, as set in the editor.
+    pub fn get_suspension_spring_strength(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Wheel_get_SuspensionSpringStrength",
+            &[&self.class],
+        )
+    }
+
+    /// Suspension damper strength, as set in the editor.
+    pub fn get_suspension_damper_strength(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call(
+            "SpaceCenter",
+            "Wheel_get_SuspensionDamperStrength",
+            &[&self.class],
+        )
+    }
+
+    /// Whether the wheel is broken.
+    pub fn get_broken(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Wheel_get_Broken", &[&self.class])
+    }
+
+    /// Whether the wheel is repairable.
+    pub fn get_repairable(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Wheel_get_Repairable", &[&self.class])
+    }
+
+    /// Current stress on the wheel.
+    pub fn get_stress(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Wheel_get_Stress", &[&self.class])
+    }
+
+    /// Stress tolerance of the wheel.
+    pub fn get_stress_tolerance(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Wheel_get_StressTolerance", &[&self.class])
+    }
+
+    /// Current stress on the wheel as a percentage of its stress tolerance.
+    pub fn get_stress_percentage(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Wheel_get_StressPercentage", &[&self.class])
+    }
+
+    /// Current deflection of the wheel.
+    pub fn get_deflection(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Wheel_get_Deflection", &[&self.class])
+    }
+
+    /// Current slip of the wheel.
+    pub fn get_slip(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Wheel_get_Slip", &[&self.class])
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{
+    class::Class,
+    values::{Quaternion, TupleOf, Vector3},
+    CallResult, KrpcConnection,
+};
+
+/// Represents a reference frame for positions, rotations and
+/// velocities. Contains:
+/// - The position of the origin.
+/// - The directions of the x, y and z axes.
+/// - The linear velocity of the frame.
+/// - The angular velocity of the frame.
+///
+/// # Note
+///
+/// Only used as a parameter to other functions. Obtained from objects it's
+/// relative to, like [`Vessel::get_reference_frame`].
+///
+/// [`Vessel::get_reference_frame`]: crate::Vessel::get_reference_frame
+pub struct ReferenceFrame {
+    pub(crate) class: Class,
+}
+
+impl ReferenceFrame {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// Create a relative reference frame. This is a custom reference frame
+    /// whose components offset the components of a parent reference frame.
+    ///
+    /// - `reference_frame`: The parent reference frame on which to base this
+    ///   reference frame.
+    /// - `position`: The offset of the position of the origin, as a position
+    ///   vector. Defaults to `(0, 0, 0)`
+    /// - `rotation`: The rotation to apply to the parent frames rotation, as a
+    ///   quaternion of the form `(x, y, z, w)`. Defaults to `(0, 0, 0, 1)`
+    ///   (i.e. no rotation)
+    /// - `velocity`: The linear velocity to offset the parent frame by, as a
+    ///   vector pointing in the direction of travel, whose magnitude is the
+    ///   speed in meters per second. Defaults to `(0, 0, 0)`.
+    /// - `angular_velocity`: The angular velocity to offset the parent frame
+    ///   by, as a vector. This vector points in the direction of the axis of
+    ///   rotation, and its magnitude is the speed of the rotation in radians
+    ///   per second. Defaults to `(0, 0, 0)`.
+    pub fn create_relative(
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+        position: Vector3,
+        rotation: Quaternion,
+        velocity: Vector3,
+        angular_velocity: Vector3,
+    ) -> CallResult<ReferenceFrame> {
+        krpc.call(
+            "SpaceCenter",
+            "ReferenceFrame_static_CreateRelative",
+            &[
+                &reference_frame.class,
+                &TupleOf(position),
+                &TupleOf(rotation),
+                &TupleOf(velocity),
+                &TupleOf(angular_velocity),
+            ],
+        )
+        .map(ReferenceFrame::new)
+    }
+
+    /// Create a hybrid reference frame. This is a custom reference frame whose
+    /// components inherited from other reference frames.
+    ///
+    /// - `position`: The reference frame providing the position of the origin.
+    /// - `rotation`: The reference frame providing the rotation of the frame.
+    /// - `velocity`: The reference frame providing the linear velocity of the
+    ///   frame.
+    /// - `angular_velocity`: The reference frame providing the angular velocity
+    ///   of the frame.
+    ///
+    /// # Note
+    ///
+    /// Use `position` frame for other components that should not be
+    /// changed.
+    pub fn create_hybrid(
+        krpc: &mut KrpcConnection,
+        position: &ReferenceFrame,
+        rotation: &ReferenceFrame,
+        velocity: &ReferenceFrame,
+        angular_velocity: &ReferenceFrame,
+    ) -> CallResult<ReferenceFrame> {
+        krpc.call(
+            "SpaceCenter",
+            "ReferenceFrame_static_CreateHybrid",
+            &[
+                &position.class,
+                &rotation.class,
+                &velocity.class,
+                &angular_velocity.class,
+            ],
+        )
+        .map(ReferenceFrame::new)
+    }
+}
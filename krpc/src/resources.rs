@@ -0,0 +1,166 @@
+mod resource;
+mod resource_transfer;
+
+use crate::{
+    class::Class,
+    values::{krpc_enum, ListOf},
+    CallResult, KrpcConnection,
+};
+
+pub use resource::Resource;
+pub use resource_transfer::ResourceTransfer;
+
+krpc_enum! {
+    /// The way in which a resource flows between parts. See
+    /// [`Resources::flow_mode`].
+    pub enum ResourceFlowMode {
+        /// The resource flows to any part in the vessel. For example, electric
+        /// charge.
+        Vessel = 0,
+        /// The resource flows from parts in the first stage, followed by the
+        /// second, and so on. For example, mono-propellant.
+        Stage = 1,
+        /// The resource flows between adjacent parts within the vessel. For
+        /// example, liquid fuel or oxidizer.
+        Adjacent = 2,
+        /// The resource does not flow. For example, solid fuel.
+        None = 3,
+    }
+}
+
+/// Represents the collection of resources stored in a vessel, stage or part.
+/// Created by calling [`Vessel::get_resources`](crate::Vessel::get_resources),
+/// [`Vessel::resources_in_decouple_stage`](
+/// crate::Vessel::resources_in_decouple_stage) or
+/// [`Part::get_resources`](crate::Part::get_resources).
+pub struct Resources {
+    pub(crate) class: Class,
+}
+
+impl Resources {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// All the individual resources with the given name that can be stored.
+    pub fn with_resource(
+        &self,
+        krpc: &mut KrpcConnection,
+        name: &str,
+    ) -> CallResult<Vec<Resource>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Resources_WithResource",
+            &[&self.class, &name],
+        )
+        .map(|x| x.0.into_iter().map(Resource::new).collect())
+    }
+
+    /// Check whether the named resource can be stored.
+    ///
+    /// - `name`: The name of the resource.
+    pub fn has_resource(
+        &self,
+        krpc: &mut KrpcConnection,
+        name: &str,
+    ) -> CallResult<bool> {
+        krpc.call(
+            "SpaceCenter",
+            "Resources_HasResource",
+            &[&self.class, &name],
+        )
+    }
+
+    /// Returns the amount of a resource that can be stored.
+    ///
+    /// - `name`: The name of the resource.
+    pub fn max(
+        &self,
+        krpc: &mut KrpcConnection,
+        name: &str,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Resources_Max", &[&self.class, &name])
+    }
+
+    /// Returns the amount of a resource that is currently stored.
+    ///
+    /// - `name`: The name of the resource.
+    pub fn amount(
+        &self,
+        krpc: &mut KrpcConnection,
+        name: &str,
+    ) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Resources_Amount", &[&self.class, &name])
+    }
+
+    /// Returns the density of a resource, in `kg/l`.
+    ///
+    /// - `name`: The name of the resource.
+    pub fn density(krpc: &mut KrpcConnection, name: &str) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Resources_static_Density", &[&name])
+    }
+
+    /// Returns the flow mode of a resource.
+    ///
+    /// - `name`: The name of the resource.
+    pub fn flow_mode(
+        krpc: &mut KrpcConnection,
+        name: &str,
+    ) -> CallResult<ResourceFlowMode> {
+        krpc.call("SpaceCenter", "Resources_static_FlowMode", &[&name])
+    }
+
+    /// All the individual resources that can be stored.
+    pub fn get_all(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<Resource>> {
+        krpc.call::<ListOf<Class>>(
+            "SpaceCenter",
+            "Resources_get_All",
+            &[&self.class],
+        )
+        .map(|x| x.0.into_iter().map(Resource::new).collect())
+    }
+
+    /// A list of resource names that can be stored.
+    pub fn get_names(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Vec<String>> {
+        krpc.call::<ListOf<String>>(
+            "SpaceCenter",
+            "Resources_get_Names",
+            &[&self.class],
+        )
+        .map(|x| x.0)
+    }
+
+    /// Whether use of all the resources are enabled.
+    ///
+    /// # Note
+    ///
+    /// This is `true` if all of the resources are enabled. If any of the
+    /// resources are not enabled, this is `false`.
+    pub fn get_enabled(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Resources_get_Enabled", &[&self.class])
+    }
+
+    /// Whether use of all the resources are enabled.
+    ///
+    /// # Note
+    ///
+    /// This is `true` if all of the resources are enabled. If any of the
+    /// resources are not enabled, this is `false`.
+    pub fn set_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Resources_set_Enabled",
+            &[&self.class, &value],
+        )
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{
+    class::Class, parts::Part, resources::ResourceFlowMode, CallResult,
+    KrpcConnection,
+};
+
+/// An individual resource stored within a part. Created using methods in the
+/// [`Resources`](crate::Resources) class.
+pub struct Resource {
+    pub(crate) class: Class,
+}
+
+impl Resource {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// The name of the resource.
+    pub fn get_name(&self, krpc: &mut KrpcConnection) -> CallResult<String> {
+        krpc.call("SpaceCenter", "Resource_get_Name", &[&self.class])
+    }
+
+    /// The part containing the resource.
+    pub fn get_part(&self, krpc: &mut KrpcConnection) -> CallResult<Part> {
+        krpc.call("SpaceCenter", "Resource_get_Part", &[&self.class])
+            .map(Part::new)
+    }
+
+    /// The total amount of the resource that can be stored in the part.
+    pub fn get_max(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Resource_get_Max", &[&self.class])
+    }
+
+    /// The amount of the resource that is currently stored in the part.
+    pub fn get_amount(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Resource_get_Amount", &[&self.class])
+    }
+
+    /// The density of the resource, in `kg/l`.
+    pub fn get_density(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "Resource_get_Density", &[&self.class])
+    }
+
+    /// The flow mode of the resource.
+    pub fn get_flow_mode(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<ResourceFlowMode> {
+        krpc.call("SpaceCenter", "Resource_get_FlowMode", &[&self.class])
+    }
+
+    /// Whether use of this resource is enabled.
+    pub fn get_enabled(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Resource_get_Enabled", &[&self.class])
+    }
+
+    /// Whether use of this resource is enabled.
+    pub fn set_enabled(
+        &self,
+        krpc: &mut KrpcConnection,
+        value: bool,
+    ) -> CallResult {
+        krpc.call(
+            "SpaceCenter",
+            "Resource_set_Enabled",
+            &[&self.class, &value],
+        )
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{class::Class, parts::Part, CallResult, KrpcConnection};
+
+/// Transfer resources between parts.
+pub struct ResourceTransfer {
+    pub(crate) class: Class,
+}
+
+impl ResourceTransfer {
+    pub(crate) fn new(class: Class) -> Self {
+        Self { class }
+    }
+
+    /// Start transferring a resource transfer between a pair of parts. The
+    /// transfer will move at most `max_amount` units of the resource, depending
+    /// on how much of the resource is available in the source part and how much
+    /// storage is available in the destination part. Use
+    /// [`ResourceTransfer::get_complete`] to check if the transfer is complete.
+    /// Use [`ResourceTransfer::get_amount`] to see how much of the resource has
+    /// been transferred.
+    ///
+    /// - `from_part`: The part to transfer from.
+    /// - `to_part`: The part to transfer to.
+    /// - `resource`: The name of the resource to transfer.
+    /// - `max_amount`: The maximum amount of resource to transfer.
+    pub fn start(
+        krpc: &mut KrpcConnection,
+        from_part: &Part,
+        to_part: &Part,
+        resource: &str,
+        max_amount: f32,
+    ) -> CallResult<ResourceTransfer> {
+        krpc.call(
+            "SpaceCenter",
+            "ResourceTransfer_static_Start",
+            &[&from_part.class, &to_part.class, &resource, &max_amount],
+        )
+        .map(ResourceTransfer::new)
+    }
+
+    /// Whether the transfer has completed.
+    pub fn get_complete(&self, krpc: &mut KrpcConnection) -> CallResult<bool> {
+        krpc.call(
+            "SpaceCenter",
+            "ResourceTransfer_get_Complete",
+            &[&self.class],
+        )
+    }
+
+    /// The amount of the resource that has been transferred.
+    pub fn get_amount(&self, krpc: &mut KrpcConnection) -> CallResult<f32> {
+        krpc.call("SpaceCenter", "ResourceTransfer_get_Amount", &[&self.class])
+    }
+}
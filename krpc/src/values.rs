@@ -0,0 +1,182 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    io::{Read, Write},
+};
+
+use krpc_proto::{Dictionary, List, Tuple};
+use protobuf_but_worse::encoding::{Decode, Encode, EncodeDyn, EncodingError};
+
+/// Vector `(x, y, z)`, in a reference frame chosen by procedure arguments
+pub type Vector3 = (f64, f64, f64);
+
+/// Rotation `(x, y, z, w)`, in a reference frame chosen by procedure arguments
+pub type Quaternion = (f64, f64, f64, f64);
+
+/// `List` of procedure result, with items decoded as `T`. Also used for
+/// `Set`, which has the same encoding
+pub(crate) struct ListOf<T>(pub Vec<T>);
+
+impl<T: Decode> Decode for ListOf<T> {
+    fn decode<R: Read>(r: R) -> Result<Self, EncodingError> {
+        List::decode(r)?
+            .items
+            .iter()
+            .map(|x| T::decode(x.as_slice()))
+            .collect::<Result<_, _>>()
+            .map(ListOf)
+    }
+}
+
+/// `Dictionary` of procedure result, with keys and values decoded
+pub(crate) struct DictOf<K, V>(pub HashMap<K, V>);
+
+impl<K: Decode + Eq + Hash, V: Decode> Decode for DictOf<K, V> {
+    fn decode<R: Read>(r: R) -> Result<Self, EncodingError> {
+        Dictionary::decode(r)?
+            .entries
+            .iter()
+            .map(|x| {
+                let key = K::decode(x.key.as_slice())?;
+                Ok((key, V::decode(x.value.as_slice())?))
+            })
+            .collect::<Result<_, _>>()
+            .map(DictOf)
+    }
+}
+
+/// `Tuple` of procedure argument or result, as Rust tuple
+pub(crate) struct TupleOf<T>(pub T);
+
+macro_rules! impl_tuple {
+    ($($name:ident: $index:tt),*) => {
+        impl<$($name: Encode),*> TupleOf<($($name,)*)> {
+            fn tuple(&self) -> Result<Tuple, EncodingError> {
+                let items = vec![$((self.0).$index.encode_to_vec()?),*];
+                Ok(Tuple { items })
+            }
+        }
+
+        impl<$($name: Encode),*> Encode for TupleOf<($($name,)*)> {
+            fn size(&self) -> u32 {
+                // Encoding of items only fails in `encode`
+                self.tuple().map_or(0, |x| x.size())
+            }
+
+            fn encode<W: Write>(&self, w: W) -> Result<(), EncodingError> {
+                self.tuple()?.encode(w)
+            }
+        }
+
+        impl<$($name: Decode),*> Decode for TupleOf<($($name,)*)> {
+            fn decode<R: Read>(r: R) -> Result<Self, EncodingError> {
+                let tuple = Tuple::decode(r)?;
+                let mut items = tuple.items.iter();
+                Ok(TupleOf(($(
+                    $name::decode(
+                        items
+                            .next()
+                            .ok_or(EncodingError::MissingField("Tuple.items"))?
+                            .as_slice(),
+                    )?,
+                )*)))
+            }
+        }
+    };
+}
+
+impl_tuple!(A: 0, B: 1);
+impl_tuple!(A: 0, B: 1, C: 2);
+impl_tuple!(A: 0, B: 1, C: 2, D: 3);
+
+/// Declares enum of a kRPC service, which is `sint32` on the wire
+macro_rules! krpc_enum {
+    (
+        $(#[$attr:meta])*
+        pub enum $name:ident {
+            $($(#[$variant_attr:meta])* $variant:ident = $number:literal,)*
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$variant_attr])* $variant = $number,)*
+        }
+
+        impl protobuf_but_worse::encoding::Encode for $name {
+            fn size(&self) -> u32 {
+                protobuf_but_worse::encoding::SVarint(*self as i32).size()
+            }
+
+            fn encode<W: std::io::Write>(
+                &self,
+                w: W,
+            ) -> Result<(), protobuf_but_worse::encoding::EncodingError> {
+                protobuf_but_worse::encoding::SVarint(*self as i32).encode(w)
+            }
+        }
+
+        impl protobuf_but_worse::encoding::Decode for $name {
+            fn decode<R: std::io::Read>(
+                r: R,
+            ) -> Result<Self, protobuf_but_worse::encoding::EncodingError> {
+                use protobuf_but_worse::encoding::{EncodingError, SVarint};
+                match SVarint::<i32>::decode(r)?.0 {
+                    $($number => Ok(Self::$variant),)*
+                    x => Err(EncodingError::InvalidEnumValue(
+                        stringify!($name),
+                        x as u32,
+                    )),
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use krpc_enum;
+
+#[cfg(test)]
+mod tests {
+    use krpc_proto::DictionaryEntry;
+
+    use super::*;
+
+    krpc_enum! {
+        pub enum Mode {
+            A = 0,
+            B = 1,
+            C = 2,
+        }
+    }
+
+    #[test]
+    fn enums_and_lists() {
+        assert_eq!(Mode::B.encode_to_vec().unwrap(), [2]);
+        assert_eq!(Mode::decode(&[4][..]).unwrap(), Mode::C);
+        assert!(Mode::decode(&[6][..]).is_err());
+
+        let list = List {
+            items: vec![vec![0], vec![2]],
+        };
+        let bytes = list.encode_to_vec().unwrap();
+        let modes = ListOf::<Mode>::decode(bytes.as_slice()).unwrap().0;
+        assert_eq!(modes, [Mode::A, Mode::B]);
+
+        let dict = Dictionary {
+            entries: vec![DictionaryEntry {
+                key: "a".to_string().encode_to_vec().unwrap(),
+                value: vec![2],
+            }],
+        };
+        let bytes = dict.encode_to_vec().unwrap();
+        let dict = DictOf::<String, Mode>::decode(bytes.as_slice()).unwrap();
+        assert_eq!(dict.0["a"], Mode::B);
+
+        let vector = TupleOf((1.0, 2.0, Mode::C));
+        let bytes = vector.encode_to_vec().unwrap();
+        assert_eq!(bytes.len() as u32, vector.size());
+        let decoded = TupleOf::<(f64, f64, Mode)>::decode(bytes.as_slice());
+        assert_eq!(decoded.unwrap().0, vector.0);
+        assert!(TupleOf::<(f64, f64)>::decode(&[][..]).is_err());
+    }
+}
@@ -1,7 +1,13 @@
-use crate::{class::Class, control::Control, CallResult, KrpcConnection};
+use protobuf_but_worse::encoding::SVarint;
+
+use crate::{
+    auto_pilot::AutoPilot, class::Class, control::Control, flight::Flight,
+    orbit::Orbit, parts::Parts, reference_frame::ReferenceFrame,
+    resources::Resources, CallResult, KrpcConnection,
+};
 
 pub struct Vessel {
-    class: Class,
+    pub(crate) class: Class,
 }
 
 impl Vessel {
@@ -20,4 +26,181 @@ impl Vessel {
         krpc.call("SpaceCenter", "Vessel_get_Control", &[&self.class])
             .map(Control::new)
     }
+
+    /// An [`AutoPilot`] object, that can be used to perform simple
+    /// auto-piloting of the vessel.
+    pub fn get_auto_pilot(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<AutoPilot> {
+        krpc.call("SpaceCenter", "Vessel_get_AutoPilot", &[&self.class])
+            .map(AutoPilot::new)
+    }
+
+    /// Returns a [`Flight`] object that can be used to get flight telemetry
+    /// for the vessel, in the specified reference frame. The game uses
+    /// vessel's surface reference frame
+    /// ([`Vessel::get_surface_reference_frame`]) for most of its readouts.
+    pub fn flight(
+        &self,
+        krpc: &mut KrpcConnection,
+        reference_frame: &ReferenceFrame,
+    ) -> CallResult<Flight> {
+        krpc.call(
+            "SpaceCenter",
+            "Vessel_Flight",
+            &[&self.class, &reference_frame.class],
+        )
+        .map(Flight::new)
+    }
+
+    /// The reference frame that is fixed relative to the vessel, and orientated
+    /// with the vessel.
+    ///
+    /// - The origin is at the center of mass of the vessel.
+    /// - The axes rotate with the vessel.
+    /// - The x-axis points out to the right of the vessel.
+    /// - The y-axis points in the forward direction of the vessel.
+    /// - The z-axis points out of the bottom off the vessel.
+    pub fn get_reference_frame(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<ReferenceFrame> {
+        krpc.call("SpaceCenter", "Vessel_get_ReferenceFrame", &[&self.class])
+            .map(ReferenceFrame::new)
+    }
+
+    /// The reference frame that is fixed relative to the vessel, and orientated
+    /// with the vessels orbital prograde/normal/radial directions.
+    ///
+    /// - The origin is at the center of mass of the vessel.
+    /// - The axes rotate with the orbital prograde/normal/radial directions.
+    /// - The x-axis points in the orbital anti-radial direction.
+    /// - The y-axis points in the orbital prograde direction.
+    /// - The z-axis points in the orbital normal direction.
+    ///
+    /// # Note
+    ///
+    /// Be careful not to confuse this with 'orbit' mode on the navball.
+    pub fn get_orbital_reference_frame(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<ReferenceFrame> {
+        krpc.call(
+            "SpaceCenter",
+            "Vessel_get_OrbitalReferenceFrame",
+            &[&self.class],
+        )
+        .map(ReferenceFrame::new)
+    }
+
+    /// The reference frame that is fixed relative to the vessel, and orientated
+    /// with the surface of the body being orbited.
+    ///
+    /// - The origin is at the center of mass of the vessel.
+    /// - The axes rotate with the north and up directions on the surface of the
+    ///   body.
+    /// - The x-axis points in the zenith direction (upwards, normal to the body
+    ///   being orbited, from the center of the body towards the center of mass
+    ///   of the vessel).
+    /// - The y-axis points northwards towards the astronomical horizon (north,
+    ///   and tangential to the surface of the body -- the direction in which a
+    ///   compass would point when on the surface).
+    /// - The z-axis points eastwards towards the astronomical horizon (east,
+    ///   and tangential to the surface of the body -- east on a compass when on
+    ///   the surface).
+    ///
+    /// # Note
+    ///
+    /// Be careful not to confuse this with 'surface' mode on the navball.
+    pub fn get_surface_reference_frame(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<ReferenceFrame> {
+        krpc.call(
+            "SpaceCenter",
+            "Vessel_get_SurfaceReferenceFrame",
+            &[&self.class],
+        )
+        .map(ReferenceFrame::new)
+    }
+
+    /// The reference frame that is fixed relative to the vessel, and orientated
+    /// with the velocity vector of the vessel relative to the surface of the
+    /// body being orbited.
+    ///
+    /// - The origin is at the center of mass of the vessel.
+    /// - The axes rotate with the vessel's velocity vector.
+    /// - The y-axis points in the direction of the vessel's velocity vector,
+    ///   relative to the surface of the body being orbited.
+    /// - The z-axis is in the plane of the astronomical horizon.
+    /// - The x-axis is orthogonal to the other two axes.
+    pub fn get_surface_velocity_reference_frame(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<ReferenceFrame> {
+        krpc.call(
+            "SpaceCenter",
+            "Vessel_get_SurfaceVelocityReferenceFrame",
+            &[&self.class],
+        )
+        .map(ReferenceFrame::new)
+    }
+
+    /// The current orbit of the vessel.
+    pub fn get_orbit(&self, krpc: &mut KrpcConnection) -> CallResult<Orbit> {
+        krpc.call("SpaceCenter", "Vessel_get_Orbit", &[&self.class])
+            .map(Orbit::new)
+    }
+
+    /// A [`Parts`](crate::Parts) object, that can used to interact with the
+    /// parts that make up this vessel.
+    pub fn get_parts(&self, krpc: &mut KrpcConnection) -> CallResult<Parts> {
+        krpc.call("SpaceCenter", "Vessel_get_Parts", &[&self.class])
+            .map(Parts::new)
+    }
+
+    /// Returns a [`Resources`](crate::Resources) object, that can used to get
+    /// information about resources stored in a given `stage`.
+    ///
+    /// - `stage`: Get resources for parts that are decoupled in this stage.
+    /// - `cumulative`: When `false`, returns the resources for parts decoupled
+    ///   in just the given stage. When `true` returns the resources decoupled
+    ///   in the given stage and all subsequent stages combined.
+    pub fn resources_in_decouple_stage(
+        &self,
+        krpc: &mut KrpcConnection,
+        stage: i32,
+        cumulative: bool,
+    ) -> CallResult<Resources> {
+        krpc.call(
+            "SpaceCenter",
+            "Vessel_ResourcesInDecoupleStage",
+            &[&self.class, &SVarint(stage), &cumulative],
+        )
+        .map(Resources::new)
+    }
+
+    /// A [`Resources`](crate::Resources) object, that can used to get
+    /// information about resources stored in the vessel.
+    pub fn get_resources(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<Resources> {
+        krpc.call("SpaceCenter", "Vessel_get_Resources", &[&self.class])
+            .map(Resources::new)
+    }
+
+    /// Recover the vessel.
+    pub fn recover(&self, krpc: &mut KrpcConnection) -> CallResult {
+        krpc.call("SpaceCenter", "Vessel_Recover", &[&self.class])
+    }
+
+    /// Whether the vessel is recoverable.
+    pub fn get_recoverable(
+        &self,
+        krpc: &mut KrpcConnection,
+    ) -> CallResult<bool> {
+        krpc.call("SpaceCenter", "Vessel_get_Recoverable", &[&self.class])
+    }
 }
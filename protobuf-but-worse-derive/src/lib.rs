@@ -0,0 +1,357 @@
+//! `#[derive(Encode, Decode)]` for hand-written messages, producing
+//! the same encoding as code generated from .proto files.
+//!
+//! Every field of a struct needs `#[pb(tag = N)]`. Integers are varints
+//! by default, `encoding = "zigzag"` (`sint32`) or `encoding = "fixed"`
+//! (`fixed32`, `sfixed64`...) changes that. Fields of other types are
+//! nested messages, or enums with `encoding = "varint"`.
+//! `Option<T>`, `Vec<T>` (packed) and `HashMap<K, V>` / `BTreeMap<K, V>`
+//! work like optional, repeated and map fields, with `key_encoding` for
+//! map keys. Plain fields are skipped when equal to their default.
+//!
+//! A tuple struct with single field is encoded just like that field.
+//!
+//! ```
+//! use protobuf_but_worse::encoding::{Decode, EncodeDyn};
+//! use protobuf_but_worse_derive::{Decode, Encode};
+//!
+//! #[derive(Encode, Decode, PartialEq, Default, Debug)]
+//! struct Point {
+//!     #[pb(tag = 1, encoding = "zigzag")]
+//!     x: i32,
+//!     #[pb(tag = 2)]
+//!     name: Option<String>,
+//!     #[pb(tag = 3)]
+//!     ids: Vec<u32>,
+//! }
+//!
+//! #[derive(Encode, Decode, PartialEq, Debug)]
+//! struct Id(#[pb(encoding = "fixed")] u32);
+//!
+//! let point = Point {
+//!     x: -1,
+//!     name: Some("a".into()),
+//!     ids: vec![1, 2],
+//! };
+//! let bytes = point.encode_to_vec().unwrap();
+//! assert_eq!(bytes, [8, 1, 18, 1, b'a', 26, 2, 1, 2]);
+//! assert_eq!(Point::decode(bytes.as_slice()).unwrap(), point);
+//! assert_eq!(Id(1).encode_to_vec().unwrap(), [1, 0, 0, 0]);
+//! assert_eq!(Id::decode(&[2, 0, 0, 0][..]).unwrap(), Id(2));
+//! ```
+
+use proc_macro2::{Span, TokenStream};
+use protobuf_but_worse::codegen::fields::{
+    gen_message_code, FieldSpec, MessageCode, Presence, ValueSpec,
+};
+use quote::quote;
+use syn::{
+    spanned::Spanned, Data, DeriveInput, Fields, GenericArgument, Ident, Lit,
+    Meta, NestedMeta, PathArguments, Type,
+};
+
+#[proc_macro_derive(Encode, attributes(pb))]
+pub fn derive_encode(
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    expand(&input, Derive::Encode)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Decode, attributes(pb))]
+pub fn derive_decode(
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    expand(&input, Derive::Decode)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Derive {
+    Encode,
+    Decode,
+}
+
+/// Contents of `#[pb(...)]`
+#[derive(Default)]
+struct FieldAttrs {
+    tag: Option<u32>,
+    encoding: Option<String>,
+    key_encoding: Option<String>,
+}
+
+fn expand(input: &DeriveInput, derive: Derive) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "Only structs can be derived, enums need to be generated",
+            ))
+        }
+    };
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    let body = match fields {
+        Fields::Named(named) => {
+            let mut specs = vec![];
+            for field in &named.named {
+                let attrs = field_attrs(field)?;
+                let number = attrs.tag.ok_or_else(|| {
+                    syn::Error::new(field.span(), "Missing #[pb(tag = N)]")
+                })?;
+                let ident = field.ident.clone().unwrap();
+                specs.push(field_spec(ident, number, &field.ty, &attrs)?);
+            }
+            let MessageCode {
+                size,
+                encode,
+                decode,
+            } = gen_message_code(name, &specs);
+            match derive {
+                Derive::Encode => quote! {
+                    impl #impl_generics Encode for #name #ty_generics #where_clause {
+                        fn size(&self) -> u32 {
+                            #size
+                        }
+
+                        fn encode<W: std::io::Write>(&self, mut w: W) -> Result<(), EncodingError> {
+                            #encode
+                        }
+                    }
+                },
+                Derive::Decode => quote! {
+                    impl #impl_generics Decode for #name #ty_generics #where_clause {
+                        fn decode<R: std::io::Read>(mut r: R) -> Result<Self, EncodingError> {
+                            #decode
+                        }
+                        fn decode_as_field(r: &mut dyn std::io::Read) -> Result<Self, EncodingError> {
+                            Self::decode_with_len(r)
+                        }
+                    }
+                },
+            }
+        }
+        Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => {
+            let field = &unnamed.unnamed[0];
+            let attrs = field_attrs(field)?;
+            if attrs.tag.is_some() {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "Single field of tuple struct has no tag",
+                ));
+            }
+            let value = value_spec(&field.ty, attrs.encoding.as_deref())?;
+            let rust_type = &value.rust_type;
+            let (encoding_type, wrapped, unwrap) = match &value.wrapper {
+                Some(wrapper) => (
+                    quote! { #wrapper<#rust_type> },
+                    quote! { #wrapper(self.0) },
+                    quote! { .0 },
+                ),
+                None => (quote! { #rust_type }, quote! { self.0 }, quote! {}),
+            };
+            match derive {
+                Derive::Encode => quote! {
+                    impl #impl_generics Encode for #name #ty_generics #where_clause {
+                        fn size(&self) -> u32 {
+                            (#wrapped).size()
+                        }
+
+                        fn encode<W: std::io::Write>(&self, w: W) -> Result<(), EncodingError> {
+                            (#wrapped).encode(w)
+                        }
+                    }
+                },
+                Derive::Decode => quote! {
+                    impl #impl_generics Decode for #name #ty_generics #where_clause {
+                        fn decode<R: std::io::Read>(r: R) -> Result<Self, EncodingError> {
+                            Ok(Self(<#encoding_type>::decode(r)? #unwrap))
+                        }
+                        fn decode_as_field(r: &mut dyn std::io::Read) -> Result<Self, EncodingError> {
+                            Ok(Self(<#encoding_type>::decode_as_field(r)? #unwrap))
+                        }
+                    }
+                },
+            }
+        }
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "Expected struct with named fields, or with single field",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        const _: () = {
+            use protobuf_but_worse::encoding::*;
+            #body
+        };
+    })
+}
+
+fn field_attrs(field: &syn::Field) -> syn::Result<FieldAttrs> {
+    let mut attrs = FieldAttrs::default();
+    for attr in field.attrs.iter().filter(|a| a.path.is_ident("pb")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => {
+                return Err(syn::Error::new(meta.span(), "Expected #[pb(...)]"))
+            }
+        };
+        for nested in list.nested {
+            let pair = match nested {
+                NestedMeta::Meta(Meta::NameValue(pair)) => pair,
+                nested => {
+                    return Err(syn::Error::new(
+                        nested.span(),
+                        "Expected `name = value`",
+                    ))
+                }
+            };
+            match (pair.path.get_ident(), &pair.lit) {
+                (Some(name), Lit::Int(int)) if name == "tag" => {
+                    attrs.tag = Some(int.base10_parse()?)
+                }
+                (Some(name), Lit::Str(s)) if name == "encoding" => {
+                    attrs.encoding = Some(s.value())
+                }
+                (Some(name), Lit::Str(s)) if name == "key_encoding" => {
+                    attrs.key_encoding = Some(s.value())
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        pair.span(),
+                        "Unknown pb attribute, expected `tag = N`, \
+                         `encoding = \"...\"` or `key_encoding = \"...\"`",
+                    ))
+                }
+            }
+        }
+    }
+    Ok(attrs)
+}
+
+fn field_spec(
+    ident: Ident,
+    number: u32,
+    field_type: &Type,
+    attrs: &FieldAttrs,
+) -> syn::Result<FieldSpec> {
+    let encoding = attrs.encoding.as_deref();
+    let (presence, key, value) = match generic_args(field_type) {
+        Some((name, args)) if name == "Option" && args.len() == 1 => {
+            (Presence::Optional, None, value_spec(args[0], encoding)?)
+        }
+        Some((name, args))
+            if name == "Vec" && args.len() == 1 && !is_bytes(&args) =>
+        {
+            (Presence::Repeated, None, value_spec(args[0], encoding)?)
+        }
+        Some((name, args))
+            if (name == "HashMap" || name == "BTreeMap") && args.len() == 2 =>
+        {
+            let key_encoding = attrs.key_encoding.as_deref();
+            let key = value_spec(args[0], key_encoding)?;
+            (Presence::Map, Some(key), value_spec(args[1], encoding)?)
+        }
+        _ => (Presence::Defaulted, None, value_spec(field_type, encoding)?),
+    };
+    Ok(FieldSpec {
+        ident,
+        number,
+        presence,
+        field_type: field_type.clone(),
+        key,
+        value,
+        packed: true,
+    })
+}
+
+// Types with encodings of their own, anything else is a message or enum
+const SCALARS: &[&str] = &[
+    "i32", "i64", "u32", "u64", "f32", "f64", "bool", "String", "bytes",
+    "Bytes",
+];
+
+fn value_spec(
+    rust_type: &Type,
+    encoding: Option<&str>,
+) -> syn::Result<ValueSpec> {
+    let name = match generic_args(rust_type) {
+        Some((name, args)) if name == "Vec" && is_bytes(&args) => {
+            "bytes".to_string()
+        }
+        Some((name, _)) => name.to_string(),
+        None => String::new(),
+    };
+    let (wrapper, wire_type) = match (name.as_str(), encoding) {
+        ("i32" | "i64" | "u32" | "u64", None | Some("varint")) => {
+            (Some("Varint"), 0)
+        }
+        ("i32" | "i64", Some("zigzag")) => (Some("SVarint"), 0),
+        ("i32" | "u32", Some("fixed")) => (Some("Fixed"), 5),
+        ("i64" | "u64", Some("fixed")) => (Some("Fixed"), 1),
+        ("f32", None | Some("fixed")) => (None, 5),
+        ("f64", None | Some("fixed")) => (None, 1),
+        ("bool", None | Some("varint")) => (None, 0),
+        ("String" | "bytes" | "Bytes", None) => (None, 2),
+        // Enum
+        (_, Some("varint")) if !SCALARS.contains(&name.as_str()) => (None, 0),
+        // Nested message
+        (_, None) => {
+            return Ok(ValueSpec {
+                wrapper: None,
+                rust_type: rust_type.clone(),
+                wire_type: 2,
+                is_message: true,
+            })
+        }
+        (_, Some(encoding)) => {
+            return Err(syn::Error::new(
+                rust_type.span(),
+                format!("Invalid encoding {:?} for this type", encoding),
+            ))
+        }
+    };
+    Ok(ValueSpec {
+        wrapper: wrapper.map(|x| Ident::new(x, Span::call_site())),
+        rust_type: rust_type.clone(),
+        wire_type,
+        is_message: false,
+    })
+}
+
+// Last segment of type's path, and its type arguments
+fn generic_args(typ: &Type) -> Option<(&Ident, Vec<&Type>)> {
+    let segment = match typ {
+        Type::Path(path) if path.qself.is_none() => {
+            path.path.segments.last()?
+        }
+        _ => return None,
+    };
+    let args = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(typ) => Some(typ),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    };
+    Some((&segment.ident, args))
+}
+
+// Type arguments of `Vec<u8>`
+fn is_bytes(args: &[&Type]) -> bool {
+    matches!(args, [Type::Path(path)] if path.path.is_ident("u8"))
+}
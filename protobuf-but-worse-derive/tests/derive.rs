@@ -0,0 +1,260 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use protobuf_but_worse::encoding::{Decode, EncodeDyn};
+use protobuf_but_worse::{Config, FieldStyle, MapType};
+use protobuf_but_worse_derive::{Decode, Encode};
+
+#[derive(Encode, Decode, PartialEq, Default, Debug)]
+struct Inner {
+    #[pb(tag = 1)]
+    id: u32,
+}
+
+#[derive(Encode, Decode, PartialEq, Default, Debug)]
+struct Outer {
+    #[pb(tag = 1)]
+    inner: Inner,
+    #[pb(tag = 2)]
+    maybe: Option<Inner>,
+    #[pb(tag = 3)]
+    list: Vec<Inner>,
+}
+
+fn roundtrip<T: EncodeDyn + Decode + PartialEq + std::fmt::Debug>(
+    value: &T,
+    bytes: &[u8],
+) {
+    assert_eq!(value.encode_to_vec().unwrap(), bytes);
+    assert_eq!(&T::decode(bytes).unwrap(), value);
+}
+
+#[test]
+fn nested_messages() {
+    roundtrip(&Outer::default(), &[]);
+    let outer = Outer {
+        inner: Inner { id: 1 },
+        maybe: Some(Inner::default()),
+        list: vec![Inner { id: 2 }, Inner::default()],
+    };
+    roundtrip(&outer, &[10, 2, 8, 1, 18, 0, 26, 2, 8, 2, 26, 0]);
+    // Default message is skipped, but `Some` is always encoded
+    let outer = Outer {
+        maybe: Some(Inner { id: 3 }),
+        ..Outer::default()
+    };
+    roundtrip(&outer, &[18, 2, 8, 3]);
+}
+
+#[derive(Encode, Decode, PartialEq, Default, Debug)]
+struct Lists {
+    #[pb(tag = 1)]
+    data: Vec<u8>,
+    #[pb(tag = 2)]
+    ids: Vec<u32>,
+    #[pb(tag = 3)]
+    chunks: Vec<Vec<u8>>,
+    #[pb(tag = 4)]
+    names: Vec<String>,
+}
+
+#[test]
+fn bytes_and_lists() {
+    roundtrip(&Lists::default(), &[]);
+    let lists = Lists {
+        data: vec![1, 2],
+        ids: vec![1, 300],
+        chunks: vec![vec![3], vec![]],
+        names: vec!["a".into()],
+    };
+    roundtrip(
+        &lists,
+        &[10, 2, 1, 2, 18, 3, 1, 172, 2, 26, 1, 3, 26, 0, 34, 1, b'a'],
+    );
+    // Unpacked repeated scalars are accepted as well
+    let lists = Lists::decode(&[16, 1, 16, 2][..]).unwrap();
+    assert_eq!(lists.ids, [1, 2]);
+}
+
+#[derive(Encode, Decode, PartialEq, Default, Debug)]
+struct Integers {
+    #[pb(tag = 1, encoding = "fixed")]
+    fixed: u64,
+    #[pb(tag = 2, encoding = "fixed")]
+    sfixed: i64,
+    #[pb(tag = 3, encoding = "zigzag")]
+    sint: i64,
+    #[pb(tag = 4)]
+    int: i64,
+    #[pb(tag = 5, encoding = "fixed")]
+    fixed32: Option<u32>,
+    #[pb(tag = 6, encoding = "fixed")]
+    fixed_list: Vec<u64>,
+}
+
+#[test]
+fn integer_encodings() {
+    let integers = Integers {
+        fixed: 0x0102030405060708,
+        sfixed: -2,
+        sint: -2,
+        int: -2,
+        fixed32: Some(1),
+        fixed_list: vec![1],
+    };
+    let mut bytes = vec![9, 8, 7, 6, 5, 4, 3, 2, 1];
+    bytes.extend([17, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+    bytes.extend([24, 3]);
+    bytes.extend([32, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]);
+    bytes.extend([0xff, 1]);
+    bytes.extend([45, 1, 0, 0, 0]);
+    bytes.extend([50, 8, 1, 0, 0, 0, 0, 0, 0, 0]);
+    roundtrip(&integers, &bytes);
+}
+
+#[derive(Encode, Decode, PartialEq, Default, Debug)]
+struct Maps {
+    #[pb(tag = 1, key_encoding = "zigzag")]
+    by_delta: BTreeMap<i32, Inner>,
+    #[pb(tag = 2, key_encoding = "fixed", encoding = "fixed")]
+    fixed: HashMap<u64, i32>,
+    #[pb(tag = 3)]
+    names: BTreeMap<String, Vec<u8>>,
+}
+
+#[test]
+fn maps() {
+    let mut maps = Maps::default();
+    maps.by_delta.insert(-1, Inner { id: 1 });
+    maps.by_delta.insert(1, Inner::default());
+    maps.fixed.insert(2, -1);
+    maps.names.insert("a".into(), vec![1]);
+    let mut bytes = vec![10, 6, 8, 1, 18, 2, 8, 1];
+    bytes.extend([10, 4, 8, 2, 18, 0]);
+    bytes.extend([18, 14, 9, 2, 0, 0, 0, 0, 0, 0, 0]);
+    bytes.extend([21, 0xff, 0xff, 0xff, 0xff]);
+    bytes.extend([26, 6, 10, 1, b'a', 18, 1, 1]);
+    roundtrip(&maps, &bytes);
+}
+
+// Error messages of invalid derives
+#[test]
+fn errors() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
+
+// Derived messages encode the same as code generated from .proto files
+#[test]
+fn generated() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("generated");
+    std::fs::create_dir_all(&dir).unwrap();
+    let proto = dir.join("test.proto");
+    std::fs::write(
+        &proto,
+        r#"
+        syntax = "proto3";
+        package test;
+        enum Kind { A = 0; B = 1; }
+        message Inner { string name = 1; bytes data = 2; }
+        message Outer {
+            sint32 delta = 1;
+            fixed64 stamp = 2;
+            sfixed64 offset = 3;
+            Kind kind = 4;
+            Inner inner = 5;
+            repeated Inner children = 6;
+            repeated uint64 ids = 7;
+            map<sint32, Inner> by_delta = 8;
+            map<fixed32, Kind> kinds = 9;
+            double ratio = 10;
+            bool flag = 11;
+        }
+        "#,
+    )
+    .unwrap();
+    Config::new()
+        .out_dir(&dir)
+        .field_style(FieldStyle::Default)
+        .map_type(MapType::BTreeMap)
+        .compile(&[&proto])
+        .unwrap();
+
+    let main = format!(
+        r#"
+        mod generated {{
+            include!({:?});
+        }}
+
+        use std::collections::BTreeMap;
+
+        use generated::Kind;
+        use protobuf_but_worse::encoding::{{Decode, EncodeDyn}};
+        use protobuf_but_worse_derive::{{Decode, Encode}};
+
+        #[derive(Encode, Decode, PartialEq, Default, Debug)]
+        struct Inner {{
+            #[pb(tag = 1)]
+            name: String,
+            #[pb(tag = 2)]
+            data: Vec<u8>,
+        }}
+
+        #[derive(Encode, Decode, PartialEq, Default, Debug)]
+        struct Outer {{
+            #[pb(tag = 1, encoding = "zigzag")]
+            delta: i32,
+            #[pb(tag = 2, encoding = "fixed")]
+            stamp: u64,
+            #[pb(tag = 3, encoding = "fixed")]
+            offset: i64,
+            #[pb(tag = 4, encoding = "varint")]
+            kind: Kind,
+            #[pb(tag = 5)]
+            inner: Option<Inner>,
+            #[pb(tag = 6)]
+            children: Vec<Inner>,
+            #[pb(tag = 7)]
+            ids: Vec<u64>,
+            #[pb(tag = 8, key_encoding = "zigzag")]
+            by_delta: BTreeMap<i32, Inner>,
+            #[pb(tag = 9, key_encoding = "fixed", encoding = "varint")]
+            kinds: BTreeMap<u32, Kind>,
+            #[pb(tag = 10)]
+            ratio: f64,
+            #[pb(tag = 11)]
+            flag: bool,
+        }}
+
+        fn inner(name: &str) -> Inner {{
+            Inner {{ name: name.into(), data: name.as_bytes().to_vec() }}
+        }}
+
+        fn main() {{
+            let outer = Outer {{
+                delta: -5,
+                stamp: u64::MAX,
+                offset: i64::MIN,
+                kind: Kind::B,
+                inner: Some(inner("a")),
+                children: vec![inner(""), inner("b")],
+                ids: vec![0, 1 << 40],
+                by_delta: [(-1, inner("c")), (2, inner(""))].into(),
+                kinds: [(0, Kind::A), (7, Kind::B)].into(),
+                ratio: -0.5,
+                flag: true,
+            }};
+            for outer in [Outer::default(), outer] {{
+                let bytes = outer.encode_to_vec().unwrap();
+                let generated =
+                    generated::Outer::decode(bytes.as_slice()).unwrap();
+                assert_eq!(generated.encode_to_vec().unwrap(), bytes);
+                assert_eq!(Outer::decode(bytes.as_slice()).unwrap(), outer);
+            }}
+        }}
+        "#,
+        dir.join("test.rs")
+    );
+    let main_file = dir.join("main.rs");
+    std::fs::write(&main_file, main).unwrap();
+    trybuild::TestCases::new().pass(&main_file);
+}
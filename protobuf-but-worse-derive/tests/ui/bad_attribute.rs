@@ -0,0 +1,12 @@
+use protobuf_but_worse_derive::Encode;
+
+#[derive(Encode)]
+struct Packed {
+    #[pb(tag = 1, packed = false)]
+    ids: Vec<u32>,
+}
+
+#[derive(Encode)]
+struct Pair(#[pb(encoding = "fixed")] u32, u32);
+
+fn main() {}
@@ -0,0 +1,21 @@
+use protobuf_but_worse_derive::Encode;
+
+#[derive(Encode)]
+struct Unsigned {
+    #[pb(tag = 1, encoding = "zigzag")]
+    x: u32,
+}
+
+#[derive(Encode)]
+struct Text {
+    #[pb(tag = 1, encoding = "fixed")]
+    name: Option<String>,
+}
+
+#[derive(Encode)]
+struct Key {
+    #[pb(tag = 1, key_encoding = "varint")]
+    names: std::collections::HashMap<f32, String>,
+}
+
+fn main() {}
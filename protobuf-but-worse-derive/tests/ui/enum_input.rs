@@ -0,0 +1,9 @@
+use protobuf_but_worse_derive::{Decode, Encode};
+
+#[derive(Encode, Decode)]
+enum Kind {
+    A,
+    B,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use protobuf_but_worse_derive::Encode;
+
+#[derive(Encode)]
+struct Point {
+    #[pb(tag = 1)]
+    x: i32,
+    y: i32,
+}
+
+fn main() {}
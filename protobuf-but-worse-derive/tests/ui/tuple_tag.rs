@@ -0,0 +1,6 @@
+use protobuf_but_worse_derive::Decode;
+
+#[derive(Decode)]
+struct Id(#[pb(tag = 1)] u32);
+
+fn main() {}
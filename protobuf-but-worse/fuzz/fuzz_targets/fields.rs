@@ -0,0 +1,13 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use protobuf_but_worse::encoding::*;
+
+// Walks input as a message of unknown fields
+fuzz_target!(|data: &[u8]| {
+    let mut r = data;
+    while let Ok(Some((_, wire_type))) = decode_key(&mut r) {
+        if skip_field(&mut r, wire_type).is_err() {
+            break;
+        }
+    }
+});
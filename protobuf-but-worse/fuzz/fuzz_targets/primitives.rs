@@ -0,0 +1,90 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use protobuf_but_worse::encoding::*;
+
+// Decodes value, and if it's valid, checks that it encodes
+// to `size()` bytes and decodes back to the same value
+macro_rules! roundtrip {
+    ($t:ty, $data:expr) => {
+        if let Ok(x) = <$t>::decode($data) {
+            let mut encoded = vec![];
+            x.encode(&mut encoded).unwrap();
+            assert_eq!(x.size() as usize, encoded.len());
+            let y = <$t>::decode(encoded.as_slice()).unwrap();
+            assert_eq!(x.0.to_le_bytes(), y.0.to_le_bytes());
+        }
+    };
+}
+
+fuzz_target!(|data: &[u8]| {
+    let (kind, data) = match data.split_first() {
+        Some(x) => x,
+        None => return,
+    };
+    match kind % 18 {
+        0 => roundtrip!(Varint<u32>, data),
+        1 => roundtrip!(Varint<u64>, data),
+        2 => roundtrip!(Varint<i32>, data),
+        3 => roundtrip!(Varint<i64>, data),
+        4 => roundtrip!(SVarint<i32>, data),
+        5 => roundtrip!(SVarint<i64>, data),
+        6 => roundtrip!(Fixed<u32>, data),
+        7 => roundtrip!(Fixed<u64>, data),
+        8 => roundtrip!(Fixed<i32>, data),
+        9 => roundtrip!(Fixed<i64>, data),
+        10 => {
+            if let Ok(x) = f64::decode(data) {
+                assert_eq!(&x.to_le_bytes()[..], &data[..8]);
+                let mut encoded = vec![];
+                x.encode(&mut encoded).unwrap();
+                assert_eq!(x.size() as usize, encoded.len());
+                assert_eq!(encoded, &data[..8]);
+            }
+        }
+        11 => {
+            if let Ok(x) = bool::decode(data) {
+                assert_eq!(x as u8, data[0]);
+            }
+        }
+        12 => {
+            if let Ok(x) = String::decode(data) {
+                let mut encoded = vec![];
+                x.encode(&mut encoded).unwrap();
+                assert_eq!(x.size() as usize, encoded.len());
+                assert_eq!(String::decode(encoded.as_slice()).unwrap(), x);
+            }
+        }
+        13 => {
+            let _ = <Vec<u8>>::decode_with_len(data);
+        }
+        14 => {
+            let _ = decode_packed::<_, Varint<u64>>(data);
+        }
+        15 => {
+            if let Ok(x) = f32::decode(data) {
+                assert_eq!(&x.to_le_bytes()[..], &data[..4]);
+                let mut encoded = vec![];
+                x.encode(&mut encoded).unwrap();
+                assert_eq!(x.size() as usize, encoded.len());
+                assert_eq!(encoded, &data[..4]);
+            }
+        }
+        16 => {
+            // Encoded like int64, so negative values take 10 bytes
+            if let Ok(x) = Fixed::<i32>::decode(data) {
+                let x = x.0;
+                let mut encoded = vec![];
+                Varint(x).encode(&mut encoded).unwrap();
+                assert_eq!(Varint(x).size() as usize, encoded.len());
+                assert_eq!(encoded.len() == 10, x < 0);
+                let y = <Varint<i64>>::decode(encoded.as_slice()).unwrap();
+                assert_eq!(y.0, x as i64);
+                let y = <Varint<i32>>::decode(encoded.as_slice()).unwrap();
+                assert_eq!(y.0, x);
+            }
+        }
+        _ => {
+            let _ = <Vec<Fixed<u32>>>::decode(data);
+        }
+    }
+});
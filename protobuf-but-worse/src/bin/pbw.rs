@@ -0,0 +1,191 @@
+use std::{
+    collections::HashSet,
+    error::Error,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use protobuf_but_worse::{
+    descriptor::Pool,
+    dynamic::{decode_raw, RawDisplay},
+    encoding::{Encode, FrameDecoder},
+    Config, Layout,
+};
+
+const USAGE: &str = "\
+Usage:
+  pbw generate [-o <dir or file.rs>] <file.proto>...
+  pbw decode [-d] <file.proto> <message type> [input]
+  pbw decode --raw [-d] [input]
+  pbw encode [-d] <file.proto> <message type> [input]
+
+Binary messages are read from and written to stdin/stdout, text format
+is used for decoded messages. Message types are fully qualified.
+Imports are looked up relative to directory of <file.proto>, while
+`generate` needs all files listed.
+  -o     Output directory, or single file collecting all packages
+  -d     Messages are length-delimited, like in kRPC traffic
+  --raw  Decode without schema, like `protoc --decode_raw`";
+
+/// Command line, split into flags and positional arguments
+#[derive(Default)]
+struct Args {
+    out: Option<PathBuf>,
+    delimited: bool,
+    raw: bool,
+    positional: Vec<String>,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut res = Self::default();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-o" => {
+                    let out = args.next().ok_or("Missing path after -o")?;
+                    res.out = Some(out.into());
+                }
+                "-d" | "--delimited" => res.delimited = true,
+                "--raw" => res.raw = true,
+                "-" => res.positional.push(arg),
+                _ if arg.starts_with('-') => {
+                    return Err(format!("Unknown option {}", arg))
+                }
+                _ => res.positional.push(arg),
+            }
+        }
+        Ok(res)
+    }
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let command = args.next();
+    let res = Args::parse(args).map_err(Into::into).and_then(|args| {
+        match command.as_deref() {
+            Some("generate") => generate(&args),
+            Some("decode") => decode(&args),
+            Some("encode") => encode(&args),
+            _ => Err(USAGE.into()),
+        }
+    });
+    if let Err(e) = res {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+fn generate(args: &Args) -> Result<(), Box<dyn Error>> {
+    if args.positional.is_empty() {
+        return Err(USAGE.into());
+    }
+    let out = args.out.clone().unwrap_or_else(|| PathBuf::from("."));
+    let mut config = Config::new();
+    let dir = if out.extension() == Some("rs".as_ref()) {
+        let file_name = out.file_name().unwrap_or_default();
+        config.layout(Layout::SingleFile(file_name.to_string_lossy().into()));
+        out.parent().unwrap_or_else(|| Path::new("."))
+    } else {
+        &out
+    };
+    std::fs::create_dir_all(dir)?;
+    config.out_dir(dir).compile(&args.positional)?;
+    Ok(())
+}
+
+fn decode(args: &Args) -> Result<(), Box<dyn Error>> {
+    let (pool, name, input) = match (args.raw, args.positional.as_slice()) {
+        (true, input) if input.len() <= 1 => (None, "", input.first()),
+        (false, [proto, name, input @ ..]) if input.len() <= 1 => {
+            (Some(load_pool(proto)?), name.as_str(), input.first())
+        }
+        _ => return Err(USAGE.into()),
+    };
+    let bytes = read_input(input)?;
+    let messages = if args.delimited {
+        let mut decoder = FrameDecoder::new();
+        decoder.push(&bytes);
+        let mut frames = vec![];
+        while let Some(frame) = decoder.next_frame()? {
+            frames.push(frame);
+        }
+        if decoder.buffered() != 0 {
+            return Err("Input ends in the middle of a message".into());
+        }
+        frames
+    } else {
+        vec![bytes]
+    };
+    for (i, bytes) in messages.iter().enumerate() {
+        if args.delimited {
+            println!("# Message {}", i);
+        }
+        match &pool {
+            Some(pool) => print!("{}", pool.decode(name, bytes)?),
+            None => print!("{}", RawDisplay(&decode_raw(bytes)?)),
+        }
+    }
+    Ok(())
+}
+
+fn encode(args: &Args) -> Result<(), Box<dyn Error>> {
+    let (proto, name, input) = match args.positional.as_slice() {
+        [proto, name, input @ ..] if input.len() <= 1 && !args.raw => {
+            (proto, name, input.first())
+        }
+        _ => return Err(USAGE.into()),
+    };
+    let pool = load_pool(proto)?;
+    let text = String::from_utf8(read_input(input)?)?;
+    let bytes = pool.encode(&pool.parse_text(name, &text)?)?;
+    let mut stdout = std::io::stdout();
+    if args.delimited {
+        bytes.encode(&mut stdout)?;
+    } else {
+        stdout.write_all(&bytes)?;
+    }
+    stdout.flush()?;
+    Ok(())
+}
+
+// Loads file along with files it imports, which are looked up relative
+// to its directory. Well-known types are always there.
+fn load_pool(proto: &str) -> Result<Pool, Box<dyn Error>> {
+    let proto = Path::new(proto);
+    let root = proto.parent().unwrap_or_else(|| Path::new(""));
+    let mut pool = Pool::new();
+    load_file(&mut pool, &mut HashSet::new(), root, proto)?;
+    Ok(pool)
+}
+
+// Adds imports before the file, as types are resolved when adding
+fn load_file(
+    pool: &mut Pool,
+    loaded: &mut HashSet<PathBuf>,
+    root: &Path,
+    proto: &Path,
+) -> Result<(), Box<dyn Error>> {
+    if !loaded.insert(proto.to_path_buf()) {
+        return Ok(());
+    }
+    let file = protobuf_but_worse::parse(proto)?;
+    for import in &file.descriptor.import_paths {
+        if !import.starts_with("google/protobuf/") {
+            load_file(pool, loaded, root, &root.join(import))?;
+        }
+    }
+    pool.add_file(&file)?;
+    Ok(())
+}
+
+// Reads file, or stdin when missing or `-`
+fn read_input(input: Option<&String>) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut bytes = vec![];
+    match input.map(String::as_str) {
+        None | Some("-") => {
+            std::io::stdin().read_to_end(&mut bytes)?;
+        }
+        Some(path) => bytes = std::fs::read(path)?,
+    }
+    Ok(bytes)
+}
@@ -1,319 +1,536 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+};
 
 use heck::{CamelCase, SnakeCase};
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::quote;
 
-use protobuf_parser::{
-    Enumeration, FieldType, FileDescriptor, Message, Rule, Syntax,
+use protobuf_parser::{Enumeration, Field, FieldType, Message, Rule, Syntax};
+use syn::{Expr, Ident, LitByteStr, LitInt, LitStr, Type};
+
+pub mod fields;
+
+use fields::{gen_message_code, FieldSpec, MessageCode, Presence, ValueSpec};
+
+use crate::{
+    config::{BytesType, Config, FieldStyle, Layout, MapType},
+    descriptor::{package_prefixes, resolve},
+    source::{ServiceInfo, SourceInfo},
+    well_known, ProtoFile,
 };
-use syn::{Expr, Ident, LitInt, Path, Type};
 
-#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 enum TypeInfo {
     Message,
     Enum,
+    /// Well-known wrapper message, represented as `Option` of its value
+    Wrapper,
 }
 
-// Scoped map of type information
-// Codegen needs to know in certain place to know if a type is Message or Enum,
-// and in other place if type is in local or outer scope
-#[derive(Default, Debug)]
-struct TypeInfoMap<'a> {
-    prev: Option<&'a TypeInfoMap<'a>>,
-    current: HashMap<String, TypeInfo>,
+#[derive(Debug)]
+struct Symbol {
+    info: TypeInfo,
+    // Package of the file declaring the type, without leading dot
+    package: String,
+    // Rust module the type is generated in, from root of generated code
+    module: Vec<String>,
+    ident: String,
+    // Full Rust type of types that aren't generated, like well-known ones
+    external: Option<String>,
 }
 
-impl<'a> TypeInfoMap<'a> {
-    fn new() -> Self {
-        Self::default()
-    }
+/// Messages and enums of files generated together,
+/// by fully qualified name (`.package.Message.Nested`),
+/// along with well-known types
+#[derive(Debug)]
+pub(crate) struct SymbolTable {
+    symbols: HashMap<String, Symbol>,
+    packages: HashSet<String>,
+}
 
-    fn wrap(prev: &'a TypeInfoMap) -> Self {
+impl Default for SymbolTable {
+    fn default() -> Self {
+        let package = ".google.protobuf";
+        let symbols = well_known::TYPES
+            .iter()
+            .map(|&(name, rust_type, is_wrapper)| {
+                let symbol = Symbol {
+                    info: if is_wrapper {
+                        TypeInfo::Wrapper
+                    } else {
+                        TypeInfo::Message
+                    },
+                    package: package[1..].to_string(),
+                    module: vec![],
+                    ident: name.to_string(),
+                    external: Some(rust_type.to_string()),
+                };
+                (format!("{}.{}", package, name), symbol)
+            })
+            .collect();
         Self {
-            prev: Some(prev),
-            ..Self::default()
+            symbols,
+            packages: package_prefixes(package).collect(),
         }
     }
+}
 
-    fn insert(&mut self, key: &str, info: TypeInfo) {
-        self.current.insert(key.to_owned(), info);
+impl SymbolTable {
+    pub(crate) fn add_file(&mut self, proto: &ProtoFile) -> syn::Result<()> {
+        let package = &proto.descriptor.package;
+        let (scope, module) = if package.is_empty() {
+            (String::new(), vec![])
+        } else {
+            let module = package.split('.').map(module_name).collect();
+            (format!(".{}", package), module)
+        };
+        self.packages.extend(package_prefixes(&scope));
+        let descriptor = &proto.descriptor;
+        self.add(
+            package,
+            &scope,
+            &module,
+            &descriptor.messages,
+            &descriptor.enums,
+        )
     }
 
-    fn get_current(&self, key: &str) -> Option<&TypeInfo> {
-        self.current.get(key)
+    fn add(
+        &mut self,
+        package: &str,
+        scope: &str,
+        module: &[String],
+        messages: &[Message],
+        enums: &[Enumeration],
+    ) -> syn::Result<()> {
+        let names = messages
+            .iter()
+            .map(|m| (&m.name, TypeInfo::Message))
+            .chain(enums.iter().map(|e| (&e.name, TypeInfo::Enum)));
+        for (name, info) in names {
+            let full_name = format!("{}.{}", scope, name);
+            let symbol = Symbol {
+                info,
+                package: package.to_string(),
+                module: module.to_vec(),
+                ident: name.to_camel_case(),
+                external: None,
+            };
+            // Well-known types can be generated from their own .proto
+            let previous = self.symbols.insert(full_name.clone(), symbol);
+            if previous.is_some_and(|x| x.external.is_none()) {
+                let msg =
+                    format!("Type {} is defined more than once", full_name);
+                return Err(syn::Error::new(Span::call_site(), msg));
+            }
+        }
+        for message in messages {
+            let mut module = module.to_vec();
+            module.push(module_name(&message.name));
+            let scope = format!("{}.{}", scope, message.name);
+            self.add(
+                package,
+                &scope,
+                &module,
+                &message.messages,
+                &message.enums,
+            )?;
+        }
+        Ok(())
     }
+}
 
-    fn get_prev(&self, key: &str) -> Option<&TypeInfo> {
-        self.prev.map(|prev| prev.get(key)).flatten()
+// Where code is generated: protobuf scope for resolving type names
+// and Rust module the code ends up in
+#[derive(Clone, Debug)]
+struct Scope {
+    proto: String,
+    module: Vec<String>,
+}
+
+impl Scope {
+    fn package(package: &str) -> Self {
+        if package.is_empty() {
+            Self {
+                proto: String::new(),
+                module: vec![],
+            }
+        } else {
+            Self {
+                proto: format!(".{}", package),
+                module: package.split('.').map(module_name).collect(),
+            }
+        }
     }
 
-    fn get(&self, key: &str) -> Option<&TypeInfo> {
-        self.get_current(key).or_else(|| self.get_prev(key))
+    // Scope of fields of `message`, which go next to it
+    fn message_fields(&self, message: &str) -> Self {
+        Self {
+            proto: format!("{}.{}", self.proto, message),
+            module: self.module.clone(),
+        }
     }
 
-    fn populate(&mut self, messages: &[Message], enums: &[Enumeration]) {
-        for message in messages {
-            self.insert(&message.name, TypeInfo::Message);
+    // Scope of types nested in `message`, which go into its module
+    fn nested(&self, message: &str) -> Self {
+        let mut module = self.module.clone();
+        module.push(module_name(message));
+        Self {
+            proto: format!("{}.{}", self.proto, message),
+            module,
+        }
+    }
+}
+
+// Shared state for generating one .proto file
+struct Context<'a> {
+    config: &'a Config,
+    package: &'a str,
+    syntax: Syntax,
+    info: &'a SourceInfo,
+    symbols: &'a SymbolTable,
+}
+
+impl Context<'_> {
+    // Resolves type `name` used in `scope`, following protobuf rules
+    fn resolve(&self, scope: &Scope, name: &str) -> syn::Result<&Symbol> {
+        let symbols = self.symbols;
+        let is_package = |x: &str| symbols.packages.contains(x);
+        let lookup = |x: &str| symbols.symbols.get(x);
+        resolve(&scope.proto, name, is_package, lookup).ok_or_else(|| {
+            let msg = format!("Unknown type {} in {}", name, scope.proto);
+            syn::Error::new(Span::call_site(), msg)
+        })
+    }
+
+    // Rust path to type `name`, relative to module of `scope`
+    fn rust_path(&self, scope: &Scope, name: &str) -> syn::Result<String> {
+        let symbol = self.resolve(scope, name)?;
+        if let Some(rust_type) = &symbol.external {
+            return Ok(rust_type.clone());
         }
-        for e in enums {
-            self.insert(&e.name, TypeInfo::Enum);
+        // Files are generated flat, without modules for packages
+        if self.config.layout == Layout::PerFile
+            && symbol.package != self.package
+        {
+            let msg = format!(
+                "Type {} from package {:?} can't be used in package {:?} \
+                 with Layout::PerFile, use Layout::SingleFile \
+                 or Layout::ModuleTree",
+                name, symbol.package, self.package
+            );
+            return Err(syn::Error::new(Span::call_site(), msg));
         }
+        let common = scope
+            .module
+            .iter()
+            .zip(&symbol.module)
+            .take_while(|(a, b)| a == b)
+            .count();
+        let mut path = vec!["super"; scope.module.len() - common];
+        path.extend(symbol.module[common..].iter().map(String::as_str));
+        path.push(&symbol.ident);
+        Ok(path.join("::"))
     }
 }
 
-pub fn gen_proto(proto: &FileDescriptor) -> syn::Result<String> {
-    assert!(matches!(proto.syntax, Syntax::Proto3));
+impl Presence {
+    // `full_name` is fully qualified name of the field
+    fn of(
+        ctx: &Context,
+        field: &Field,
+        full_name: &str,
+        is_message: bool,
+    ) -> Self {
+        match (field.rule, &field.typ) {
+            (_, FieldType::Map(_)) => Presence::Map,
+            (Rule::Required, _) => Presence::Required,
+            (Rule::Repeated, _) => Presence::Repeated,
+            (Rule::Optional, _)
+                if ctx.config.field_style == FieldStyle::Default
+                    && matches!(ctx.syntax, Syntax::Proto3)
+                    && !is_message
+                    && !ctx.info.explicit_optional.contains(full_name) =>
+            {
+                Presence::Defaulted
+            }
+            (Rule::Optional, _) => Presence::Optional,
+        }
+    }
+}
 
-    let mut type_info = TypeInfoMap::new();
-    type_info.populate(&proto.messages, &proto.enums);
+/// Generates code for whole .proto file, including imports
+pub fn gen_proto(proto: &ProtoFile, config: &Config) -> syn::Result<String> {
+    let mut symbols = SymbolTable::default();
+    symbols.add_file(proto)?;
+    Ok(gen_imports(config) + &gen_items(proto, &symbols, config)?)
+}
 
-    let import = quote! {
-        use serde::{Serialize, Deserialize};
+pub(crate) fn gen_imports(config: &Config) -> String {
+    let serde = if config.serde {
+        quote! { use serde::{Serialize, Deserialize}; }
+    } else {
+        quote! {}
+    };
+    let imports = quote! {
+        #serde
         use protobuf_but_worse::encoding::*;
     };
+    imports.to_string()
+}
+
+// Generates messages and enums, without imports.
+// `symbols` has types of this file and all files it can refer to.
+pub(crate) fn gen_items(
+    proto: &ProtoFile,
+    symbols: &SymbolTable,
+    config: &Config,
+) -> syn::Result<String> {
+    let info = &proto.info;
+    let proto = &proto.descriptor;
+    let ctx = Context {
+        config,
+        package: &proto.package,
+        syntax: proto.syntax,
+        info,
+        symbols,
+    };
+    let scope = Scope::package(&proto.package);
 
     let messages: TokenStream = proto
         .messages
         .iter()
-        .map(|message| gen_message(&type_info, message))
+        .map(|message| gen_message(&ctx, &scope, message))
         .collect::<syn::Result<_>>()?;
 
     let enums: TokenStream = proto
         .enums
         .iter()
-        .map(|e| gen_enum(e))
+        .map(|e| gen_enum(&ctx, &scope.proto, e))
+        .collect::<syn::Result<_>>()?;
+
+    let services: TokenStream = info
+        .services
+        .iter()
+        .map(|service| gen_service(&ctx, &scope, service))
         .collect::<syn::Result<_>>()?;
 
     let file = quote! {
-        #import
         #messages
         #enums
+        #services
     };
     Ok(file.to_string())
 }
 
+pub(crate) fn module_name(name: &str) -> String {
+    escape_rust_keyword(&name.to_snake_case()).to_string()
+}
+
+fn gen_attributes(attributes: &[&str]) -> syn::Result<TokenStream> {
+    attributes
+        .iter()
+        .map(|attribute| syn::parse_str::<TokenStream>(attribute))
+        .collect()
+}
+
+// Derives and attributes of generated message or enum
+fn gen_type_attributes(
+    ctx: &Context,
+    full_name: &str,
+) -> syn::Result<TokenStream> {
+    let serde = if ctx.config.serde {
+        quote! { Serialize, Deserialize }
+    } else {
+        quote! {}
+    };
+    let attributes =
+        gen_attributes(&ctx.config.type_attributes_for(full_name))?;
+    Ok(quote! {
+        #[derive(Clone, PartialEq, Debug, #serde)]
+        #attributes
+    })
+}
+
+// Doc comment from .proto source of `full_name`, or `fallback`
+fn gen_doc(ctx: &Context, full_name: &str, fallback: String) -> TokenStream {
+    let doc = ctx.info.docs.get(full_name).cloned().unwrap_or(fallback);
+    let lines = doc
+        .lines()
+        .map(|line| format!(" {}", line).trim_end().to_string());
+    quote! { #(#[doc = #lines])* }
+}
+
+// Field type as written in .proto
+fn proto_type(typ: &FieldType) -> String {
+    match typ {
+        FieldType::Double => "double".to_string(),
+        FieldType::Float => "float".to_string(),
+        FieldType::Int32 => "int32".to_string(),
+        FieldType::Int64 => "int64".to_string(),
+        FieldType::Uint32 => "uint32".to_string(),
+        FieldType::Uint64 => "uint64".to_string(),
+        FieldType::Sint32 => "sint32".to_string(),
+        FieldType::Sint64 => "sint64".to_string(),
+        FieldType::Fixed32 => "fixed32".to_string(),
+        FieldType::Fixed64 => "fixed64".to_string(),
+        FieldType::Sfixed32 => "sfixed32".to_string(),
+        FieldType::Sfixed64 => "sfixed64".to_string(),
+        FieldType::Bool => "bool".to_string(),
+        FieldType::String => "string".to_string(),
+        FieldType::Bytes => "bytes".to_string(),
+        FieldType::MessageOrEnum(name) => name.clone(),
+        FieldType::Group(_) => "group".to_string(),
+        FieldType::Map(map) => {
+            format!("map<{}, {}>", proto_type(&map.0), proto_type(&map.1))
+        }
+    }
+}
+
+// Encoding of a single value of field, map key or map value
+fn value_spec(
+    ctx: &Context,
+    scope: &Scope,
+    typ: &FieldType,
+) -> syn::Result<ValueSpec> {
+    let wire_type = to_wire_type(ctx, scope, typ)?;
+    Ok(ValueSpec {
+        wrapper: wrapper(typ).map(|x| Ident::new(x, Span::call_site())),
+        rust_type: syn::parse_str(&to_rust_type(ctx, scope, typ)?)?,
+        wire_type,
+        is_message: matches!(typ, FieldType::MessageOrEnum(_))
+            && wire_type == 2,
+    })
+}
+
 fn gen_message(
-    type_info: &TypeInfoMap,
+    ctx: &Context,
+    scope: &Scope,
     message: &Message,
 ) -> syn::Result<TokenStream> {
-    let mut type_info = TypeInfoMap::wrap(type_info);
-    type_info.populate(&message.messages, &message.enums);
-
+    let fields_scope = &scope.message_fields(&message.name);
+    let full_name = fields_scope.proto.clone();
     let struct_name: Ident = syn::parse_str(&message.name.to_camel_case())?;
-    let module_name_str = message.name.to_snake_case();
-    let module_name_str = escape_rust_keyword(&module_name_str);
-    let module_name: Ident = syn::parse_str(module_name_str)?;
+    let module_name: Ident = syn::parse_str(&module_name(&message.name))?;
 
-    // TODO: deduplicate all fields loops
-    let fields: TokenStream = message
-        .fields
-        .iter()
-        .map(|field| {
-            let field_name: Ident =
-                syn::parse_str(escape_rust_keyword(&field.name))?;
-            let as_rust_type =
-                to_rust_type(&field.typ, &module_name_str, &type_info);
-            let field_type = match field.rule {
-                Rule::Required => as_rust_type,
-                Rule::Repeated => format!("Vec<{}>", as_rust_type),
-                Rule::Optional => format!("Option<{}>", as_rust_type),
-            };
-            let field_type: Type = syn::parse_str(&field_type)?;
-            Ok(quote! { pub #field_name: #field_type, })
-        })
-        .collect::<syn::Result<_>>()?;
-    let main_struct = quote! {
-        #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
-        pub struct #struct_name { #fields }
-    };
-
-    let mut sizes = quote! {};
+    let mut fields = quote! {};
+    let mut specs = vec![];
+    let mut getters = quote! {};
     for field in &message.fields {
         let field_name: Ident =
             syn::parse_str(escape_rust_keyword(&field.name))?;
-        match field.rule {
-            Rule::Optional => {
-                let encoded_field: Expr =
-                    syn::parse_str(&encoding_field(&field.typ, "*x"))?;
-                sizes = quote! {
-                    #sizes
-                    size += self.#field_name
-                        .as_ref()
-                        .map(|x| 1 + (#encoded_field).size())
-                        .unwrap_or(0);
-                };
-            }
-            Rule::Repeated => {
-                sizes = quote! {
-                    #sizes
-                    size += 1 + self.#field_name.size();
-                }
-            }
-            Rule::Required => todo!(),
-        }
-    }
-    let mut encoding_code = quote! {};
-    for field in &message.fields {
-        let number = field.number as u8;
-        let wire_type = to_wire_type(&field.typ, &type_info);
-        let field_name_str = escape_rust_keyword(&field.name);
-        let field_name: Ident = syn::parse_str(&field_name_str)?;
-        match field.rule {
-            Rule::Optional => {
-                let field_name: Ident =
-                    syn::parse_str(escape_rust_keyword(&field.name))?;
-                let encoded_field: Expr =
-                    syn::parse_str(&encoding_field(&field.typ, "*x"))?;
-                encoding_code = quote! {
-                    #encoding_code
-                    self.#field_name
-                        .as_ref()
-                        .map(|x| encode_field(&mut w, #number, #wire_type, &#encoded_field))
-                        .transpose()?;
-                };
-            }
-            Rule::Repeated => {
-                // let encoded_field: Expr = syn::parse_str(&encoding_field(
-                //     &field.typ,
-                //     &("self.".to_string() + field_name_str),
-                // ))?;
-                encoding_code = quote! {
-                    #encoding_code
-                    for i in &self.#field_name {
-                        let buf = i.encode_to_vec()?;
-                        encode_field(&mut w, #number, #wire_type, &buf)?;
-                    }
-                };
+        let wire_type = to_wire_type(ctx, fields_scope, &field.typ)?;
+        let is_message =
+            matches!(field.typ, FieldType::MessageOrEnum(_)) && wire_type == 2;
+        let field_full_name = format!("{}.{}", full_name, field.name);
+        let is_wrapper = match &field.typ {
+            FieldType::MessageOrEnum(name) => {
+                ctx.resolve(fields_scope, name)?.info == TypeInfo::Wrapper
             }
-            _ => {
-                let encoded_field: Expr = syn::parse_str(&encoding_field(
-                    &field.typ,
-                    &("self.".to_string() + field_name_str),
-                ))?;
-                encoding_code = quote! {
-                    #encoding_code
-                    encode_field(&mut w, #number, #wire_type, &#encoded_field)?;
-                };
+            _ => false,
+        };
+        let presence =
+            match Presence::of(ctx, field, &field_full_name, is_message) {
+                // Wrappers are `Option`s already
+                Presence::Optional if is_wrapper => Presence::Defaulted,
+                presence => presence,
+            };
+        let as_rust_type = to_rust_type(ctx, fields_scope, &field.typ)?;
+        let field_type = match presence {
+            Presence::Required | Presence::Defaulted | Presence::Map => {
+                as_rust_type.clone()
             }
+            Presence::Repeated => format!("Vec<{}>", as_rust_type),
+            Presence::Optional => format!("Option<{}>", as_rust_type),
+        };
+        let field_type: Type = syn::parse_str(&field_type)?;
+        let field_attributes =
+            gen_attributes(&ctx.config.field_attributes_for(&field_full_name))?;
+        let label = match (field.rule, &field.typ) {
+            (_, FieldType::Map(_)) => "",
+            (Rule::Repeated, _) => "repeated ",
+            _ => "",
+        };
+        let fallback = format!(
+            "Field {}, type `{}{}`",
+            field.number,
+            label,
+            proto_type(&field.typ)
+        );
+        let doc = gen_doc(ctx, &field_full_name, fallback);
+        fields = quote! {
+            #fields
+            #doc
+            #field_attributes
+            pub #field_name: #field_type,
+        };
+
+        let (key, value) = match &field.typ {
+            FieldType::Map(map) => (
+                Some(value_spec(ctx, fields_scope, &map.0)?),
+                value_spec(ctx, fields_scope, &map.1)?,
+            ),
+            typ => (None, value_spec(ctx, fields_scope, typ)?),
+        };
+        specs.push(FieldSpec {
+            ident: field_name,
+            number: field.number as u32,
+            presence,
+            field_type,
+            key,
+            value,
+            // Scalar repeated fields are packed by default only in proto3
+            packed: field
+                .packed
+                .unwrap_or(matches!(ctx.syntax, Syntax::Proto3)),
+        });
+
+        if let (Presence::Optional, Some(default)) = (presence, &field.default)
+        {
+            let getter = gen_default_getter(
+                ctx,
+                fields_scope,
+                field,
+                default,
+                &as_rust_type,
+            )?;
+            getters = quote! { #getters #getter };
         }
     }
+
+    let attributes = gen_type_attributes(ctx, &full_name)?;
+    let fallback = format!("`{}` message", full_name.trim_start_matches('.'));
+    let doc = gen_doc(ctx, &full_name, fallback);
+    let main_struct = quote! {
+        #doc
+        #attributes
+        #[derive(Default)]
+        pub struct #struct_name { #fields }
+    };
+
+    let MessageCode {
+        size,
+        encode,
+        decode,
+    } = gen_message_code(&struct_name, &specs);
     let encode_impl = quote! {
         impl Encode for #struct_name {
             fn size(&self) -> u32 {
-                let mut size = 0;
-                #sizes
-                size
+                #size
             }
 
             fn encode<W: std::io::Write>(&self, mut w: W) -> Result<(), EncodingError> {
-                #encoding_code
-                Ok(())
+                #encode
             }
         }
     };
 
-    let mut decode_init_fields = quote! {};
-    let mut decode_fields = quote! {};
-    let mut decode_match = quote! {};
-    for field in &message.fields {
-        let field_name: Ident =
-            syn::parse_str(escape_rust_keyword(&field.name))?;
-        let number = field.number as u8;
-        let wire_type = to_wire_type(&field.typ, &type_info);
-        let encoding_type: Path = syn::parse_str(&encoding_type(
-            &field.typ,
-            module_name_str,
-            &type_info,
-        ))?;
-
-        let field_init = match field.rule {
-            Rule::Repeated => quote! { let mut #field_name = vec![]; },
-            _ => quote! { let mut #field_name = None; },
-        };
-        decode_init_fields = quote! { #decode_init_fields #field_init };
-
-        let field_assignment = match field.rule {
-            Rule::Required => quote! {
-                #field_name: #field_name
-                    .ok_or(EncodingError::MissingField(number))?,
-            },
-            _ => quote! { #field_name: #field_name, },
-        };
-        decode_fields = quote! { #decode_fields #field_assignment };
-
-        let decode_result =
-            quote! { <#encoding_type>::decode_as_field(&mut r)? };
-        let field_match = match (field.rule, uses_wrapper(&field.typ)) {
-            (Rule::Repeated, false) => {
-                quote! {
-                    #number => if __wire_type == #wire_type {
-                        #field_name.push(#decode_result);
-                    } else if __wire_type == 2 {
-                        #field_name.append(
-                            &mut decode_packed::<_, #encoding_type>(&mut r)?);
-                    } else {
-                        return Err(EncodingError::WrongWireType(
-                            stringify!(#struct_name.#field_name), __wire_type))
-                    }
-                }
-            }
-            (Rule::Repeated, true) => {
-                quote! {
-                    #number => if __wire_type == #wire_type {
-                        #field_name.push(#decode_result.0);
-                    } else if __wire_type == 2 {
-                        #field_name.extend(
-                            decode_packed::<_, #encoding_type>(&mut r)?
-                                .into_iter()
-                                .map(|x| x.0)
-                        );
-                    } else {
-                        return Err(EncodingError::WrongWireType(
-                            stringify!(#struct_name.#field_name), __wire_type))
-                    }
-                }
-            }
-            (_, false) => {
-                quote! {
-                    #number => if __wire_type == #wire_type {
-                        #field_name = Some(#decode_result);
-                    } else {
-                        return Err(EncodingError::WrongWireType(
-                            stringify!(#struct_name.#field_name), __wire_type))
-                    }
-                }
-            }
-            (_, true) => {
-                quote! {
-                    #number => if __wire_type == #wire_type {
-                        #field_name = Some(#decode_result.0);
-                    } else {
-                        return Err(EncodingError::WrongWireType(
-                            stringify!(#struct_name.#field_name), __wire_type))
-                    }
-                }
-            }
-        };
-        decode_match = quote! { #decode_match #field_match };
-    }
     let decode_impl = quote! {
         impl Decode for #struct_name {
             fn decode<R: std::io::Read>(mut r: R) -> Result<Self, EncodingError> {
-                #decode_init_fields
-                loop {
-                    let mut __key = 0u8;
-                    if r.read(std::slice::from_mut(&mut __key))? == 0 {
-                        return Ok(Self {
-                            #decode_fields
-                        });
-                    }
-                    let (__number, __wire_type) = (__key >> 3, __key & 0x7);
-                    match __number {
-                        #decode_match
-                        _ => (),
-                    }
-                }
+                #decode
             }
             fn decode_as_field(r: &mut dyn std::io::Read) -> Result<Self, EncodingError> {
                 Self::decode_with_len(r)
@@ -321,162 +538,440 @@ fn gen_message(
         }
     };
 
+    let name_str = full_name.trim_start_matches('.');
+    let name_impl = quote! {
+        impl MessageName for #struct_name {
+            const FULL_NAME: &'static str = #name_str;
+        }
+    };
+
+    let getters_impl = if getters.is_empty() {
+        quote! {}
+    } else {
+        quote! { impl #struct_name { #getters } }
+    };
+
     let sub_messages: TokenStream = message
         .messages
         .iter()
-        .map(|m| gen_message(&type_info, m))
+        .map(|m| gen_message(ctx, &scope.nested(&message.name), m))
         .collect::<Result<_, _>>()?;
     let sub_enums: TokenStream = message
         .enums
         .iter()
-        .map(|e| gen_enum(e))
+        .map(|e| gen_enum(ctx, &full_name, e))
         .collect::<Result<_, _>>()?;
 
     // subtypes
     let emit_mod = !message.messages.is_empty() || !message.enums.is_empty();
-    let sub_mod = emit_mod
-        .then(|| {
-            quote! {
-                pub mod #module_name {
-                    #[allow(unused_imports)]
-                    use super::*;
-                    #sub_messages
-                    #sub_enums
-                }
+    let sub_mod = if emit_mod {
+        quote! {
+            pub mod #module_name {
+                #[allow(unused_imports)]
+                use super::*;
+                #sub_messages
+                #sub_enums
             }
-        })
-        .unwrap_or_default();
+        }
+    } else {
+        quote! {}
+    };
 
     Ok(quote! {
         #main_struct
         #encode_impl
         #decode_impl
+        #name_impl
+        #getters_impl
         #sub_mod
     })
 }
 
-fn gen_enum(e: &Enumeration) -> syn::Result<TokenStream> {
-    let variants: TokenStream = e
+// Getter returning explicit proto2 `[default = ...]` when field is not set
+fn gen_default_getter(
+    ctx: &Context,
+    scope: &Scope,
+    field: &Field,
+    default: &str,
+    rust_type: &str,
+) -> syn::Result<TokenStream> {
+    let field_name: Ident = syn::parse_str(escape_rust_keyword(&field.name))?;
+    let invalid = || {
+        let msg = format!(
+            "Invalid default value for field `{}`: {}",
+            field.name, default
+        );
+        syn::Error::new(Span::call_site(), msg)
+    };
+    let getter = match &field.typ {
+        FieldType::String => {
+            let bytes = unescape_string(default).ok_or_else(invalid)?;
+            let value = String::from_utf8(bytes).map_err(|_| invalid())?;
+            let value = LitStr::new(&value, Span::call_site());
+            quote! {
+                pub fn #field_name(&self) -> &str {
+                    self.#field_name.as_deref().unwrap_or(#value)
+                }
+            }
+        }
+        FieldType::Bytes => {
+            let value = unescape_string(default).ok_or_else(invalid)?;
+            let value = LitByteStr::new(&value, Span::call_site());
+            quote! {
+                pub fn #field_name(&self) -> &[u8] {
+                    self.#field_name.as_deref().unwrap_or(&#value[..])
+                }
+            }
+        }
+        typ => {
+            let value = match typ {
+                FieldType::Bool => match default {
+                    "true" | "false" => default.to_string(),
+                    _ => return Err(invalid()),
+                },
+                FieldType::Float | FieldType::Double => match default {
+                    "inf" => format!("{}::INFINITY", rust_type),
+                    "-inf" => format!("{}::NEG_INFINITY", rust_type),
+                    "nan" => format!("{}::NAN", rust_type),
+                    _ => {
+                        let value: f64 =
+                            default.parse().map_err(|_| invalid())?;
+                        format!("{:?}{}", value, rust_type)
+                    }
+                },
+                FieldType::MessageOrEnum(name) => match ctx
+                    .resolve(scope, name)?
+                    .info
+                {
+                    TypeInfo::Enum => {
+                        format!("{}::{}", rust_type, default.to_camel_case())
+                    }
+                    _ => return Err(invalid()),
+                },
+                _ => {
+                    let value = parse_int(default).ok_or_else(invalid)?;
+                    let in_range = match rust_type {
+                        "i32" => i32::try_from(value).is_ok(),
+                        "i64" => i64::try_from(value).is_ok(),
+                        "u32" => u32::try_from(value).is_ok(),
+                        "u64" => u64::try_from(value).is_ok(),
+                        _ => false,
+                    };
+                    if !in_range {
+                        return Err(invalid());
+                    }
+                    format!("{}{}", value, rust_type)
+                }
+            };
+            let value: Expr = syn::parse_str(&value)?;
+            let rust_type: Type = syn::parse_str(rust_type)?;
+            quote! {
+                pub fn #field_name(&self) -> #rust_type {
+                    self.#field_name.unwrap_or(#value)
+                }
+            }
+        }
+    };
+    Ok(getter)
+}
+
+// Parses decimal, hex and octal integer literals
+fn parse_int(s: &str) -> Option<i128> {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+    let value = if let Some(hex) =
+        s.strip_prefix("0x").or_else(|| s.strip_prefix("0X"))
+    {
+        i128::from_str_radix(hex, 16).ok()?
+    } else if s.len() > 1 && s.starts_with('0') {
+        i128::from_str_radix(&s[1..], 8).ok()?
+    } else {
+        s.parse().ok()?
+    };
+    Some(if negative { -value } else { value })
+}
+
+// Turns quoted protobuf string literal into raw bytes
+fn unescape_string(s: &str) -> Option<Vec<u8>> {
+    let quote = s.chars().next().filter(|&c| c == '"' || c == '\'')?;
+    let s = s.strip_prefix(quote)?.strip_suffix(quote)?;
+    let mut res = vec![];
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            let mut buf = [0; 4];
+            res.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        let byte = match chars.next()? {
+            'a' => 0x07,
+            'b' => 0x08,
+            'f' => 0x0c,
+            'n' => b'\n',
+            'r' => b'\r',
+            't' => b'\t',
+            'v' => 0x0b,
+            '?' => b'?',
+            '\\' => b'\\',
+            '\'' => b'\'',
+            '"' => b'"',
+            'x' | 'X' => {
+                let mut value = 0;
+                for _ in 0..2 {
+                    match chars.peek().and_then(|c| c.to_digit(16)) {
+                        Some(d) => value = value * 16 + d,
+                        None => break,
+                    }
+                    chars.next();
+                }
+                value as u8
+            }
+            c @ '0'..='7' => {
+                let mut value = c.to_digit(8)?;
+                for _ in 0..2 {
+                    match chars.peek().and_then(|c| c.to_digit(8)) {
+                        Some(d) => value = value * 8 + d,
+                        None => break,
+                    }
+                    chars.next();
+                }
+                value as u8
+            }
+            _ => return None,
+        };
+        res.push(byte);
+    }
+    Some(res)
+}
+
+fn gen_enum(
+    ctx: &Context,
+    scope: &str,
+    e: &Enumeration,
+) -> syn::Result<TokenStream> {
+    let full_name = format!("{}.{}", scope, e.name);
+    let name: Ident = syn::parse_str(&e.name.to_camel_case())?;
+    let values = e
         .values
         .iter()
         .map(|f| {
             let name: Ident = syn::parse_str(&f.name.to_camel_case())?;
             let number: LitInt = syn::parse_str(&f.number.to_string())?;
-            Ok(quote! { #name = #number, })
+            let value_name = format!("{}.{}", full_name, f.name);
+            let doc = gen_doc(ctx, &value_name, format!("Value {}", f.number));
+            Ok((name, number, doc))
         })
-        .collect::<syn::Result<_>>()?;
+        .collect::<syn::Result<Vec<_>>>()?;
+    let names: Vec<_> = values.iter().map(|(name, ..)| name).collect();
+    let numbers: Vec<_> = values.iter().map(|(_, number, _)| number).collect();
+    let docs: Vec<_> = values.iter().map(|(.., doc)| doc).collect();
 
-    let match_variants: TokenStream = e
-        .values
-        .iter()
-        .map(|f| {
-            let name: Ident = syn::parse_str(&f.name.to_camel_case())?;
-            let number: LitInt = syn::parse_str(&f.number.to_string())?;
-            Ok(quote! { #number => Ok(Self::#name), })
+    // Both proto2 and proto3 default to first listed value
+    let derive_default = if names.is_empty() {
+        quote! {}
+    } else {
+        quote! { #[derive(Default)] }
+    };
+    let defaults: Vec<_> = (0..names.len())
+        .map(|i| {
+            if i == 0 {
+                quote! { #[default] }
+            } else {
+                quote! {}
+            }
         })
-        .collect::<syn::Result<_>>()?;
+        .collect();
+
+    let attributes = gen_type_attributes(ctx, &full_name)?;
+    let fallback = format!("`{}` enum", full_name.trim_start_matches('.'));
+    let doc = gen_doc(ctx, &full_name, fallback);
+
+    // proto3 enums are open, unknown values are kept in extra variant
+    let is_open = matches!(ctx.syntax, Syntax::Proto3);
+    let unknown: Ident = if names.iter().any(|x| *x == "Unknown") {
+        syn::parse_str("Unrecognized")?
+    } else {
+        syn::parse_str("Unknown")?
+    };
+    let declaration = if is_open {
+        quote! {
+            #doc
+            #attributes
+            #[derive(Copy)]
+            #derive_default
+            pub enum #name {
+                #(#defaults #docs #names,)*
+                /// Value not known when generating code
+                #unknown(i32),
+            }
+        }
+    } else {
+        quote! {
+            #doc
+            #[repr(u32)]
+            #attributes
+            #[derive(Copy)]
+            #derive_default
+            pub enum #name {
+                #(#defaults #docs #names = #numbers,)*
+            }
+        }
+    };
+    let (unknown_number, from_number) = if is_open {
+        let from_number = quote! {
+            impl From<i32> for #name {
+                fn from(number: i32) -> Self {
+                    match number {
+                        #(#numbers => Self::#names,)*
+                        x => Self::#unknown(x),
+                    }
+                }
+            }
+        };
+        (quote! { Self::#unknown(x) => x, }, from_number)
+    } else {
+        (quote! {}, quote! {})
+    };
+    // Negative values are sign-extended to 64 bits on the wire, like int32
+    let decode = if is_open {
+        quote! { Ok(Self::from(<Varint<u64>>::decode(r)?.0 as i32)) }
+    } else {
+        quote! {
+            match <Varint<u64>>::decode(r)?.0 as i32 {
+                #(#numbers => Ok(Self::#names),)*
+                e => Err(EncodingError::InvalidEnumValue(
+                    stringify!(#name), e as u32)),
+            }
+        }
+    };
 
-    let name: Ident = syn::parse_str(&e.name.to_camel_case())?;
     Ok(quote! {
-        #[repr(u32)]
-        #[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
-        pub enum #name {
-            #variants
+        #declaration
+        impl #name {
+            /// Number of the value, as on the wire
+            pub fn number(self) -> i32 {
+                match self {
+                    #(Self::#names => #numbers,)*
+                    #unknown_number
+                }
+            }
+        }
+        #from_number
+        impl From<#name> for i32 {
+            fn from(value: #name) -> i32 {
+                value.number()
+            }
         }
         impl Encode for #name {
             fn size(&self) -> u32 {
-                Varint(*self as u32).size()
+                Varint(self.number() as i64 as u64).size()
             }
 
             fn encode<W: std::io::Write>(&self, w: W)
                 -> Result<(), EncodingError>
             {
-                Varint(*self as u32).encode(w)
+                Varint(self.number() as i64 as u64).encode(w)
             }
         }
         impl Decode for #name {
             fn decode<R: std::io::Read>(r: R) -> Result<Self, EncodingError> {
-                match <Varint<u32>>::decode(r)?.0 {
-                    #match_variants
-                    e => Err(EncodingError::InvalidEnumValue(
-                        stringify!(#name), e)),
-                }
+                #decode
             }
         }
     })
 }
 
-fn encoding_field(typ: &FieldType, field_name: &str) -> String {
-    match typ {
-        FieldType::Int32 => format!("Varint({})", field_name),
-        FieldType::Int64 => format!("Varint({})", field_name),
-        FieldType::Uint32 => format!("Varint({})", field_name),
-        FieldType::Uint64 => format!("Varint({})", field_name),
-        FieldType::Sint32 => format!("SVarint({})", field_name),
-        FieldType::Sint64 => format!("SVarint({})", field_name),
-        FieldType::Fixed32 => format!("Fixed({})", field_name),
-        FieldType::Fixed64 => format!("Fixed({})", field_name),
-        FieldType::Sfixed32 => format!("Fixed({})", field_name),
-        FieldType::Sfixed64 => format!("Fixed({})", field_name),
-        _ => field_name.to_string(),
+// Client trait of a service, implemented for every transport
+fn gen_service(
+    ctx: &Context,
+    scope: &Scope,
+    service: &ServiceInfo,
+) -> syn::Result<TokenStream> {
+    let proto_name = format!("{}.{}", scope.proto, service.name);
+    let full_name = proto_name.trim_start_matches('.');
+    let trait_name: Ident =
+        syn::parse_str(&format!("{}Client", service.name.to_camel_case()))?;
+    let mut signatures = vec![];
+    let mut docs = vec![];
+    let mut bodies = vec![];
+    for method in &service.methods {
+        if method.client_streaming || method.server_streaming {
+            let msg = format!(
+                "Streaming rpc {}.{} is not supported",
+                full_name, method.name
+            );
+            return Err(syn::Error::new(Span::call_site(), msg));
+        }
+        let name: Ident =
+            syn::parse_str(escape_rust_keyword(&method.name.to_snake_case()))?;
+        let input = rpc_type(ctx, scope, &method.input)?;
+        let output = rpc_type(ctx, scope, &method.output)?;
+        let path = format!("/{}/{}", full_name, method.name);
+        let method_name = format!("{}.{}", proto_name, method.name);
+        docs.push(gen_doc(ctx, &method_name, format!("Calls `{}`", path)));
+        signatures.push(quote! {
+            fn #name(&mut self, request: &#input)
+                -> Result<#output, protobuf_but_worse::rpc::RpcError>
+        });
+        bodies.push(quote! { self.call(#path, request) });
+    }
+    let fallback = format!("Client of `{}` service", full_name);
+    let doc = gen_doc(ctx, &proto_name, fallback);
+    Ok(quote! {
+        #doc
+        pub trait #trait_name {
+            #(#docs #signatures;)*
+        }
+        impl<T: protobuf_but_worse::rpc::Transport> #trait_name for T {
+            #(#signatures { #bodies })*
+        }
+    })
+}
+
+fn rpc_type(ctx: &Context, scope: &Scope, name: &str) -> syn::Result<Type> {
+    if ctx.resolve(scope, name)?.info != TypeInfo::Message {
+        let msg = format!("Rpc type {} is not a message", name);
+        return Err(syn::Error::new(Span::call_site(), msg));
     }
+    syn::parse_str(&ctx.rust_path(scope, name)?)
 }
 
-fn encoding_type(
-    typ: &FieldType,
-    parent: &str,
-    type_info: &TypeInfoMap,
-) -> String {
-    match typ {
-        FieldType::Int32 => "Varint<i32>".to_string(),
-        FieldType::Int64 => "Varint<i64>".to_string(),
-        FieldType::Uint32 => "Varint<u32>".to_string(),
-        FieldType::Uint64 => "Varint<u64>".to_string(),
-        FieldType::Sint32 => "SVarint<i32>".to_string(),
-        FieldType::Sint64 => "SVarint<i64>".to_string(),
-        FieldType::Fixed32 => "Fixed<u32>".to_string(),
-        FieldType::Fixed64 => "Fixed<u64>".to_string(),
-        FieldType::Sfixed32 => "Fixed<i32>".to_string(),
-        FieldType::Sfixed64 => "Fixed<i64>".to_string(),
-        FieldType::Double => "f64".to_string(),
-        FieldType::Float => "f32".to_string(),
-        FieldType::Bool => "bool".to_string(),
-        FieldType::String => "String".to_string(),
-        FieldType::Bytes => "Vec<u8>".to_string(),
-        FieldType::MessageOrEnum(s) => {
-            if type_info.get_current(s).is_some() {
-                format!("{}::{}", parent, s)
-            } else {
-                s.to_string()
-            }
-        }
-        FieldType::Group(_) => todo!("group"),
-        FieldType::Map(_) => todo!("map"),
+fn unsupported_group(scope: &Scope) -> syn::Error {
+    let msg = format!("Groups are not supported, in {}", scope.proto);
+    syn::Error::new(Span::call_site(), msg)
+}
+
+fn bytes_type(ctx: &Context) -> &'static str {
+    match ctx.config.bytes {
+        BytesType::Vec => "Vec<u8>",
+        BytesType::Bytes => "protobuf_but_worse::bytes::Bytes",
     }
 }
 
-fn uses_wrapper(typ: &FieldType) -> bool {
+// Wrapper selecting encoding of integer types
+fn wrapper(typ: &FieldType) -> Option<&'static str> {
     match typ {
         FieldType::Int32
         | FieldType::Int64
         | FieldType::Uint32
-        | FieldType::Uint64
-        | FieldType::Sint32
-        | FieldType::Sint64
-        | FieldType::Fixed32
+        | FieldType::Uint64 => Some("Varint"),
+        FieldType::Sint32 | FieldType::Sint64 => Some("SVarint"),
+        FieldType::Fixed32
         | FieldType::Fixed64
         | FieldType::Sfixed32
-        | FieldType::Sfixed64 => true,
-        _ => false,
+        | FieldType::Sfixed64 => Some("Fixed"),
+        _ => None,
     }
 }
 
 fn to_rust_type(
+    ctx: &Context,
+    scope: &Scope,
     typ: &FieldType,
-    parent: &str,
-    type_info: &TypeInfoMap,
-) -> String {
-    match typ {
+) -> syn::Result<String> {
+    Ok(match typ {
         FieldType::Double => "f64".to_string(),
         FieldType::Float => "f32".to_string(),
         FieldType::Int32 => "i32".to_string(),
@@ -491,21 +986,30 @@ fn to_rust_type(
         FieldType::Sfixed64 => "i64".to_string(),
         FieldType::Bool => "bool".to_string(),
         FieldType::String => "String".to_string(),
-        FieldType::Bytes => "Vec<u8>".to_string(),
-        FieldType::MessageOrEnum(s) => {
-            if type_info.get_current(s).is_some() {
-                format!("{}::{}", parent, s)
-            } else {
-                s.to_string()
-            }
+        FieldType::Bytes => bytes_type(ctx).to_string(),
+        FieldType::MessageOrEnum(s) => ctx.rust_path(scope, s)?,
+        FieldType::Group(_) => return Err(unsupported_group(scope)),
+        FieldType::Map(map) => {
+            let map_type = match ctx.config.map_type {
+                MapType::HashMap => "std::collections::HashMap",
+                MapType::BTreeMap => "std::collections::BTreeMap",
+            };
+            format!(
+                "{}<{}, {}>",
+                map_type,
+                to_rust_type(ctx, scope, &map.0)?,
+                to_rust_type(ctx, scope, &map.1)?
+            )
         }
-        FieldType::Group(_) => todo!("group"),
-        FieldType::Map(_) => todo!("map"),
-    }
+    })
 }
 
-fn to_wire_type(typ: &FieldType, type_info: &TypeInfoMap) -> u8 {
-    match typ {
+fn to_wire_type(
+    ctx: &Context,
+    scope: &Scope,
+    typ: &FieldType,
+) -> syn::Result<u8> {
+    Ok(match typ {
         FieldType::Int32
         | FieldType::Int64
         | FieldType::Uint32
@@ -515,19 +1019,14 @@ fn to_wire_type(typ: &FieldType, type_info: &TypeInfoMap) -> u8 {
         | FieldType::Bool => 0,
         FieldType::Fixed64 | FieldType::Sfixed64 | FieldType::Double => 1,
         FieldType::String | FieldType::Bytes => 2,
-        FieldType::MessageOrEnum(s) => {
-            match type_info
-                .get(s)
-                .expect(&format!("Missing type info on {}", s))
-            {
-                TypeInfo::Enum => 0,
-                TypeInfo::Message => 2,
-            }
-        }
+        FieldType::MessageOrEnum(s) => match ctx.resolve(scope, s)?.info {
+            TypeInfo::Enum => 0,
+            TypeInfo::Message | TypeInfo::Wrapper => 2,
+        },
         FieldType::Fixed32 | FieldType::Sfixed32 | FieldType::Float => 5,
-        FieldType::Group(_) => todo!("group wire type"),
-        FieldType::Map(_) => todo!("map wire type"),
-    }
+        FieldType::Group(_) => return Err(unsupported_group(scope)),
+        FieldType::Map(_) => 2,
+    })
 }
 
 const RUST_KEYWORDS: &[(&str, &str)] = &[
@@ -591,3 +1090,246 @@ fn escape_rust_keyword(s: &str) -> &str {
         .map(|x| x.1)
         .unwrap_or(s)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proto2() {
+        let source = r#"
+            syntax = "proto2";
+            message Foo {
+                required uint32 id = 1;
+                optional string name = 2 [default = "foo\n"];
+                optional sint64 offset = 3 [default = -0x10];
+                optional Kind kind = 4 [default = SECOND];
+                repeated fixed32 values = 17 [packed = true];
+                extensions 100 to 199;
+                enum Kind {
+                    FIRST = 0;
+                    SECOND = 1;
+                }
+            }
+            extend Foo {
+                optional bool flag = 100;
+            }
+        "#;
+        let proto = crate::parse_str(source).unwrap();
+        let code = gen_proto(&proto, &Config::new()).unwrap();
+        syn::parse_file(&code).unwrap();
+        assert!(code.contains("pub id : u32"));
+        assert!(code.contains("MissingField"));
+        assert!(code.contains("unwrap_or (\"foo\\n\")"));
+        assert!(code.contains("unwrap_or (- 16i64)"));
+        assert!(code.contains("unwrap_or (foo :: Kind :: Second)"));
+    }
+
+    #[test]
+    fn invalid_default() {
+        let source = r#"
+            syntax = "proto2";
+            message Foo {
+                optional int32 small = 1 [default = 3000000000];
+            }
+        "#;
+        let proto = crate::parse_str(source).unwrap();
+        assert!(gen_proto(&proto, &Config::new()).is_err());
+    }
+
+    #[test]
+    fn implicit_presence() {
+        let source = r#"
+            syntax = "proto3";
+            package test;
+            message Foo {
+                int32 id = 1;
+                optional string name = 2;
+                Foo next = 3;
+            }
+        "#;
+        let proto = crate::parse_str(source).unwrap();
+        assert!(proto.info.explicit_optional.contains(".test.Foo.name"));
+        let mut config = Config::new();
+        config.field_style(FieldStyle::Default);
+        let code = gen_proto(&proto, &config).unwrap();
+        syn::parse_file(&code).unwrap();
+        assert!(code.contains("pub id : i32"));
+        assert!(code.contains("pub name : Option < String >"));
+        assert!(code.contains("pub next : Option < Foo >"));
+    }
+
+    #[test]
+    fn nested_types() {
+        let source = r#"
+            syntax = "proto3";
+            package a.b;
+            message Inner {}
+            message Outer {
+                message Middle {
+                    message Inner {
+                        Middle parent = 1;
+                        Kind kind = 2;
+                    }
+                    Inner inner = 1;
+                    Sibling sibling = 2;
+                    .a.b.Inner top = 3;
+                }
+                message Sibling {
+                    Middle.Inner inner = 1;
+                }
+                enum Kind { A = 0; }
+                Middle.Inner deep = 1;
+                map<string, Outer.Middle> middles = 2;
+            }
+        "#;
+        let proto = crate::parse_str(source).unwrap();
+        let code = gen_proto(&proto, &Config::new()).unwrap();
+        syn::parse_file(&code).unwrap();
+        let fields = [
+            "pub parent : Option < super :: Middle >",
+            "pub kind : Option < super :: Kind >",
+            "pub inner : Option < middle :: Inner >",
+            "pub sibling : Option < Sibling >",
+            "pub top : Option < super :: Inner >",
+            "pub deep : Option < outer :: middle :: Inner >",
+            "HashMap < String , outer :: Middle >",
+        ];
+        for field in &fields {
+            assert!(code.contains(field), "{}", field);
+        }
+
+        let source = "syntax = \"proto3\"; message A { B b = 1; }";
+        let proto = crate::parse_str(source).unwrap();
+        let error = gen_proto(&proto, &Config::new()).unwrap_err();
+        assert_eq!(error.to_string(), "Unknown type B in .A");
+    }
+
+    #[test]
+    fn open_enums() {
+        let source = r#"
+            syntax = "proto3";
+            enum Kind { A = 0; B = 2; }
+            enum Status { UNKNOWN = 0; }
+        "#;
+        let proto = crate::parse_str(source).unwrap();
+        let code = gen_proto(&proto, &Config::new()).unwrap();
+        syn::parse_file(&code).unwrap();
+        assert!(code.contains("Unknown (i32) ,"));
+        assert!(code.contains("x => Self :: Unknown (x)"));
+        assert!(code.contains("Unrecognized (i32) ,"));
+        assert!(!code.contains("InvalidEnumValue"));
+
+        let source = source.replace("proto3", "proto2");
+        let proto = crate::parse_str(&source).unwrap();
+        let code = gen_proto(&proto, &Config::new()).unwrap();
+        assert!(!code.contains("Unknown (i32)"));
+        assert!(code.contains("InvalidEnumValue"));
+    }
+
+    #[test]
+    fn services() {
+        let source = r#"
+            syntax = "proto3";
+            package test;
+            message Foo {
+                message Bar {}
+            }
+            service Things {
+                rpc GetFoo(Foo) returns (.test.Foo.Bar);
+                rpc Stream(Foo) returns (stream Foo) {
+                    option deprecated = true;
+                }
+            }
+        "#;
+        let proto = crate::parse_str(source).unwrap();
+        let methods = &proto.info.services[0].methods;
+        assert_eq!(methods[0].output, ".test.Foo.Bar");
+        assert!(methods[1].server_streaming);
+        assert!(gen_proto(&proto, &Config::new()).is_err());
+
+        let source = source.replace("stream Foo", "Foo");
+        let proto = crate::parse_str(&source).unwrap();
+        let code = gen_proto(&proto, &Config::new()).unwrap();
+        syn::parse_file(&code).unwrap();
+        assert!(code.contains("pub trait ThingsClient"));
+        assert!(code.contains("fn get_foo (& mut self , request : & Foo)"));
+        assert!(code.contains("Result < foo :: Bar ,"));
+        assert!(
+            code.contains("self . call (\"/test.Things/Stream\" , request)")
+        );
+    }
+
+    #[test]
+    fn docs() {
+        let source = r#"
+            syntax = "proto3";
+            package test;
+
+            // Detached
+
+            // Thing
+            // with two lines
+            message Foo {
+                int32 a = 1; // Trailing
+                /* Block
+                 * comment */
+                repeated string b = 2;
+                map<string, Foo> c = 3;
+                repeated uint32 e = 5;
+                oneof x {
+                    // In oneof
+                    bool d = 4;
+                }
+                enum E {
+                    A = 0; // First
+                };
+            }
+            service S {
+                // Method
+                rpc M(Foo) returns (Foo);
+            }
+        "#;
+        let proto = crate::parse_str(source).unwrap();
+        let docs = &proto.info.docs;
+        assert_eq!(docs[".test.Foo"], "Thing\nwith two lines");
+        assert_eq!(docs[".test.Foo.a"], "Trailing");
+        assert_eq!(docs[".test.Foo.b"], "Block\ncomment");
+        assert_eq!(docs[".test.Foo.d"], "In oneof");
+        assert_eq!(docs[".test.Foo.E.A"], "First");
+        assert_eq!(docs[".test.S.M"], "Method");
+        assert_eq!(docs.len(), 6);
+
+        let code = gen_proto(&proto, &Config::new()).unwrap();
+        syn::parse_file(&code).unwrap();
+        assert!(code.contains("# [doc = \" with two lines\"]"));
+        assert!(code.contains("# [doc = \" Field 5, type `repeated uint32`\"]"));
+        assert!(
+            code.contains("# [doc = \" Field 3, type `map<string, Foo>`\"]")
+        );
+        assert!(code.contains("# [doc = \" First\"] A"));
+        assert!(code.contains("# [doc = \" `test.Foo.E` enum\"]"));
+    }
+
+    #[test]
+    fn well_known_types() {
+        let source = r#"
+            syntax = "proto3";
+            package test;
+            message Foo {
+                google.protobuf.Timestamp at = 1;
+                google.protobuf.Int32Value count = 2;
+                repeated .google.protobuf.StringValue names = 3;
+            }
+        "#;
+        let proto = crate::parse_str(source).unwrap();
+        let code = gen_proto(&proto, &Config::new()).unwrap();
+        syn::parse_file(&code).unwrap();
+        assert!(code.contains(
+            "pub at : Option < protobuf_but_worse :: well_known :: Timestamp >"
+        ));
+        assert!(code.contains("pub count : Option < i32 >"));
+        assert!(code.contains("pub names : Vec < Option < String > >"));
+        assert!(crate::descriptor::Pool::from_source(source).is_ok());
+    }
+}
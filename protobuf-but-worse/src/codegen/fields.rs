@@ -0,0 +1,362 @@
+// Encoding and decoding of message fields, shared between
+// code generated from .proto files and `protobuf-but-worse-derive`.
+// Emitted code expects `protobuf_but_worse::encoding::*` in scope.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Ident, Type};
+
+/// How a field is stored in message struct
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Presence {
+    /// Plain value, error when missing
+    Required,
+    /// Plain value, type's default when missing, not encoded when default
+    Defaulted,
+    Optional,
+    Repeated,
+    Map,
+}
+
+/// Single value of a field, or map key or value
+#[derive(Clone)]
+pub struct ValueSpec {
+    /// `Varint`, `SVarint` or `Fixed` wrapper used for encoding
+    pub wrapper: Option<Ident>,
+    pub rust_type: Type,
+    pub wire_type: u8,
+    /// Nested message, encoded with length prefix
+    pub is_message: bool,
+}
+
+impl ValueSpec {
+    // `x` wrapped for encoding
+    fn wrap(&self, x: TokenStream) -> TokenStream {
+        match &self.wrapper {
+            Some(wrapper) => quote! { #wrapper(#x) },
+            None => x,
+        }
+    }
+
+    fn encoding_type(&self) -> TokenStream {
+        let rust_type = &self.rust_type;
+        match &self.wrapper {
+            Some(wrapper) => quote! { #wrapper<#rust_type> },
+            None => quote! { #rust_type },
+        }
+    }
+
+    // Size and encoding of value bound as `x: &T`, written into `w`
+    fn size_and_encode(&self) -> (TokenStream, TokenStream) {
+        let value = self.wrap(quote! { *x });
+        if self.is_message {
+            (
+                quote! { { let len = (#value).size(); Varint(len).size() + len } },
+                quote! { (#value).encode_with_len(&mut w)?; },
+            )
+        } else {
+            (
+                quote! { (#value).size() },
+                quote! { (#value).encode(&mut w)?; },
+            )
+        }
+    }
+
+    // Expression decoding value from `r`
+    fn decode(&self) -> TokenStream {
+        let encoding_type = self.encoding_type();
+        if self.wrapper.is_some() {
+            quote! { <#encoding_type>::decode_as_field(&mut r)?.0 }
+        } else {
+            quote! { <#encoding_type>::decode_as_field(&mut r)? }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct FieldSpec {
+    pub ident: Ident,
+    pub number: u32,
+    pub presence: Presence,
+    /// Type of whole field, e.g. `Option<T>` or `Vec<T>`
+    pub field_type: Type,
+    /// Key of map fields
+    pub key: Option<ValueSpec>,
+    /// Value, or map value
+    pub value: ValueSpec,
+    /// Repeated scalars are written packed
+    pub packed: bool,
+}
+
+/// Bodies of `Encode::size`, `Encode::encode` and `Decode::decode`
+pub struct MessageCode {
+    pub size: TokenStream,
+    pub encode: TokenStream,
+    pub decode: TokenStream,
+}
+
+pub fn gen_message_code(
+    struct_name: &Ident,
+    fields: &[FieldSpec],
+) -> MessageCode {
+    let mut sizes = quote! {};
+    let mut encoding_code = quote! {};
+    let mut decode_init_fields = quote! {};
+    let mut decode_fields = quote! {};
+    let mut decode_match = quote! {};
+    for field in fields {
+        let field_name = &field.ident;
+        let field_type = &field.field_type;
+        let number = field.number;
+        let presence = field.presence;
+        let wrong_wire_type = quote! {
+            return Err(EncodingError::WrongWireType(
+                stringify!(#struct_name.#field_name), __wire_type))
+        };
+
+        let value = &field.value;
+        if let Some(key) = &field.key {
+            let (key_size, encode_key_value) = key.size_and_encode();
+            let (value_size, encode_value) = value.size_and_encode();
+            let key_wire_type = key.wire_type;
+            let value_wire_type = value.wire_type;
+            sizes = quote! {
+                #sizes
+                for (k, v) in &self.#field_name {
+                    let len = { let x = k; key_size(1) + #key_size }
+                        + { let x = v; key_size(2) + #value_size };
+                    size += key_size(#number) + Varint(len).size() + len;
+                }
+            };
+            encoding_code = quote! {
+                #encoding_code
+                for (k, v) in &self.#field_name {
+                    let mut buf = vec![];
+                    {
+                        let mut w = &mut buf;
+                        let x = k;
+                        encode_key(&mut w, 1, #key_wire_type)?;
+                        #encode_key_value
+                        let x = v;
+                        encode_key(&mut w, 2, #value_wire_type)?;
+                        #encode_value
+                    }
+                    encode_key(&mut w, #number, 2)?;
+                    buf.encode(&mut w)?;
+                }
+            };
+            let decode_key_value = key.decode();
+            let decode_value = value.decode();
+            decode_init_fields = quote! {
+                #decode_init_fields
+                let mut #field_name = <#field_type>::new();
+            };
+            decode_match = quote! {
+                #decode_match
+                #number => if __wire_type == 2 {
+                    let buf = <Vec<u8>>::decode_as_field(&mut r)?;
+                    let mut r = buf.as_slice();
+                    let mut key = None;
+                    let mut value = None;
+                    while let Some((__number, __wire_type)) = decode_key(&mut r)? {
+                        match __number {
+                            1 if __wire_type == #key_wire_type => {
+                                key = Some(#decode_key_value)
+                            }
+                            2 if __wire_type == #value_wire_type => {
+                                value = Some(#decode_value)
+                            }
+                            _ => skip_field(&mut r, __wire_type)?,
+                        }
+                    }
+                    #field_name.insert(
+                        key.unwrap_or_default(),
+                        value.unwrap_or_default(),
+                    );
+                    __limits.check_repeated(#field_name.len())?;
+                } else {
+                    #wrong_wire_type
+                }
+            };
+            decode_fields = quote! { #decode_fields #field_name, };
+            continue;
+        }
+
+        let wire_type = value.wire_type;
+        let (value_size, encode_value) = value.size_and_encode();
+        let wrapped = value.wrap(quote! { *x });
+        let is_packed =
+            presence == Presence::Repeated && wire_type != 2 && field.packed;
+        let (size, encode) = match presence {
+            Presence::Required => (
+                quote! {
+                    let x = &self.#field_name;
+                    size += key_size(#number) + #value_size;
+                },
+                quote! {
+                    let x = &self.#field_name;
+                    encode_key(&mut w, #number, #wire_type)?;
+                    #encode_value
+                },
+            ),
+            Presence::Defaulted => (
+                quote! {
+                    let x = &self.#field_name;
+                    if *x != <#field_type>::default() {
+                        size += key_size(#number) + #value_size;
+                    }
+                },
+                quote! {
+                    let x = &self.#field_name;
+                    if *x != <#field_type>::default() {
+                        encode_key(&mut w, #number, #wire_type)?;
+                        #encode_value
+                    }
+                },
+            ),
+            Presence::Optional => (
+                quote! {
+                    if let Some(x) = &self.#field_name {
+                        size += key_size(#number) + #value_size;
+                    }
+                },
+                quote! {
+                    if let Some(x) = &self.#field_name {
+                        encode_key(&mut w, #number, #wire_type)?;
+                        #encode_value
+                    }
+                },
+            ),
+            Presence::Repeated if is_packed => (
+                quote! {
+                    if !self.#field_name.is_empty() {
+                        let len: u32 = self.#field_name
+                            .iter()
+                            .map(|x| #value_size)
+                            .sum();
+                        size += key_size(#number) + Varint(len).size() + len;
+                    }
+                },
+                quote! {
+                    if !self.#field_name.is_empty() {
+                        let mut buf = vec![];
+                        for x in &self.#field_name {
+                            (#wrapped).encode(&mut buf)?;
+                        }
+                        encode_key(&mut w, #number, 2)?;
+                        buf.encode(&mut w)?;
+                    }
+                },
+            ),
+            Presence::Repeated | Presence::Map => (
+                quote! {
+                    for x in &self.#field_name {
+                        size += key_size(#number) + #value_size;
+                    }
+                },
+                quote! {
+                    for x in &self.#field_name {
+                        encode_key(&mut w, #number, #wire_type)?;
+                        #encode_value
+                    }
+                },
+            ),
+        };
+        sizes = quote! { #sizes #size };
+        encoding_code = quote! { #encoding_code #encode };
+
+        let field_init = match presence {
+            Presence::Repeated => quote! { let mut #field_name = vec![]; },
+            Presence::Defaulted => {
+                quote! { let mut #field_name = Default::default(); }
+            }
+            _ => quote! { let mut #field_name = None; },
+        };
+        decode_init_fields = quote! { #decode_init_fields #field_init };
+
+        let field_assignment = match presence {
+            Presence::Required => quote! {
+                #field_name: #field_name.ok_or(EncodingError::MissingField(
+                    stringify!(#struct_name.#field_name)))?,
+            },
+            _ => quote! { #field_name: #field_name, },
+        };
+        decode_fields = quote! { #decode_fields #field_assignment };
+
+        let decode_value = value.decode();
+        let field_match = match presence {
+            Presence::Repeated if wire_type != 2 => {
+                let encoding_type = value.encoding_type();
+                let decode_packed = if value.wrapper.is_some() {
+                    quote! {
+                        decode_packed::<_, #encoding_type>(&mut r)?
+                            .into_iter()
+                            .map(|x| x.0)
+                    }
+                } else {
+                    quote! { decode_packed::<_, #encoding_type>(&mut r)? }
+                };
+                quote! {
+                    #number => if __wire_type == #wire_type {
+                        #field_name.push(#decode_value);
+                        __limits.check_repeated(#field_name.len())?;
+                    } else if __wire_type == 2 {
+                        #field_name.extend(#decode_packed);
+                        __limits.check_repeated(#field_name.len())?;
+                    } else {
+                        #wrong_wire_type
+                    }
+                }
+            }
+            Presence::Repeated => quote! {
+                #number => if __wire_type == #wire_type {
+                    #field_name.push(#decode_value);
+                    __limits.check_repeated(#field_name.len())?;
+                } else {
+                    #wrong_wire_type
+                }
+            },
+            Presence::Defaulted => quote! {
+                #number => if __wire_type == #wire_type {
+                    #field_name = #decode_value;
+                } else {
+                    #wrong_wire_type
+                }
+            },
+            _ => quote! {
+                #number => if __wire_type == #wire_type {
+                    #field_name = Some(#decode_value);
+                } else {
+                    #wrong_wire_type
+                }
+            },
+        };
+        decode_match = quote! { #decode_match #field_match };
+    }
+
+    MessageCode {
+        size: quote! {
+            let mut size = 0;
+            #sizes
+            size
+        },
+        encode: quote! {
+            #encoding_code
+            Ok(())
+        },
+        decode: quote! {
+            let _depth = DepthGuard::enter()?;
+            let __limits = DecodeLimits::current();
+            #decode_init_fields
+            while let Some((__number, __wire_type)) = decode_key(&mut r)? {
+                match __number {
+                    #decode_match
+                    _ => skip_field(&mut r, __wire_type)?,
+                }
+            }
+            Ok(Self {
+                #decode_fields
+            })
+        },
+    }
+}
@@ -0,0 +1,364 @@
+use std::{
+    collections::BTreeMap,
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::codegen;
+
+/// How singular (non-repeated) fields are represented
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FieldStyle {
+    /// Every singular field is wrapped in `Option` (default)
+    Option,
+    /// Singular scalar, string, bytes and enum fields of proto3 files are
+    /// plain values, set to their type's default when missing on the wire,
+    /// and not encoded when equal to it (implicit presence).
+    /// Message fields and fields marked `optional` stay `Option`,
+    /// and proto2 files are not affected.
+    Default,
+}
+
+/// Rust type used for `bytes` fields
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BytesType {
+    /// `Vec<u8>` (default)
+    Vec,
+    /// `bytes::Bytes`, requires `bytes` feature
+    Bytes,
+}
+
+/// Rust type used for `map<K, V>` fields
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MapType {
+    /// `std::collections::HashMap` (default)
+    HashMap,
+    /// `std::collections::BTreeMap`
+    BTreeMap,
+}
+
+/// Where generated code is written to
+#[derive(Clone, PartialEq, Debug)]
+pub enum Layout {
+    /// One `<file stem>.rs` per .proto file (default), without modules
+    /// for packages, so types can only use types of their own package
+    /// and well-known types. File stems have to be unique.
+    PerFile,
+    /// All .proto files in one file,
+    /// wrapped in modules named after their packages
+    SingleFile(String),
+    /// One file per package in a directory tree,
+    /// with `mod.rs` files declaring child modules.
+    /// Child modules have `#[path]` attributes with absolute paths,
+    /// so the root `mod.rs` can be used with `include!`,
+    /// but the tree can't be moved after generating it.
+    ModuleTree,
+}
+
+/// Codegen configuration
+///
+/// ```no_run
+/// protobuf_but_worse::Config::new()
+///     .serde(true)
+///     .type_attribute(".", "#[derive(Hash)]")
+///     .compile(&["foo.proto"])
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub(crate) out_dir: Option<PathBuf>,
+    pub(crate) type_attributes: Vec<(String, String)>,
+    pub(crate) field_attributes: Vec<(String, String)>,
+    pub(crate) serde: bool,
+    pub(crate) field_style: FieldStyle,
+    pub(crate) bytes: BytesType,
+    pub(crate) map_type: MapType,
+    pub(crate) layout: Layout,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            out_dir: None,
+            type_attributes: vec![],
+            field_attributes: vec![],
+            serde: false,
+            field_style: FieldStyle::Option,
+            bytes: BytesType::Vec,
+            map_type: MapType::HashMap,
+            layout: Layout::PerFile,
+        }
+    }
+}
+
+impl Config {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Directory to write generated code to. Defaults to `OUT_DIR` env var.
+    pub fn out_dir(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.out_dir = Some(path.into());
+        self
+    }
+
+    /// Adds attribute to every message and enum matching `path`.
+    ///
+    /// `path` is either `.` for all types, fully qualified name with
+    /// leading dot (`.package.Message`, also matches everything inside),
+    /// or a name suffix (`Message`, `Outer.Inner`).
+    pub fn type_attribute(
+        &mut self,
+        path: impl Into<String>,
+        attribute: impl Into<String>,
+    ) -> &mut Self {
+        self.type_attributes.push((path.into(), attribute.into()));
+        self
+    }
+
+    /// Adds attribute to every field matching `path` (`Message.field`),
+    /// with same matching rules as [`Config::type_attribute`]
+    pub fn field_attribute(
+        &mut self,
+        path: impl Into<String>,
+        attribute: impl Into<String>,
+    ) -> &mut Self {
+        self.field_attributes.push((path.into(), attribute.into()));
+        self
+    }
+
+    /// Shorthand for adding `#[derive(...)]` with `type_attribute`
+    pub fn derive(
+        &mut self,
+        path: impl Into<String>,
+        derive: &str,
+    ) -> &mut Self {
+        self.type_attribute(path, format!("#[derive({})]", derive))
+    }
+
+    /// Derives serde's `Serialize` and `Deserialize` for all types.
+    /// Generated code then needs `serde` with `derive` feature.
+    pub fn serde(&mut self, enabled: bool) -> &mut Self {
+        self.serde = enabled;
+        self
+    }
+
+    pub fn field_style(&mut self, style: FieldStyle) -> &mut Self {
+        self.field_style = style;
+        self
+    }
+
+    pub fn bytes(&mut self, bytes: BytesType) -> &mut Self {
+        self.bytes = bytes;
+        self
+    }
+
+    pub fn map_type(&mut self, map_type: MapType) -> &mut Self {
+        self.map_type = map_type;
+        self
+    }
+
+    pub fn layout(&mut self, layout: Layout) -> &mut Self {
+        self.layout = layout;
+        self
+    }
+
+    pub(crate) fn type_attributes_for(&self, name: &str) -> Vec<&str> {
+        matching(&self.type_attributes, name)
+    }
+
+    pub(crate) fn field_attributes_for(&self, name: &str) -> Vec<&str> {
+        matching(&self.field_attributes, name)
+    }
+
+    /// Generates code for all `protos` and writes it out according to
+    /// the layout
+    pub fn compile(&self, protos: &[impl AsRef<Path>]) -> io::Result<()> {
+        let out_dir = match &self.out_dir {
+            Some(dir) => dir.clone(),
+            None => std::env::var_os("OUT_DIR").map(PathBuf::from).ok_or_else(
+                || io::Error::other("Missing \"OUT_DIR\" env var"),
+            )?,
+        };
+
+        let invalid = |proto_file: &Path, e: syn::Error| {
+            let msg = format!(
+                "Error when generating code for {}: {}",
+                proto_file.display(),
+                e
+            );
+            io::Error::new(io::ErrorKind::InvalidInput, msg)
+        };
+
+        // All files are parsed first, so they can refer to each other's types
+        let mut files = vec![];
+        let mut symbols = codegen::SymbolTable::default();
+        // Output file name -> .proto file generated to it
+        let mut outputs = BTreeMap::new();
+        for proto_file in protos {
+            let proto_file = proto_file.as_ref();
+            if self.layout == Layout::PerFile {
+                let rs_file = proto_file.with_extension("rs");
+                let rs_file = rs_file.file_name().unwrap_or_default();
+                let previous = outputs.insert(rs_file.to_owned(), proto_file);
+                if let Some(previous) = previous {
+                    let msg = format!(
+                        "Both {} and {} would be generated to {}",
+                        previous.display(),
+                        proto_file.display(),
+                        Path::new(rs_file).display()
+                    );
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        msg,
+                    ));
+                }
+            }
+            let proto = crate::parse(proto_file)?;
+            symbols
+                .add_file(&proto)
+                .map_err(|e| invalid(proto_file, e))?;
+            files.push((proto_file, proto));
+        }
+
+        // Package name -> generated items
+        let mut packages: BTreeMap<String, String> = BTreeMap::new();
+        for (proto_file, proto) in files {
+            let items = codegen::gen_items(&proto, &symbols, self)
+                .map_err(|e| invalid(proto_file, e))?;
+            match &self.layout {
+                Layout::PerFile => {
+                    let code = codegen::gen_imports(self) + &items;
+                    let rs_file = proto_file.with_extension("rs");
+                    let rs_file = rs_file.file_name().unwrap_or_default();
+                    std::fs::write(out_dir.join(rs_file), code)?;
+                }
+                _ => {
+                    *packages
+                        .entry(proto.descriptor.package.clone())
+                        .or_default() += &items
+                }
+            }
+        }
+
+        match &self.layout {
+            Layout::PerFile => (),
+            Layout::SingleFile(name) => {
+                let mut root = Module::default();
+                for (package, items) in packages {
+                    root.insert(&package, items);
+                }
+                std::fs::write(out_dir.join(name), root.to_code(self))?;
+            }
+            Layout::ModuleTree => {
+                let mut root = Module::default();
+                for (package, items) in packages {
+                    root.insert(&package, items);
+                }
+                // `include!` resolves `mod` relative to including file
+                let out_dir = out_dir.canonicalize()?;
+                root.write_tree(self, &out_dir, "mod.rs")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn matching<'a>(
+    attributes: &'a [(String, String)],
+    name: &str,
+) -> Vec<&'a str> {
+    attributes
+        .iter()
+        .filter(|(path, _)| path_matches(path, name))
+        .map(|(_, attribute)| attribute.as_str())
+        .collect()
+}
+
+// `name` is fully qualified, with leading dot
+fn path_matches(path: &str, name: &str) -> bool {
+    if path == "." {
+        true
+    } else if path.starts_with('.') {
+        name == path || name.starts_with(&format!("{}.", path))
+    } else {
+        name.ends_with(&format!(".{}", path))
+    }
+}
+
+// Tree of modules built from package names
+#[derive(Default)]
+struct Module {
+    items: String,
+    children: BTreeMap<String, Module>,
+}
+
+impl Module {
+    fn insert(&mut self, package: &str, items: String) {
+        let mut module = self;
+        for segment in package.split('.').filter(|s| !s.is_empty()) {
+            module = module.children.entry(segment.to_string()).or_default();
+        }
+        module.items += &items;
+    }
+
+    fn to_code(&self, config: &Config) -> String {
+        let mut code = String::new();
+        if !self.items.is_empty() {
+            code += &codegen::gen_imports(config);
+            code += &self.items;
+        }
+        for (name, child) in &self.children {
+            let name = codegen::module_name(name);
+            code +=
+                &format!("pub mod {} {{ {} }}", name, child.to_code(config));
+        }
+        code
+    }
+
+    fn write_tree(
+        &self,
+        config: &Config,
+        dir: &Path,
+        file_name: &str,
+    ) -> io::Result<()> {
+        let mut code = String::new();
+        if !self.items.is_empty() {
+            code += &codegen::gen_imports(config);
+            code += &self.items;
+        }
+        for (name, child) in &self.children {
+            let name = codegen::module_name(name);
+            let path = if child.children.is_empty() {
+                let file_name = format!("{}.rs", name);
+                child.write_tree(config, dir, &file_name)?;
+                dir.join(file_name)
+            } else {
+                let dir = dir.join(&name);
+                std::fs::create_dir_all(&dir)?;
+                child.write_tree(config, &dir, "mod.rs")?;
+                dir.join("mod.rs")
+            };
+            let path = path.to_string_lossy();
+            code += &format!("#[path = {:?}] pub mod {};", path, name);
+        }
+        std::fs::write(dir.join(file_name), code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths() {
+        assert!(path_matches(".", ".krpc.schema.Request"));
+        assert!(path_matches(".krpc", ".krpc.schema.Request"));
+        assert!(path_matches(".krpc.schema.Request", ".krpc.schema.Request"));
+        assert!(!path_matches(".krpc.sch", ".krpc.schema.Request"));
+        assert!(path_matches("Request", ".krpc.schema.Request"));
+        assert!(path_matches("schema.Request", ".krpc.schema.Request"));
+        assert!(!path_matches("quest", ".krpc.schema.Request"));
+        assert!(path_matches("Type.code", ".krpc.schema.Type.code"));
+    }
+}
@@ -0,0 +1,423 @@
+// Runtime description of parsed .proto files, used by dynamic decoding
+// All type names are fully qualified, with leading dot (`.package.Message`)
+
+use std::collections::{HashMap, HashSet};
+
+use protobuf_parser::{Enumeration, Field, FieldType, Message, Rule};
+
+use crate::ProtoFile;
+
+#[derive(Debug, thiserror::Error)]
+pub enum DescriptorError {
+    #[error("Unknown type {0} of field {1}")]
+    UnknownType(String, String),
+    #[error("Groups are not supported, in field {0}")]
+    Group(String),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Label {
+    Optional,
+    Required,
+    Repeated,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Kind {
+    Double,
+    Float,
+    Int32,
+    Int64,
+    Uint32,
+    Uint64,
+    Sint32,
+    Sint64,
+    Fixed32,
+    Fixed64,
+    Sfixed32,
+    Sfixed64,
+    Bool,
+    String,
+    Bytes,
+    /// Fully qualified message name
+    Message(String),
+    /// Fully qualified enum name
+    Enum(String),
+    /// Key and value kinds
+    Map(Box<Kind>, Box<Kind>),
+}
+
+impl Kind {
+    pub fn wire_type(&self) -> u8 {
+        match self {
+            Kind::Int32
+            | Kind::Int64
+            | Kind::Uint32
+            | Kind::Uint64
+            | Kind::Sint32
+            | Kind::Sint64
+            | Kind::Bool
+            | Kind::Enum(_) => 0,
+            Kind::Fixed64 | Kind::Sfixed64 | Kind::Double => 1,
+            Kind::String | Kind::Bytes | Kind::Message(_) | Kind::Map(..) => 2,
+            Kind::Fixed32 | Kind::Sfixed32 | Kind::Float => 5,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct FieldDesc {
+    pub name: String,
+    pub number: u32,
+    pub label: Label,
+    pub kind: Kind,
+    /// Explicit `[packed = ...]` option
+    pub packed: Option<bool>,
+}
+
+impl FieldDesc {
+    /// Is field encoded as packed, when repeated
+    pub fn is_packed(&self, syntax: protobuf_parser::Syntax) -> bool {
+        self.label == Label::Repeated
+            && self.kind.wire_type() != 2
+            && self
+                .packed
+                .unwrap_or(matches!(syntax, protobuf_parser::Syntax::Proto3))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MessageDesc {
+    /// Fully qualified name
+    pub name: String,
+    pub syntax: protobuf_parser::Syntax,
+    /// Includes fields of oneofs
+    pub fields: Vec<FieldDesc>,
+}
+
+impl MessageDesc {
+    pub fn field(&self, number: u32) -> Option<&FieldDesc> {
+        self.fields.iter().find(|f| f.number == number)
+    }
+
+    pub fn field_by_name(&self, name: &str) -> Option<&FieldDesc> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct EnumDesc {
+    /// Fully qualified name
+    pub name: String,
+    pub values: Vec<(String, i32)>,
+}
+
+impl EnumDesc {
+    pub fn name_of(&self, number: i32) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(_, x)| *x == number)
+            .map(|(name, _)| name.as_str())
+    }
+
+    pub fn number_of(&self, name: &str) -> Option<i32> {
+        self.values.iter().find(|(x, _)| x == name).map(|&(_, x)| x)
+    }
+}
+
+/// Messages and enums of one or more .proto files
+#[derive(Clone, Default, Debug)]
+pub struct Pool {
+    messages: HashMap<String, MessageDesc>,
+    enums: HashMap<String, EnumDesc>,
+    /// Packages and their parents, fully qualified
+    packages: HashSet<String>,
+}
+
+impl Pool {
+    /// Pool with well-known types (`google.protobuf.Timestamp`...) only.
+    /// [`Pool::default`] is completely empty.
+    pub fn new() -> Self {
+        let mut pool = Self::default();
+        let file = crate::parse_str(crate::well_known::SOURCE)
+            .expect("Well-known types should parse");
+        pool.add_file(&file)
+            .expect("Well-known types should resolve");
+        pool
+    }
+
+    /// Parses .proto source and builds pool from it
+    pub fn from_source(source: &str) -> std::io::Result<Self> {
+        let mut pool = Self::new();
+        pool.add_file(&crate::parse_str(source)?).map_err(|e| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, e)
+        })?;
+        Ok(pool)
+    }
+
+    /// Adds all types of a file. Field types are resolved against
+    /// this file and previously added ones.
+    pub fn add_file(
+        &mut self,
+        file: &ProtoFile,
+    ) -> Result<(), DescriptorError> {
+        let proto = &file.descriptor;
+        let scope = if proto.package.is_empty() {
+            String::new()
+        } else {
+            format!(".{}", proto.package)
+        };
+        self.packages.extend(package_prefixes(&scope));
+
+        // Collect names first, as fields can refer to types declared later
+        let mut names = Names::default();
+        names.collect(&scope, &proto.messages, &proto.enums);
+        for (name, e) in &names.enums {
+            let values = e
+                .values
+                .iter()
+                .map(|v| (v.name.clone(), v.number))
+                .collect();
+            let name = name.clone();
+            self.enums.insert(name.clone(), EnumDesc { name, values });
+        }
+        let mut messages = vec![];
+        for (name, message) in &names.messages {
+            let fields = message
+                .fields
+                .iter()
+                .chain(message.oneofs.iter().flat_map(|o| &o.fields))
+                .map(|field| self.field(&names, name, field))
+                .collect::<Result<_, _>>()?;
+            messages.push(MessageDesc {
+                name: name.clone(),
+                syntax: proto.syntax,
+                fields,
+            });
+        }
+        for message in messages {
+            self.messages.insert(message.name.clone(), message);
+        }
+        Ok(())
+    }
+
+    /// Looks up message by fully qualified name, leading dot is optional
+    pub fn message(&self, name: &str) -> Option<&MessageDesc> {
+        self.messages.get(&qualify(name))
+    }
+
+    /// Looks up enum by fully qualified name, leading dot is optional
+    pub fn enumeration(&self, name: &str) -> Option<&EnumDesc> {
+        self.enums.get(&qualify(name))
+    }
+
+    pub fn messages(&self) -> impl Iterator<Item = &MessageDesc> {
+        self.messages.values()
+    }
+
+    fn field(
+        &self,
+        names: &Names,
+        message: &str,
+        field: &Field,
+    ) -> Result<FieldDesc, DescriptorError> {
+        let full_name = format!("{}.{}", message, field.name);
+        let label = match field.rule {
+            Rule::Optional => Label::Optional,
+            Rule::Required => Label::Required,
+            Rule::Repeated => Label::Repeated,
+        };
+        Ok(FieldDesc {
+            name: field.name.clone(),
+            number: field.number as u32,
+            label,
+            kind: self.kind(names, message, &full_name, &field.typ)?,
+            packed: field.packed,
+        })
+    }
+
+    fn kind(
+        &self,
+        names: &Names,
+        message: &str,
+        full_name: &str,
+        typ: &FieldType,
+    ) -> Result<Kind, DescriptorError> {
+        Ok(match typ {
+            FieldType::Int32 => Kind::Int32,
+            FieldType::Int64 => Kind::Int64,
+            FieldType::Uint32 => Kind::Uint32,
+            FieldType::Uint64 => Kind::Uint64,
+            FieldType::Sint32 => Kind::Sint32,
+            FieldType::Sint64 => Kind::Sint64,
+            FieldType::Bool => Kind::Bool,
+            FieldType::Fixed64 => Kind::Fixed64,
+            FieldType::Sfixed64 => Kind::Sfixed64,
+            FieldType::Double => Kind::Double,
+            FieldType::String => Kind::String,
+            FieldType::Bytes => Kind::Bytes,
+            FieldType::Fixed32 => Kind::Fixed32,
+            FieldType::Sfixed32 => Kind::Sfixed32,
+            FieldType::Float => Kind::Float,
+            FieldType::MessageOrEnum(name) => {
+                let is_known = |x: &str| {
+                    if names.messages.contains_key(x)
+                        || self.messages.contains_key(x)
+                    {
+                        Some(Kind::Message(x.to_string()))
+                    } else if names.enums.contains_key(x)
+                        || self.enums.contains_key(x)
+                    {
+                        Some(Kind::Enum(x.to_string()))
+                    } else {
+                        None
+                    }
+                };
+                let is_package = |x: &str| self.packages.contains(x);
+                resolve(message, name, is_package, is_known).ok_or_else(
+                    || {
+                        DescriptorError::UnknownType(
+                            name.clone(),
+                            full_name.to_string(),
+                        )
+                    },
+                )?
+            }
+            FieldType::Map(map) => {
+                let (key, value) = &**map;
+                Kind::Map(
+                    Box::new(self.kind(names, message, full_name, key)?),
+                    Box::new(self.kind(names, message, full_name, value)?),
+                )
+            }
+            FieldType::Group(_) => {
+                return Err(DescriptorError::Group(full_name.to_string()))
+            }
+        })
+    }
+}
+
+// Types declared in a file, by fully qualified name
+#[derive(Default)]
+struct Names<'a> {
+    messages: HashMap<String, &'a Message>,
+    enums: HashMap<String, &'a Enumeration>,
+}
+
+impl<'a> Names<'a> {
+    fn collect(
+        &mut self,
+        scope: &str,
+        messages: &'a [Message],
+        enums: &'a [Enumeration],
+    ) {
+        for e in enums {
+            self.enums.insert(format!("{}.{}", scope, e.name), e);
+        }
+        for message in messages {
+            let name = format!("{}.{}", scope, message.name);
+            self.collect(&name, &message.messages, &message.enums);
+            self.messages.insert(name, message);
+        }
+    }
+}
+
+/// `.a.b.c` -> `.a`, `.a.b`, `.a.b.c`
+pub(crate) fn package_prefixes(
+    package: &str,
+) -> impl Iterator<Item = String> + '_ {
+    package
+        .match_indices('.')
+        .skip(1)
+        .map(move |(i, _)| package[..i].to_string())
+        .chain((!package.is_empty()).then(|| package.to_string()))
+}
+
+fn qualify(name: &str) -> String {
+    if name.starts_with('.') {
+        name.to_string()
+    } else {
+        format!(".{}", name)
+    }
+}
+
+/// Resolves type `name` referenced from inside of `scope`
+/// (fully qualified), with protobuf's scoping rules: innermost scope first,
+/// and only the first component of dotted name (type or package)
+/// is searched for.
+pub(crate) fn resolve<T>(
+    scope: &str,
+    name: &str,
+    is_package: impl Fn(&str) -> bool,
+    lookup: impl Fn(&str) -> Option<T>,
+) -> Option<T> {
+    if name.starts_with('.') {
+        return lookup(name);
+    }
+    let first = name.split('.').next().unwrap_or(name);
+    let mut scope = scope;
+    loop {
+        let candidate = format!("{}.{}", scope, first);
+        if lookup(&candidate).is_some() || is_package(&candidate) {
+            let rest = &name[first.len()..];
+            return lookup(&format!("{}{}", candidate, rest));
+        }
+        match scope.rfind('.') {
+            Some(i) => scope = &scope[..i],
+            None => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolution() {
+        let source = r#"
+            syntax = "proto3";
+            package a.b;
+            message Outer {
+                message Inner {
+                    Kind kind = 1;
+                }
+                enum Kind { A = 0; }
+                Inner inner = 1;
+                Outer.Inner other = 2;
+                .a.b.Top top = 3;
+                map<string, Top> tops = 4;
+            }
+            message Top {}
+        "#;
+        let pool = Pool::from_source(source).unwrap();
+        let inner = pool.message("a.b.Outer.Inner").unwrap();
+        assert_eq!(inner.fields[0].kind, Kind::Enum(".a.b.Outer.Kind".into()));
+        let outer = pool.message(".a.b.Outer").unwrap();
+        let kinds: Vec<_> = outer.fields.iter().map(|f| &f.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                &Kind::Message(".a.b.Outer.Inner".into()),
+                &Kind::Message(".a.b.Outer.Inner".into()),
+                &Kind::Message(".a.b.Top".into()),
+                &Kind::Map(
+                    Box::new(Kind::String),
+                    Box::new(Kind::Message(".a.b.Top".into()))
+                ),
+            ]
+        );
+
+        let source = "syntax = \"proto3\"; message A { B b = 1; }";
+        assert!(Pool::from_source(source).is_err());
+
+        let mut pool = Pool::new();
+        let source = "syntax = \"proto3\"; package a.c; message C {}";
+        pool.add_file(&crate::parse_str(source).unwrap()).unwrap();
+        let source =
+            "syntax = \"proto3\"; package a.b; message B { c.C c = 1; }";
+        pool.add_file(&crate::parse_str(source).unwrap()).unwrap();
+        let b = pool.message("a.b.B").unwrap();
+        assert_eq!(b.fields[0].kind, Kind::Message(".a.c.C".into()));
+    }
+}
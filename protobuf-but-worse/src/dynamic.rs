@@ -0,0 +1,670 @@
+// Decoding without generated types: schema-driven into `Value` tree
+// with help of `descriptor::Pool`, or schema-less into raw fields
+
+use std::{fmt, io::Read};
+
+use crate::{
+    descriptor::{Kind, Label, MessageDesc, Pool},
+    encoding::*,
+};
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Value {
+    Bool(bool),
+    I32(i32),
+    I64(i64),
+    U32(u32),
+    U64(u64),
+    F32(f32),
+    F64(f64),
+    String(String),
+    Bytes(Vec<u8>),
+    /// Enum value, with name if it's known
+    Enum(i32, Option<String>),
+    Message(MessageValue),
+    /// Repeated field
+    List(Vec<Value>),
+    /// Map field, in wire order
+    Map(Vec<(Value, Value)>),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct MessageValue {
+    /// Fully qualified type name
+    pub type_name: String,
+    /// Fields present on the wire, in order of field numbers
+    pub fields: Vec<FieldValue>,
+    /// Fields not in descriptor, or with unexpected wire type
+    pub unknown: Vec<RawField>,
+}
+
+impl MessageValue {
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.fields
+            .iter()
+            .find(|f| f.name == name)
+            .map(|f| &f.value)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct FieldValue {
+    pub number: u32,
+    pub name: String,
+    pub value: Value,
+}
+
+/// Field decoded without schema
+#[derive(Clone, PartialEq, Debug)]
+pub struct RawField {
+    pub number: u32,
+    pub value: RawValue,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum RawValue {
+    /// Wire type 0
+    Varint(u64),
+    /// Wire type 1
+    Fixed64(u64),
+    /// Wire type 2, either string, bytes, message or packed values
+    LengthDelimited(Vec<u8>),
+    /// Wire types 3 and 4
+    Group(Vec<RawField>),
+    /// Wire type 5
+    Fixed32(u32),
+}
+
+impl RawValue {
+    pub fn wire_type(&self) -> u8 {
+        match self {
+            RawValue::Varint(_) => 0,
+            RawValue::Fixed64(_) => 1,
+            RawValue::LengthDelimited(_) => 2,
+            RawValue::Group(_) => 3,
+            RawValue::Fixed32(_) => 5,
+        }
+    }
+}
+
+impl Pool {
+    /// Decodes message body of type `message_name` (fully qualified)
+    pub fn decode(
+        &self,
+        message_name: &str,
+        bytes: &[u8],
+    ) -> Result<MessageValue, EncodingError> {
+        let desc = self.message(message_name).ok_or_else(|| {
+            EncodingError::UnknownMessage(message_name.to_string())
+        })?;
+        decode_message(self, desc, bytes)
+    }
+}
+
+fn decode_message(
+    pool: &Pool,
+    desc: &MessageDesc,
+    mut r: &[u8],
+) -> Result<MessageValue, EncodingError> {
+    let _depth = DepthGuard::enter()?;
+    let limits = DecodeLimits::current();
+    let mut fields: Vec<FieldValue> = vec![];
+    let mut unknown = vec![];
+    while let Some((number, wire_type)) = decode_key(&mut r)? {
+        let field = match desc.field(number) {
+            Some(field) => field,
+            None => {
+                let value = decode_raw_value(&mut r, wire_type)?;
+                unknown.push(RawField { number, value });
+                continue;
+            }
+        };
+        let is_packed = field.label == Label::Repeated
+            && field.kind.wire_type() != 2
+            && wire_type == 2;
+        if wire_type != field.kind.wire_type() && !is_packed {
+            let value = decode_raw_value(&mut r, wire_type)?;
+            unknown.push(RawField { number, value });
+            continue;
+        }
+        let existing = fields.iter_mut().find(|f| f.number == number);
+        if let Kind::Map(key_kind, value_kind) = &field.kind {
+            let entry = decode_entry(pool, key_kind, value_kind, &mut r)?;
+            match existing {
+                Some(FieldValue {
+                    value: Value::Map(map),
+                    ..
+                }) => {
+                    map.push(entry);
+                    limits.check_repeated(map.len())?;
+                }
+                _ => fields.push(FieldValue {
+                    number,
+                    name: field.name.clone(),
+                    value: Value::Map(vec![entry]),
+                }),
+            }
+            continue;
+        }
+
+        let mut values = vec![];
+        if is_packed {
+            let buf = <Vec<u8>>::decode_as_field(&mut r)?;
+            let mut buf = buf.as_slice();
+            while !buf.is_empty() {
+                values.push(decode_value(pool, &field.kind, &mut buf)?);
+                limits.check_repeated(values.len())?;
+            }
+        } else {
+            values.push(decode_value(pool, &field.kind, &mut r)?);
+        }
+        match existing {
+            Some(FieldValue {
+                value: Value::List(list),
+                ..
+            }) => {
+                list.extend(values);
+                limits.check_repeated(list.len())?;
+            }
+            // Last one wins for singular fields
+            Some(existing) => {
+                if let Some(value) = values.pop() {
+                    existing.value = value;
+                }
+            }
+            None if field.label == Label::Repeated => fields.push(FieldValue {
+                number,
+                name: field.name.clone(),
+                value: Value::List(values),
+            }),
+            None => {
+                if let Some(value) = values.pop() {
+                    fields.push(FieldValue {
+                        number,
+                        name: field.name.clone(),
+                        value,
+                    })
+                }
+            }
+        }
+    }
+    fields.sort_by_key(|f| f.number);
+    Ok(MessageValue {
+        type_name: desc.name.clone(),
+        fields,
+        unknown,
+    })
+}
+
+fn decode_entry(
+    pool: &Pool,
+    key_kind: &Kind,
+    value_kind: &Kind,
+    r: &mut &[u8],
+) -> Result<(Value, Value), EncodingError> {
+    let buf = <Vec<u8>>::decode_as_field(r)?;
+    let mut r = buf.as_slice();
+    let mut key = None;
+    let mut value = None;
+    while let Some((number, wire_type)) = decode_key(&mut r)? {
+        match number {
+            1 if wire_type == key_kind.wire_type() => {
+                key = Some(decode_value(pool, key_kind, &mut r)?)
+            }
+            2 if wire_type == value_kind.wire_type() => {
+                value = Some(decode_value(pool, value_kind, &mut r)?)
+            }
+            _ => skip_field(&mut r, wire_type)?,
+        }
+    }
+    let key = key.unwrap_or_else(|| default_value(pool, key_kind));
+    let value = value.unwrap_or_else(|| default_value(pool, value_kind));
+    Ok((key, value))
+}
+
+fn decode_value(
+    pool: &Pool,
+    kind: &Kind,
+    r: &mut &[u8],
+) -> Result<Value, EncodingError> {
+    let varint = |r: &mut &[u8]| <Varint<u64>>::decode(r).map(|x| x.0);
+    Ok(match kind {
+        Kind::Int32 => Value::I32(varint(r)? as i32),
+        Kind::Int64 => Value::I64(varint(r)? as i64),
+        Kind::Uint32 => Value::U32(varint(r)? as u32),
+        Kind::Uint64 => Value::U64(varint(r)?),
+        Kind::Sint32 => {
+            let x = varint(r)? as u32;
+            Value::I32((x >> 1) as i32 ^ -((x & 1) as i32))
+        }
+        Kind::Sint64 => Value::I64(<SVarint<i64>>::decode(r)?.0),
+        Kind::Bool => Value::Bool(bool::decode(r)?),
+        Kind::Enum(name) => {
+            let number = varint(r)? as i32;
+            let name = pool
+                .enumeration(name)
+                .and_then(|e| e.name_of(number))
+                .map(String::from);
+            Value::Enum(number, name)
+        }
+        Kind::Fixed32 => Value::U32(<Fixed<u32>>::decode(r)?.0),
+        Kind::Sfixed32 => Value::I32(<Fixed<i32>>::decode(r)?.0),
+        Kind::Float => Value::F32(f32::decode(r)?),
+        Kind::Fixed64 => Value::U64(<Fixed<u64>>::decode(r)?.0),
+        Kind::Sfixed64 => Value::I64(<Fixed<i64>>::decode(r)?.0),
+        Kind::Double => Value::F64(f64::decode(r)?),
+        Kind::String => Value::String(String::decode_as_field(r)?),
+        Kind::Bytes => Value::Bytes(<Vec<u8>>::decode_as_field(r)?),
+        Kind::Message(name) => {
+            let buf = <Vec<u8>>::decode_as_field(r)?;
+            let desc = pool
+                .message(name)
+                .ok_or_else(|| EncodingError::UnknownMessage(name.clone()))?;
+            Value::Message(decode_message(pool, desc, &buf)?)
+        }
+        Kind::Map(key_kind, value_kind) => {
+            Value::Map(vec![decode_entry(pool, key_kind, value_kind, r)?])
+        }
+    })
+}
+
+impl Pool {
+    /// Encodes message body, inverse of [`Pool::decode`]
+    pub fn encode(
+        &self,
+        value: &MessageValue,
+    ) -> Result<Vec<u8>, EncodingError> {
+        let desc = self.message(&value.type_name).ok_or_else(|| {
+            EncodingError::UnknownMessage(value.type_name.clone())
+        })?;
+        let mut w = vec![];
+        encode_message(self, desc, value, &mut w)?;
+        Ok(w)
+    }
+}
+
+fn encode_message(
+    pool: &Pool,
+    desc: &MessageDesc,
+    value: &MessageValue,
+    w: &mut Vec<u8>,
+) -> Result<(), EncodingError> {
+    for field in &value.fields {
+        let name = format!("{}.{}", desc.name, field.name);
+        let mismatch = || EncodingError::TypeMismatch(name.clone());
+        let field_desc = desc.field(field.number).ok_or_else(mismatch)?;
+        let number = field.number;
+        let kind = &field_desc.kind;
+        match (kind, &field.value) {
+            (Kind::Map(key_kind, value_kind), Value::Map(entries)) => {
+                for (key, value) in entries {
+                    let mut entry = vec![];
+                    encode_key(&mut entry, 1, key_kind.wire_type())?;
+                    encode_value(pool, key_kind, key, &name, &mut entry)?;
+                    encode_key(&mut entry, 2, value_kind.wire_type())?;
+                    encode_value(pool, value_kind, value, &name, &mut entry)?;
+                    encode_key(&mut *w, number, 2)?;
+                    entry.encode(&mut *w)?;
+                }
+            }
+            (_, Value::List(values)) if field_desc.label == Label::Repeated => {
+                if field_desc.is_packed(desc.syntax) {
+                    let mut buf = vec![];
+                    for value in values {
+                        encode_value(pool, kind, value, &name, &mut buf)?;
+                    }
+                    if !buf.is_empty() {
+                        encode_key(&mut *w, number, 2)?;
+                        buf.encode(&mut *w)?;
+                    }
+                } else {
+                    for value in values {
+                        encode_key(&mut *w, number, kind.wire_type())?;
+                        encode_value(pool, kind, value, &name, w)?;
+                    }
+                }
+            }
+            (_, value) if field_desc.label != Label::Repeated => {
+                encode_key(&mut *w, number, kind.wire_type())?;
+                encode_value(pool, kind, value, &name, w)?;
+            }
+            _ => return Err(mismatch()),
+        }
+    }
+    for field in &value.unknown {
+        encode_raw(field, w)?;
+    }
+    Ok(())
+}
+
+// Encodes single value, without key
+fn encode_value(
+    pool: &Pool,
+    kind: &Kind,
+    value: &Value,
+    field_name: &str,
+    w: &mut Vec<u8>,
+) -> Result<(), EncodingError> {
+    match (kind, value) {
+        // Negative int32 is sign-extended to 64 bits
+        (Kind::Int32, Value::I32(x)) => Varint(*x as i64 as u64).encode(w),
+        (Kind::Int64, Value::I64(x)) => Varint(*x as u64).encode(w),
+        (Kind::Uint32, Value::U32(x)) => Varint(*x).encode(w),
+        (Kind::Uint64, Value::U64(x)) => Varint(*x).encode(w),
+        (Kind::Sint32, Value::I32(x)) => SVarint(*x).encode(w),
+        (Kind::Sint64, Value::I64(x)) => SVarint(*x).encode(w),
+        (Kind::Bool, Value::Bool(x)) => Varint(*x as u32).encode(w),
+        (Kind::Enum(_), Value::Enum(x, _)) => {
+            Varint(*x as i64 as u64).encode(w)
+        }
+        (Kind::Fixed32, Value::U32(x)) => Fixed(*x).encode(w),
+        (Kind::Sfixed32, Value::I32(x)) => Fixed(*x).encode(w),
+        (Kind::Float, Value::F32(x)) => x.encode(w),
+        (Kind::Fixed64, Value::U64(x)) => Fixed(*x).encode(w),
+        (Kind::Sfixed64, Value::I64(x)) => Fixed(*x).encode(w),
+        (Kind::Double, Value::F64(x)) => x.encode(w),
+        (Kind::String, Value::String(x)) => x.encode(w),
+        (Kind::Bytes, Value::Bytes(x)) => x.encode(w),
+        (Kind::Message(name), Value::Message(x)) => {
+            let desc = pool
+                .message(name)
+                .ok_or_else(|| EncodingError::UnknownMessage(name.clone()))?;
+            let mut buf = vec![];
+            encode_message(pool, desc, x, &mut buf)?;
+            buf.encode(w)
+        }
+        _ => Err(EncodingError::TypeMismatch(field_name.to_string())),
+    }
+}
+
+fn encode_raw(field: &RawField, w: &mut Vec<u8>) -> Result<(), EncodingError> {
+    encode_key(&mut *w, field.number, field.value.wire_type())?;
+    match &field.value {
+        RawValue::Varint(x) => Varint(*x).encode(w),
+        RawValue::Fixed64(x) => Fixed(*x).encode(w),
+        RawValue::LengthDelimited(x) => x.encode(w),
+        RawValue::Group(fields) => {
+            for field in fields {
+                encode_raw(field, w)?;
+            }
+            encode_key(w, field.number, 4)
+        }
+        RawValue::Fixed32(x) => Fixed(*x).encode(w),
+    }
+}
+
+/// Default value of a kind, as used for missing map keys and values
+pub fn default_value(pool: &Pool, kind: &Kind) -> Value {
+    match kind {
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => Value::I32(0),
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => Value::I64(0),
+        Kind::Uint32 | Kind::Fixed32 => Value::U32(0),
+        Kind::Uint64 | Kind::Fixed64 => Value::U64(0),
+        Kind::Float => Value::F32(0.0),
+        Kind::Double => Value::F64(0.0),
+        Kind::Bool => Value::Bool(false),
+        Kind::String => Value::String(String::new()),
+        Kind::Bytes => Value::Bytes(vec![]),
+        Kind::Enum(name) => {
+            let first = pool.enumeration(name).and_then(|e| e.values.first());
+            match first {
+                Some((name, number)) => {
+                    Value::Enum(*number, Some(name.clone()))
+                }
+                None => Value::Enum(0, None),
+            }
+        }
+        Kind::Message(name) => Value::Message(MessageValue {
+            type_name: name.clone(),
+            fields: vec![],
+            unknown: vec![],
+        }),
+        Kind::Map(..) => Value::Map(vec![]),
+    }
+}
+
+/// Decodes message body without schema, like `protoc --decode_raw`
+pub fn decode_raw(mut bytes: &[u8]) -> Result<Vec<RawField>, EncodingError> {
+    let _depth = DepthGuard::enter()?;
+    let mut fields = vec![];
+    while let Some((number, wire_type)) = decode_key(&mut bytes)? {
+        let value = decode_raw_value(&mut bytes, wire_type)?;
+        fields.push(RawField { number, value });
+    }
+    Ok(fields)
+}
+
+fn decode_raw_value(
+    r: &mut dyn Read,
+    wire_type: u8,
+) -> Result<RawValue, EncodingError> {
+    Ok(match wire_type {
+        0 => RawValue::Varint(<Varint<u64>>::decode(r)?.0),
+        1 => RawValue::Fixed64(<Fixed<u64>>::decode(r)?.0),
+        2 => RawValue::LengthDelimited(<Vec<u8>>::decode_as_field(r)?),
+        3 => {
+            let _depth = DepthGuard::enter()?;
+            let mut fields = vec![];
+            loop {
+                match decode_key(r)? {
+                    Some((_, 4)) => break,
+                    Some((number, wire_type)) => {
+                        let value = decode_raw_value(r, wire_type)?;
+                        fields.push(RawField { number, value });
+                    }
+                    None => {
+                        let eof = std::io::ErrorKind::UnexpectedEof.into();
+                        return Err(EncodingError::Io(eof));
+                    }
+                }
+            }
+            RawValue::Group(fields)
+        }
+        5 => RawValue::Fixed32(<Fixed<u32>>::decode(r)?.0),
+        e => return Err(EncodingError::InvalidWireType(e)),
+    })
+}
+
+/// Prints fields like `protoc --decode_raw`. Length-delimited values are
+/// shown as nested messages if they parse as one within
+/// [`DecodeLimits::max_depth`], otherwise as strings.
+pub struct RawDisplay<'a>(pub &'a [RawField]);
+
+impl fmt::Display for RawDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_raw(f, self.0, 0)
+    }
+}
+
+fn write_raw(
+    f: &mut fmt::Formatter<'_>,
+    fields: &[RawField],
+    indent: usize,
+) -> fmt::Result {
+    let pad = "  ".repeat(indent);
+    for field in fields {
+        match &field.value {
+            RawValue::Varint(x) => {
+                writeln!(f, "{}{}: {}", pad, field.number, x)?
+            }
+            RawValue::Fixed64(x) => {
+                writeln!(f, "{}{}: 0x{:016x}", pad, field.number, x)?
+            }
+            RawValue::Fixed32(x) => {
+                writeln!(f, "{}{}: 0x{:08x}", pad, field.number, x)?
+            }
+            RawValue::Group(fields) => {
+                writeln!(f, "{}{} {{", pad, field.number)?;
+                write_raw(f, fields, indent + 1)?;
+                writeln!(f, "{}}}", pad)?;
+            }
+            RawValue::LengthDelimited(bytes) => {
+                // Nesting counts towards depth limit, deeper payloads are
+                // printed as bytes
+                let depth = DepthGuard::enter();
+                let fields = match depth {
+                    Ok(_) => decode_raw(bytes).ok(),
+                    Err(_) => None,
+                };
+                match fields {
+                    Some(fields) if !fields.is_empty() => {
+                        writeln!(f, "{}{} {{", pad, field.number)?;
+                        write_raw(f, &fields, indent + 1)?;
+                        writeln!(f, "{}}}", pad)?;
+                    }
+                    _ => writeln!(
+                        f,
+                        "{}{}: \"{}\"",
+                        pad,
+                        field.number,
+                        escape_bytes(bytes)
+                    )?,
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Escapes bytes C-style, keeping printable ASCII and valid UTF-8 as is
+pub fn escape_bytes(bytes: &[u8]) -> String {
+    let mut res = String::new();
+    let octal = |res: &mut String, bytes: &[u8]| {
+        for byte in bytes {
+            res.push_str(&format!("\\{:03o}", byte));
+        }
+    };
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '"' => res += "\\\"",
+                '\'' => res += "\\'",
+                '\\' => res += "\\\\",
+                '\n' => res += "\\n",
+                '\r' => res += "\\r",
+                '\t' => res += "\\t",
+                c if c.is_control() => {
+                    octal(&mut res, c.encode_utf8(&mut [0; 4]).as_bytes())
+                }
+                c => res.push(c),
+            }
+        }
+        octal(&mut res, chunk.invalid());
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dynamic() {
+        let source = r#"
+            syntax = "proto3";
+            package test;
+            message Foo {
+                enum Kind { A = 0; B = 1; }
+                int32 id = 1;
+                repeated uint32 values = 2;
+                Kind kind = 3;
+                Foo next = 4;
+                map<string, sint32> counts = 5;
+            }
+        "#;
+        let pool = Pool::from_source(source).unwrap();
+
+        let mut bytes = vec![];
+        encode_field(&mut bytes, 1, 0, &Varint(-5i64)).unwrap();
+        encode_key(&mut bytes, 2, 2).unwrap();
+        vec![1u8, 2, 3].encode(&mut bytes).unwrap();
+        encode_field(&mut bytes, 3, 0, &Varint(1u32)).unwrap();
+        let mut next = vec![];
+        encode_field(&mut next, 3, 0, &Varint(7u32)).unwrap();
+        encode_key(&mut bytes, 4, 2).unwrap();
+        next.encode(&mut bytes).unwrap();
+        let mut entry = vec![];
+        encode_field(&mut entry, 1, 2, &"a").unwrap();
+        encode_field(&mut entry, 2, 0, &SVarint(-1i32)).unwrap();
+        encode_key(&mut bytes, 5, 2).unwrap();
+        entry.encode(&mut bytes).unwrap();
+        encode_field(&mut bytes, 9, 5, &Fixed(1u32)).unwrap();
+
+        let value = pool.decode("test.Foo", &bytes).unwrap();
+        assert_eq!(value.get("id"), Some(&Value::I32(-5)));
+        assert_eq!(
+            value.get("values"),
+            Some(&Value::List(vec![
+                Value::U32(1),
+                Value::U32(2),
+                Value::U32(3)
+            ]))
+        );
+        assert_eq!(value.get("kind"), Some(&Value::Enum(1, Some("B".into()))));
+        match value.get("next") {
+            Some(Value::Message(next)) => {
+                assert_eq!(next.type_name, ".test.Foo");
+                assert_eq!(next.get("kind"), Some(&Value::Enum(7, None)));
+            }
+            x => panic!("{:?}", x),
+        }
+        assert_eq!(
+            value.get("counts"),
+            Some(&Value::Map(vec![(
+                Value::String("a".into()),
+                Value::I32(-1)
+            )]))
+        );
+        assert_eq!(
+            value.unknown,
+            [RawField {
+                number: 9,
+                value: RawValue::Fixed32(1)
+            }]
+        );
+        assert!(pool.decode("test.Bar", &bytes).is_err());
+        let encoded = pool.encode(&value).unwrap();
+        assert_eq!(pool.decode("test.Foo", &encoded).unwrap(), value);
+
+        let raw = decode_raw(&bytes).unwrap();
+        assert_eq!(raw.len(), 6);
+        let text = RawDisplay(&raw).to_string();
+        assert!(text
+            .starts_with("1: 18446744073709551611\n2: \"\\001\\002\\003\"\n"));
+        assert!(text.contains("4 {\n  3: 7\n}\n"));
+
+        // Deep nesting stops at depth limit instead of overflowing stack
+        let mut bytes = vec![8, 1];
+        for _ in 0..10_000 {
+            let mut outer = vec![1 << 3 | 2];
+            Varint(bytes.len() as u32).encode(&mut outer).unwrap();
+            outer.extend(bytes);
+            bytes = outer;
+        }
+        let raw = decode_raw(&bytes).unwrap();
+        let text = RawDisplay(&raw).to_string();
+        let max_depth = DecodeLimits::default().max_depth as usize;
+        let nested = text.lines().filter(|x| x.ends_with(" {")).count();
+        // Parsing the innermost shown payload takes one more level
+        assert_eq!(nested, max_depth - 1);
+        assert!(!text.contains("1: 1\n"));
+    }
+
+    // Same bools are accepted as by generated code
+    #[test]
+    fn bools() {
+        let pool = Pool::from_source(
+            "syntax = \"proto3\"; message B { repeated bool on = 1; }",
+        )
+        .unwrap();
+        let value = pool.decode("B", &[8, 1, 10, 2, 0, 1]).unwrap();
+        let expected = [true, false, true].map(Value::Bool).to_vec();
+        assert_eq!(value.get("on"), Some(&Value::List(expected)));
+        for bytes in [&[8, 2][..], &[10, 1, 0x81], &[8, 0x81, 0]] {
+            assert!(matches!(
+                pool.decode("B", bytes),
+                Err(EncodingError::InvalidBoolValue(_))
+            ));
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    cell::Cell,
     io::{Read, Write},
     string::FromUtf8Error,
 };
@@ -15,12 +16,24 @@ pub enum EncodingError {
     VarintTooLong,
     #[error("Wrong wire type for {0}: {1}")]
     WrongWireType(&'static str, u8),
+    #[error("Invalid wire type: {0}")]
+    InvalidWireType(u8),
     #[error("Invalid enum value for {0}: {1}")]
     InvalidEnumValue(&'static str, u32),
     #[error("Invalid bool value: {0}")]
     InvalidBoolValue(u8),
-    #[error("Missing field: {0}")]
-    MissingField(u8),
+    #[error("Missing required field: {0}")]
+    MissingField(&'static str),
+    #[error("Message too large: {0} bytes, limit is {1}")]
+    MessageTooLarge(u32, u32),
+    #[error("Too many repeated elements: {0}, limit is {1}")]
+    TooManyElements(usize, u32),
+    #[error("Message nesting deeper than {0}")]
+    TooDeep(u32),
+    #[error("Unknown message type: {0}")]
+    UnknownMessage(String),
+    #[error("Value doesn't match type of field {0}")]
+    TypeMismatch(String),
     #[error("Error {0}: {1}")]
     Context(String, Box<dyn std::error::Error>),
 }
@@ -31,6 +44,95 @@ impl EncodingError {
     }
 }
 
+/// Limits checked while decoding untrusted input
+///
+/// Applied per thread, see [`DecodeLimits::scope`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DecodeLimits {
+    /// Max length of length-delimited message, string or bytes
+    pub max_message_size: u32,
+    /// Max number of elements in one repeated or map field
+    pub max_repeated: u32,
+    /// Max nesting depth of messages (and groups, when skipping them)
+    pub max_depth: u32,
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        Self {
+            max_message_size: 64 << 20,
+            max_repeated: 1 << 20,
+            max_depth: 100,
+        }
+    }
+}
+
+thread_local! {
+    static LIMITS: Cell<DecodeLimits> = Cell::new(DecodeLimits::default());
+    static DEPTH: Cell<u32> = const { Cell::new(0) };
+}
+
+impl DecodeLimits {
+    /// Limits currently applied on this thread
+    pub fn current() -> Self {
+        LIMITS.with(Cell::get)
+    }
+
+    /// Runs `f` with these limits applied on this thread
+    pub fn scope<T>(self, f: impl FnOnce() -> T) -> T {
+        // Restores previous limits even if `f` panics
+        struct Restore(DecodeLimits);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                LIMITS.with(|x| x.set(self.0));
+            }
+        }
+        let _restore = Restore(LIMITS.with(|x| x.replace(self)));
+        f()
+    }
+
+    pub fn check_size(&self, len: u32) -> Result<(), EncodingError> {
+        if len > self.max_message_size {
+            return Err(EncodingError::MessageTooLarge(
+                len,
+                self.max_message_size,
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn check_repeated(&self, count: usize) -> Result<(), EncodingError> {
+        if count > self.max_repeated as usize {
+            return Err(EncodingError::TooManyElements(
+                count,
+                self.max_repeated,
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Counts message nesting depth while alive, used by generated decoders
+pub struct DepthGuard(());
+
+impl DepthGuard {
+    pub fn enter() -> Result<Self, EncodingError> {
+        let max_depth = DecodeLimits::current().max_depth;
+        let depth = DEPTH.with(|x| x.get()) + 1;
+        if depth > max_depth {
+            return Err(EncodingError::TooDeep(max_depth));
+        }
+        DEPTH.with(|x| x.set(depth));
+        Ok(Self(()))
+    }
+}
+
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        DEPTH.with(|x| x.set(x.get() - 1));
+    }
+}
+
 pub trait Encode {
     fn size(&self) -> u32;
     fn encode<W: Write>(&self, w: W) -> Result<(), EncodingError>;
@@ -56,34 +158,197 @@ pub trait Decode: Sized {
         let len = Varint::<u32>::decode(&mut r)
             .map_err(|x| x.context("varint len read"))?
             .0;
-        let mut buf = vec![0; len as usize];
-        r.read_exact(&mut buf)?;
+        DecodeLimits::current().check_size(len)?;
+        // Buffer grows with data actually read, not with claimed length
+        let mut buf = vec![];
+        r.take(len as u64).read_to_end(&mut buf)?;
+        if buf.len() != len as usize {
+            let eof = std::io::ErrorKind::UnexpectedEof.into();
+            return Err(EncodingError::Io(eof));
+        }
         Self::decode(buf.as_slice())
     }
 }
 
+/// Implemented by generated messages
+pub trait MessageName {
+    /// Fully qualified name, without leading dot (`package.Message`)
+    const FULL_NAME: &'static str;
+}
+
+pub fn key_size(field_number: u32) -> u32 {
+    Varint(field_number << 3).size()
+}
+
+pub fn encode_key<W: Write>(
+    w: W,
+    field_number: u32,
+    wire_type: u8,
+) -> Result<(), EncodingError> {
+    Varint(field_number << 3 | wire_type as u32).encode(w)
+}
+
 pub fn encode_field<W: Write, T: Encode>(
     mut w: W,
-    field_number: u8,
+    field_number: u32,
     wire_type: u8,
     field: &T,
 ) -> Result<(), EncodingError> {
-    let key = field_number << 3 | wire_type;
-    w.write(&[key])?;
+    encode_key(&mut w, field_number, wire_type)?;
     field.encode(w)
 }
 
+/// Reads field key, returning `None` on clean end of input
+pub fn decode_key(
+    r: &mut dyn Read,
+) -> Result<Option<(u32, u8)>, EncodingError> {
+    let mut byte = 0;
+    if r.read(std::slice::from_mut(&mut byte))? == 0 {
+        return Ok(None);
+    }
+    let mut key = (byte & 0x7f) as u32;
+    if byte & 0x80 != 0 {
+        let rest = <Varint<u32>>::decode(&mut *r)?.0;
+        if rest > u32::MAX >> 7 {
+            return Err(EncodingError::VarintTooLong);
+        }
+        key |= rest << 7;
+    }
+    Ok(Some((key >> 3, (key & 0x7) as u8)))
+}
+
+/// Skips over value of unknown field (including proto2 extensions)
+pub fn skip_field(
+    r: &mut dyn Read,
+    wire_type: u8,
+) -> Result<(), EncodingError> {
+    match wire_type {
+        0 => {
+            <Varint<u64>>::decode(r)?;
+        }
+        1 => {
+            <Fixed<u64>>::decode(r)?;
+        }
+        2 => {
+            let len = <Varint<u32>>::decode(&mut *r)?.0 as u64;
+            let skipped =
+                std::io::copy(&mut r.take(len), &mut std::io::sink())?;
+            if skipped != len {
+                let eof = std::io::ErrorKind::UnexpectedEof.into();
+                return Err(EncodingError::Io(eof));
+            }
+        }
+        // Groups are skipped up to their matching end-group key
+        3 => {
+            let _depth = DepthGuard::enter()?;
+            loop {
+                match decode_key(r)? {
+                    Some((_, 4)) => break,
+                    Some((_, wire_type)) => skip_field(r, wire_type)?,
+                    None => {
+                        let eof = std::io::ErrorKind::UnexpectedEof.into();
+                        return Err(EncodingError::Io(eof));
+                    }
+                }
+            }
+        }
+        5 => {
+            <Fixed<u32>>::decode(r)?;
+        }
+        e => return Err(EncodingError::InvalidWireType(e)),
+    }
+    Ok(())
+}
+
 pub fn decode_packed<R: Read, T: Decode>(
     r: R,
 ) -> Result<Vec<T>, EncodingError> {
-    let mut buf = std::io::Cursor::new(<Vec<u8>>::decode_with_len(r)?);
+    let limits = DecodeLimits::current();
+    let buf = <Vec<u8>>::decode(r)?;
+    let mut buf = buf.as_slice();
     let mut res = vec![];
-    while !buf.get_ref().is_empty() {
+    while !buf.is_empty() {
         res.push(T::decode(&mut buf)?);
+        limits.check_repeated(res.len())?;
     }
     Ok(res)
 }
 
+/// Incremental decoder of length-delimited frames
+///
+/// Bytes are pushed as they arrive (e.g. from non-blocking socket),
+/// and complete frames are taken out once fully buffered.
+#[derive(Default, Debug)]
+pub struct FrameDecoder {
+    buf: Vec<u8>,
+}
+
+impl FrameDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends received bytes
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// Does one `read` into buffer, returning number of bytes read.
+    /// Errors (including `WouldBlock`) are passed through.
+    pub fn read_from<R: Read>(&mut self, mut r: R) -> std::io::Result<usize> {
+        let mut chunk = [0; 4096];
+        let n = r.read(&mut chunk)?;
+        self.push(&chunk[..n]);
+        Ok(n)
+    }
+
+    /// Number of buffered bytes not yet taken out as frames
+    pub fn buffered(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Takes next frame's contents (without length prefix) out of buffer,
+    /// or returns `None` if more bytes are needed
+    pub fn next_frame(&mut self) -> Result<Option<Vec<u8>>, EncodingError> {
+        let mut len = 0u32;
+        let mut prefix_len = None;
+        for (i, &byte) in self.buf.iter().enumerate().take(5) {
+            // Last byte only has 4 bits left for `u32`
+            if i == 4 && byte > 0x0f {
+                return Err(EncodingError::VarintTooLong);
+            }
+            len |= (byte as u32 & 0x7f) << (i * 7);
+            if byte & 0x80 == 0 {
+                prefix_len = Some(i + 1);
+                break;
+            }
+        }
+        let prefix_len = match prefix_len {
+            Some(x) => x,
+            None if self.buf.len() >= 5 => {
+                return Err(EncodingError::VarintTooLong)
+            }
+            None => return Ok(None),
+        };
+        DecodeLimits::current().check_size(len)?;
+        let end = prefix_len + len as usize;
+        if self.buf.len() < end {
+            return Ok(None);
+        }
+        let frame = self.buf[prefix_len..end].to_vec();
+        self.buf.drain(..end);
+        Ok(Some(frame))
+    }
+
+    /// Decodes next frame as `T`, or returns `None` if more bytes are needed
+    pub fn decode<T: Decode>(&mut self) -> Result<Option<T>, EncodingError> {
+        match self.next_frame()? {
+            Some(frame) => Ok(Some(T::decode(frame.as_slice())?)),
+            None => Ok(None),
+        }
+    }
+}
+
 pub trait EncodeDyn {
     fn encode_dyn(&self, w: &mut dyn Write) -> Result<(), EncodingError>;
     fn encode_to_vec(&self) -> Result<Vec<u8>, EncodingError>;
@@ -128,9 +393,10 @@ macro_rules! impl_encode_varint {
         impl Encode for Varint<$t> {
             fn size(&self) -> u32 {
                 // round up to multiply off 7 because varints
-                ((<$t>::BITS - self.0.leading_zeros()) + 7) / 7
+                let bits = <$t>::BITS - self.0.leading_zeros();
+                bits.div_ceil(7).max(1)
             }
-        
+
             fn encode<W: Write>(&self, mut w: W) -> Result<(), EncodingError> {
                 let mut i = self.0;
                 while i > 127 {
@@ -149,13 +415,17 @@ impl_encode_varint!(u32);
 impl_encode_varint!(u64);
 
 macro_rules! impl_decode_varint {
-    ($t:ty : $varsize:literal) => {
+    ($t:ty : $varsize:literal, $last_max:literal) => {
         impl Decode for Varint<$t> {
             fn decode<R: Read>(mut r: R) -> Result<Self, EncodingError> {
                 let mut res = 0;
                 for i in 0..$varsize {
                     let mut byte = 0;
                     r.read_exact(std::slice::from_mut(&mut byte))?;
+                    // Bits that don't fit in `$t` would be dropped
+                    if i == $varsize - 1 && byte > $last_max {
+                        return Err(EncodingError::VarintTooLong);
+                    }
                     res |= (byte as $t & 0x7f) << i * 7;
                     if byte & 0x80 == 0 {
                         return Ok(Varint(res));
@@ -167,22 +437,24 @@ macro_rules! impl_decode_varint {
     };
 }
 
-impl_decode_varint!(u32: 5);
-impl_decode_varint!(u64: 10);
+impl_decode_varint!(u32: 5, 0x0f);
+impl_decode_varint!(u64: 10, 0x01);
 
+// Negative values are sign-extended to 10 bytes, like with protoc,
+// and longer encodings are truncated, as protobuf spec requires
 impl Encode for Varint<i32> {
     fn size(&self) -> u32 {
-        Varint(self.0 as u32).size()
+        Varint(self.0 as i64 as u64).size()
     }
 
     fn encode<W: Write>(&self, w: W) -> Result<(), EncodingError> {
-        Varint(self.0 as u32).encode(w)
+        Varint(self.0 as i64 as u64).encode(w)
     }
 }
 
 impl Decode for Varint<i32> {
     fn decode<R: Read>(r: R) -> Result<Self, EncodingError> {
-        <Varint<u32>>::decode(r).map(|Varint(x)| Varint(x as i32))
+        <Varint<u64>>::decode(r).map(|Varint(x)| Varint(x as i32))
     }
 }
 
@@ -202,6 +474,40 @@ impl Decode for Varint<i64> {
     }
 }
 
+impl Encode for SVarint<i32> {
+    fn size(&self) -> u32 {
+        Varint(((self.0 << 1) ^ (self.0 >> 31)) as u32).size()
+    }
+
+    fn encode<W: Write>(&self, w: W) -> Result<(), EncodingError> {
+        Varint(((self.0 << 1) ^ (self.0 >> 31)) as u32).encode(w)
+    }
+}
+
+impl Decode for SVarint<i32> {
+    fn decode<R: Read>(r: R) -> Result<Self, EncodingError> {
+        <Varint<u32>>::decode(r)
+            .map(|Varint(x)| SVarint((x >> 1) as i32 ^ -((x & 1) as i32)))
+    }
+}
+
+impl Encode for SVarint<i64> {
+    fn size(&self) -> u32 {
+        Varint(((self.0 << 1) ^ (self.0 >> 63)) as u64).size()
+    }
+
+    fn encode<W: Write>(&self, w: W) -> Result<(), EncodingError> {
+        Varint(((self.0 << 1) ^ (self.0 >> 63)) as u64).encode(w)
+    }
+}
+
+impl Decode for SVarint<i64> {
+    fn decode<R: Read>(r: R) -> Result<Self, EncodingError> {
+        <Varint<u64>>::decode(r)
+            .map(|Varint(x)| SVarint((x >> 1) as i64 ^ -((x & 1) as i64)))
+    }
+}
+
 impl Encode for Fixed<u32> {
     fn size(&self) -> u32 {
         std::mem::size_of::<Self>() as u32
@@ -240,6 +546,38 @@ impl Decode for Fixed<u64> {
     }
 }
 
+impl Encode for Fixed<i32> {
+    fn size(&self) -> u32 {
+        Fixed(self.0 as u32).size()
+    }
+
+    fn encode<W: Write>(&self, w: W) -> Result<(), EncodingError> {
+        Fixed(self.0 as u32).encode(w)
+    }
+}
+
+impl Decode for Fixed<i32> {
+    fn decode<R: Read>(r: R) -> Result<Self, EncodingError> {
+        <Fixed<u32>>::decode(r).map(|Fixed(x)| Fixed(x as i32))
+    }
+}
+
+impl Encode for Fixed<i64> {
+    fn size(&self) -> u32 {
+        Fixed(self.0 as u64).size()
+    }
+
+    fn encode<W: Write>(&self, w: W) -> Result<(), EncodingError> {
+        Fixed(self.0 as u64).encode(w)
+    }
+}
+
+impl Decode for Fixed<i64> {
+    fn decode<R: Read>(r: R) -> Result<Self, EncodingError> {
+        <Fixed<u64>>::decode(r).map(|Fixed(x)| Fixed(x as i64))
+    }
+}
+
 impl Encode for f32 {
     fn size(&self) -> u32 {
         std::mem::size_of::<Self>() as u32
@@ -322,7 +660,7 @@ impl Decode for u8 {
 impl<T: Encode> Encode for &[T] {
     fn size(&self) -> u32 {
         let len = self.len() as u32;
-        Varint(len).size() + len
+        Varint(len).size() + self.iter().map(Encode::size).sum::<u32>()
     }
 
     fn encode<W: Write>(&self, mut w: W) -> Result<(), EncodingError> {
@@ -347,7 +685,13 @@ impl<T: Encode> Encode for Vec<T> {
 impl<T: Decode> Decode for Vec<T> {
     fn decode<R: Read>(mut r: R) -> Result<Self, EncodingError> {
         let len = Varint::<u32>::decode(&mut r)?.0;
-        (0..len).map(|_| T::decode(&mut r)).collect()
+        // Every element takes at least one byte
+        DecodeLimits::current().check_size(len)?;
+        let mut res = Vec::with_capacity(len.min(4096) as usize);
+        for _ in 0..len {
+            res.push(T::decode(&mut r)?);
+        }
+        Ok(res)
     }
 }
 
@@ -377,6 +721,24 @@ impl Decode for String {
     }
 }
 
+#[cfg(feature = "bytes")]
+impl Encode for bytes::Bytes {
+    fn size(&self) -> u32 {
+        self.as_ref().size()
+    }
+
+    fn encode<W: Write>(&self, w: W) -> Result<(), EncodingError> {
+        self.as_ref().encode(w)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Decode for bytes::Bytes {
+    fn decode<R: Read>(r: R) -> Result<Self, EncodingError> {
+        Ok(Vec::<u8>::decode(r)?.into())
+    }
+}
+
 impl Encode for () {
     fn size(&self) -> u32 {
         0
@@ -432,4 +794,135 @@ mod tests {
             assert_eq!(decoded.0, *input);
         }
     }
+
+    #[test]
+    fn zigzag() {
+        let cases: &[(i64, &[u8])] = &[
+            (0, &[0]),
+            (-1, &[1]),
+            (1, &[2]),
+            (-64, &[0x7f]),
+            (64, &[0x80, 0x01]),
+        ];
+        for (input, expected) in cases {
+            let mut vec = vec![];
+            SVarint(*input).encode(&mut vec).unwrap();
+            assert_eq!(&vec, expected);
+            assert_eq!(SVarint(*input).size(), expected.len() as u32);
+            let decoded = <SVarint<i64>>::decode(vec.as_slice()).unwrap();
+            assert_eq!(decoded.0, *input);
+        }
+    }
+
+    #[test]
+    fn keys_and_unknown_fields() {
+        let mut vec = vec![];
+        encode_field(&mut vec, 300, 0, &Varint(150u32)).unwrap();
+        encode_field(&mut vec, 2, 2, &"skipped").unwrap();
+        encode_field(&mut vec, 3, 5, &Fixed(7u32)).unwrap();
+        let mut r = vec.as_slice();
+        assert_eq!(decode_key(&mut r).unwrap(), Some((300, 0)));
+        skip_field(&mut r, 0).unwrap();
+        assert_eq!(decode_key(&mut r).unwrap(), Some((2, 2)));
+        skip_field(&mut r, 2).unwrap();
+        assert_eq!(decode_key(&mut r).unwrap(), Some((3, 5)));
+        skip_field(&mut r, 5).unwrap();
+        assert_eq!(decode_key(&mut r).unwrap(), None);
+    }
+
+    #[test]
+    fn limits() {
+        // Claims 4 GiB of data
+        let huge = [0xff, 0xff, 0xff, 0xff, 0x0f];
+        let err = <Vec<u8>>::decode_with_len(&huge[..]).unwrap_err();
+        assert!(matches!(err, EncodingError::MessageTooLarge(..)));
+        let err = <Vec<u8>>::decode(&huge[..]).unwrap_err();
+        assert!(matches!(err, EncodingError::MessageTooLarge(..)));
+
+        // Claimed length within limits, but input ends early
+        let short = [100, 1, 2, 3];
+        assert!(<Vec<u8>>::decode_with_len(&short[..]).is_err());
+
+        let limits = DecodeLimits {
+            max_repeated: 2,
+            ..DecodeLimits::default()
+        };
+        let packed = [3, 1, 2, 3];
+        limits.scope(|| {
+            let err = decode_packed::<_, u8>(&packed[..]).unwrap_err();
+            assert!(matches!(err, EncodingError::TooManyElements(3, 2)));
+        });
+        assert_eq!(DecodeLimits::current(), DecodeLimits::default());
+
+        // Groups nested deeper than limit
+        let limits = DecodeLimits {
+            max_depth: 2,
+            ..DecodeLimits::default()
+        };
+        let groups = [1 << 3 | 3, 1 << 3 | 3, 1 << 3 | 3];
+        limits.scope(|| {
+            let mut r = &groups[1..];
+            let err = skip_field(&mut r, 3).unwrap_err();
+            assert!(matches!(err, EncodingError::TooDeep(2)));
+        });
+    }
+
+    #[test]
+    fn frames() {
+        let mut input = vec![];
+        "first".encode_with_len(&mut input).unwrap();
+        "x".repeat(200).encode_with_len(&mut input).unwrap();
+
+        // Fed one byte at a time
+        let mut decoder = FrameDecoder::new();
+        let mut frames = vec![];
+        for byte in &input {
+            decoder.push(std::slice::from_ref(byte));
+            if let Some(frame) = decoder.decode::<String>().unwrap() {
+                frames.push(frame);
+            }
+        }
+        assert_eq!(frames, ["first".to_string(), "x".repeat(200)]);
+        assert_eq!(decoder.buffered(), 0);
+
+        // Both at once
+        decoder.push(&input);
+        assert_eq!(decoder.decode::<String>().unwrap().unwrap(), "first");
+        assert!(decoder.decode::<String>().unwrap().is_some());
+        assert!(decoder.decode::<String>().unwrap().is_none());
+
+        decoder.push(&[0xff; 5]);
+        assert!(matches!(
+            decoder.next_frame(),
+            Err(EncodingError::VarintTooLong)
+        ));
+
+        // Fifth byte with bits above `u32`
+        let mut decoder = FrameDecoder::new();
+        decoder.push(&[0x80, 0x80, 0x80, 0x80, 0x10]);
+        assert!(matches!(
+            decoder.next_frame(),
+            Err(EncodingError::VarintTooLong)
+        ));
+        let bytes = [0x80, 0x80, 0x80, 0x80, 0x10];
+        assert!(<Varint<u32>>::decode(&bytes[..]).is_err());
+        let bytes = [0xff, 0xff, 0xff, 0xff, 0x0f];
+        assert_eq!(<Varint<u32>>::decode(&bytes[..]).unwrap().0, u32::MAX);
+        let mut bytes = [0xff; 10];
+        bytes[9] = 0x02;
+        assert!(<Varint<u64>>::decode(&bytes[..]).is_err());
+        bytes[9] = 0x01;
+        assert_eq!(<Varint<u64>>::decode(&bytes[..]).unwrap().0, u64::MAX);
+
+        // int32 is sign-extended, and accepts any varint
+        let mut encoded = vec![];
+        Varint(-1i32).encode(&mut encoded).unwrap();
+        assert_eq!(encoded, bytes);
+        assert_eq!(Varint(-1i32).size(), 10);
+        assert_eq!(<Varint<i32>>::decode(&bytes[..]).unwrap().0, -1);
+        let bytes = [0xff, 0xff, 0xff, 0xff, 0x0f];
+        assert_eq!(<Varint<i32>>::decode(&bytes[..]).unwrap().0, -1);
+        let bytes = [0x80, 0x80, 0x80, 0x80, 0x10];
+        assert_eq!(<Varint<i32>>::decode(&bytes[..]).unwrap().0, 0);
+    }
 }
@@ -0,0 +1,948 @@
+// Proto3 canonical JSON mapping, on top of `dynamic` values
+// https://developers.google.com/protocol-buffers/docs/proto3#json
+
+use std::convert::TryFrom;
+
+use serde_json::{Number, Value as Json};
+
+use crate::{
+    descriptor::{Kind, Label, MessageDesc, Pool},
+    dynamic::{FieldValue, MessageValue, Value},
+    encoding::*,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum JsonError {
+    #[error("Json error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Encoding error: {0}")]
+    Encoding(#[from] EncodingError),
+    #[error("Invalid value for {0}: {1}")]
+    InvalidValue(String, Json),
+    #[error("Unknown field {0}")]
+    UnknownField(String),
+}
+
+/// Serializes message to JSON string
+pub fn to_string<T: Encode + MessageName>(
+    pool: &Pool,
+    message: &T,
+) -> Result<String, JsonError> {
+    Ok(to_json(pool, message)?.to_string())
+}
+
+/// Serializes message to indented JSON string
+pub fn to_string_pretty<T: Encode + MessageName>(
+    pool: &Pool,
+    message: &T,
+) -> Result<String, JsonError> {
+    Ok(serde_json::to_string_pretty(&to_json(pool, message)?)?)
+}
+
+/// Deserializes message from JSON string
+pub fn from_str<T: Decode + MessageName>(
+    pool: &Pool,
+    json: &str,
+) -> Result<T, JsonError> {
+    let json: Json = serde_json::from_str(json)?;
+    let value = pool.from_json(T::FULL_NAME, &json)?;
+    Ok(T::decode(pool.encode(&value)?.as_slice())?)
+}
+
+fn to_json<T: Encode + MessageName>(
+    pool: &Pool,
+    message: &T,
+) -> Result<Json, JsonError> {
+    let mut bytes = vec![];
+    message.encode(&mut bytes)?;
+    pool.to_json(&pool.decode(T::FULL_NAME, &bytes)?)
+}
+
+/// Field name as used in JSON: `foo_bar` becomes `fooBar`
+pub fn json_name(name: &str) -> String {
+    let mut res = String::new();
+    let mut upper = false;
+    for c in name.chars() {
+        match c {
+            '_' => upper = true,
+            c if upper => {
+                res.extend(c.to_uppercase());
+                upper = false;
+            }
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+impl Pool {
+    /// JSON of message. Fields not present on the wire are left out,
+    /// and unknown fields are dropped. Well-known types have their
+    /// special representations, `Any` needs its packed type in the pool.
+    pub fn to_json(&self, message: &MessageValue) -> Result<Json, JsonError> {
+        message_to_json(self, message)
+    }
+
+    /// Parses JSON object as message `message_name`. Both JSON and
+    /// original field names are accepted, `null` means default value.
+    pub fn from_json(
+        &self,
+        message_name: &str,
+        json: &Json,
+    ) -> Result<MessageValue, JsonError> {
+        let desc = self.message(message_name).ok_or_else(|| {
+            EncodingError::UnknownMessage(message_name.to_string())
+        })?;
+        message_from_json(self, desc, json)
+    }
+}
+
+fn message_to_json(
+    pool: &Pool,
+    message: &MessageValue,
+) -> Result<Json, JsonError> {
+    match message.type_name.as_str() {
+        TIMESTAMP => return timestamp_to_json(message),
+        DURATION => return duration_to_json(message),
+        ANY => return any_to_json(pool, message),
+        _ => (),
+    }
+    if let Some(default) = wrapper_default(&message.type_name) {
+        return match message.get("value") {
+            Some(value) => value_to_json(pool, value),
+            None => Ok(default),
+        };
+    }
+    let fields = message
+        .fields
+        .iter()
+        .map(|f| Ok((json_name(&f.name), value_to_json(pool, &f.value)?)))
+        .collect::<Result<_, JsonError>>()?;
+    Ok(Json::Object(fields))
+}
+
+fn value_to_json(pool: &Pool, value: &Value) -> Result<Json, JsonError> {
+    Ok(match value {
+        Value::Message(x) => message_to_json(pool, x)?,
+        Value::List(x) => Json::Array(
+            x.iter()
+                .map(|x| value_to_json(pool, x))
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Map(x) => Json::Object(
+            x.iter()
+                .map(|(k, v)| Ok((map_key(k), value_to_json(pool, v)?)))
+                .collect::<Result<_, JsonError>>()?,
+        ),
+        x => scalar_to_json(x),
+    })
+}
+
+fn scalar_to_json(value: &Value) -> Json {
+    match value {
+        Value::Bool(x) => Json::Bool(*x),
+        Value::I32(x) => Json::from(*x),
+        Value::U32(x) => Json::from(*x),
+        // 64-bit integers don't fit in JavaScript numbers
+        Value::I64(x) => Json::String(x.to_string()),
+        Value::U64(x) => Json::String(x.to_string()),
+        // Shortest representation of f32, not of it widened to f64
+        Value::F32(x) => float_to_json(*x as f64, x.to_string()),
+        Value::F64(x) => float_to_json(*x, x.to_string()),
+        Value::String(x) => Json::String(x.clone()),
+        Value::Bytes(x) => Json::String(base64_encode(x)),
+        Value::Enum(_, Some(name)) => Json::String(name.clone()),
+        Value::Enum(number, None) => Json::from(*number),
+        Value::Message(_) | Value::List(_) | Value::Map(_) => Json::Null,
+    }
+}
+
+fn float_to_json(x: f64, text: String) -> Json {
+    if x.is_nan() {
+        Json::String("NaN".into())
+    } else if x == f64::INFINITY {
+        Json::String("Infinity".into())
+    } else if x == f64::NEG_INFINITY {
+        Json::String("-Infinity".into())
+    } else {
+        text.parse()
+            .ok()
+            .and_then(Number::from_f64)
+            .map_or(Json::Null, Json::Number)
+    }
+}
+
+fn map_key(key: &Value) -> String {
+    match scalar_to_json(key) {
+        Json::String(x) => x,
+        x => x.to_string(),
+    }
+}
+
+fn message_from_json(
+    pool: &Pool,
+    desc: &MessageDesc,
+    json: &Json,
+) -> Result<MessageValue, JsonError> {
+    match desc.name.as_str() {
+        TIMESTAMP => return timestamp_from_json(desc, json),
+        DURATION => return duration_from_json(desc, json),
+        ANY => return any_from_json(pool, desc, json),
+        _ => (),
+    }
+    if wrapper_default(&desc.name).is_some() {
+        let field = desc.field(1).ok_or_else(|| invalid(desc, json))?;
+        let value = value_from_json(pool, &field.kind, json, &desc.name)?;
+        return Ok(well_known_value(desc, vec![("value", value)]));
+    }
+    let object = match json {
+        Json::Object(x) => x,
+        x => return Err(JsonError::InvalidValue(desc.name.clone(), x.clone())),
+    };
+    let mut fields = vec![];
+    for (key, json) in object {
+        let path = format!("{}.{}", desc.name, key);
+        let field = desc
+            .fields
+            .iter()
+            .find(|f| json_name(&f.name) == *key || f.name == *key)
+            .ok_or_else(|| JsonError::UnknownField(path.clone()))?;
+        if json.is_null() {
+            continue;
+        }
+        let value = match (&field.kind, json) {
+            (Kind::Map(key_kind, value_kind), Json::Object(entries)) => {
+                let entries = entries
+                    .iter()
+                    .map(|(k, v)| {
+                        // Keys are always strings, bools included
+                        let k = match (&**key_kind, k.as_str()) {
+                            (Kind::Bool, "true") => Json::Bool(true),
+                            (Kind::Bool, "false") => Json::Bool(false),
+                            _ => Json::String(k.clone()),
+                        };
+                        Ok((
+                            value_from_json(pool, key_kind, &k, &path)?,
+                            value_from_json(pool, value_kind, v, &path)?,
+                        ))
+                    })
+                    .collect::<Result<_, JsonError>>()?;
+                Value::Map(entries)
+            }
+            (kind, Json::Array(values)) if field.label == Label::Repeated => {
+                let values = values
+                    .iter()
+                    .map(|x| value_from_json(pool, kind, x, &path))
+                    .collect::<Result<_, _>>()?;
+                Value::List(values)
+            }
+            (Kind::Map(..), _) => {
+                return Err(JsonError::InvalidValue(path, json.clone()))
+            }
+            (_, _) if field.label == Label::Repeated => {
+                return Err(JsonError::InvalidValue(path, json.clone()))
+            }
+            (kind, _) => value_from_json(pool, kind, json, &path)?,
+        };
+        fields.push(FieldValue {
+            number: field.number,
+            name: field.name.clone(),
+            value,
+        });
+    }
+    fields.sort_by_key(|f| f.number);
+    Ok(MessageValue {
+        type_name: desc.name.clone(),
+        fields,
+        unknown: vec![],
+    })
+}
+
+fn value_from_json(
+    pool: &Pool,
+    kind: &Kind,
+    json: &Json,
+    path: &str,
+) -> Result<Value, JsonError> {
+    let invalid = || JsonError::InvalidValue(path.to_string(), json.clone());
+    // Integers can be numbers or strings, in both directions
+    let int = || -> Option<i128> {
+        match json {
+            Json::Number(x) => x
+                .as_i64()
+                .map(i128::from)
+                .or_else(|| x.as_u64().map(i128::from))
+                .or_else(|| {
+                    let x = x.as_f64()?;
+                    (x.fract() == 0.0 && x.abs() < 2f64.powi(64))
+                        .then_some(x as i128)
+                }),
+            Json::String(x) => x.parse().ok(),
+            _ => None,
+        }
+    };
+    let float = || -> Option<f64> {
+        match json {
+            Json::Number(x) => x.as_f64(),
+            Json::String(x) => match x.as_str() {
+                "NaN" => Some(f64::NAN),
+                "Infinity" => Some(f64::INFINITY),
+                "-Infinity" => Some(f64::NEG_INFINITY),
+                x => x.parse().ok(),
+            },
+            _ => None,
+        }
+    };
+    Ok(match kind {
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => Value::I32(
+            int()
+                .and_then(|x| i32::try_from(x).ok())
+                .ok_or_else(invalid)?,
+        ),
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => Value::I64(
+            int()
+                .and_then(|x| i64::try_from(x).ok())
+                .ok_or_else(invalid)?,
+        ),
+        Kind::Uint32 | Kind::Fixed32 => Value::U32(
+            int()
+                .and_then(|x| u32::try_from(x).ok())
+                .ok_or_else(invalid)?,
+        ),
+        Kind::Uint64 | Kind::Fixed64 => Value::U64(
+            int()
+                .and_then(|x| u64::try_from(x).ok())
+                .ok_or_else(invalid)?,
+        ),
+        Kind::Float => {
+            let x = float().ok_or_else(invalid)?;
+            // Finite values out of range would become infinite
+            if x.is_finite() && (x as f32).is_infinite() {
+                return Err(invalid());
+            }
+            Value::F32(x as f32)
+        }
+        Kind::Double => Value::F64(float().ok_or_else(invalid)?),
+        Kind::Bool => match json {
+            Json::Bool(x) => Value::Bool(*x),
+            _ => return Err(invalid()),
+        },
+        Kind::String => match json {
+            Json::String(x) => Value::String(x.clone()),
+            _ => return Err(invalid()),
+        },
+        Kind::Bytes => match json {
+            Json::String(x) => {
+                Value::Bytes(base64_decode(x).ok_or_else(invalid)?)
+            }
+            _ => return Err(invalid()),
+        },
+        Kind::Enum(name) => {
+            let desc = pool.enumeration(name);
+            match json {
+                Json::String(x) => {
+                    let number = desc
+                        .and_then(|e| e.number_of(x))
+                        .ok_or_else(invalid)?;
+                    Value::Enum(number, Some(x.clone()))
+                }
+                _ => {
+                    let number = int()
+                        .and_then(|x| i32::try_from(x).ok())
+                        .ok_or_else(invalid)?;
+                    let name =
+                        desc.and_then(|e| e.name_of(number)).map(String::from);
+                    Value::Enum(number, name)
+                }
+            }
+        }
+        Kind::Message(name) => {
+            let desc = pool
+                .message(name)
+                .ok_or_else(|| EncodingError::UnknownMessage(name.clone()))?;
+            Value::Message(message_from_json(pool, desc, json)?)
+        }
+        Kind::Map(..) => return Err(invalid()),
+    })
+}
+
+const TIMESTAMP: &str = ".google.protobuf.Timestamp";
+const DURATION: &str = ".google.protobuf.Duration";
+const ANY: &str = ".google.protobuf.Any";
+
+// 0001-01-01T00:00:00Z and 9999-12-31T23:59:59Z
+const MIN_TIMESTAMP: i64 = -62_135_596_800;
+const MAX_TIMESTAMP: i64 = 253_402_300_799;
+// About 10000 years, in both directions
+const MAX_DURATION: i64 = 315_576_000_000;
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+
+// JSON of wrapper's missing value, `None` for other messages
+fn wrapper_default(name: &str) -> Option<Json> {
+    Some(match name.strip_prefix(".google.protobuf.")? {
+        "DoubleValue" | "FloatValue" | "Int32Value" | "UInt32Value" => {
+            Json::from(0)
+        }
+        "Int64Value" | "UInt64Value" => Json::String("0".into()),
+        "BoolValue" => Json::Bool(false),
+        "StringValue" | "BytesValue" => Json::String(String::new()),
+        _ => return None,
+    })
+}
+
+// Types which `Any` keeps in `value` instead of merging their fields
+fn has_special_json(name: &str) -> bool {
+    matches!(name, TIMESTAMP | DURATION | ANY)
+        || wrapper_default(name).is_some()
+}
+
+fn invalid(desc: &MessageDesc, json: &Json) -> JsonError {
+    JsonError::InvalidValue(desc.name.clone(), json.clone())
+}
+
+// Error for well-known message with out of range fields
+fn invalid_message(message: &MessageValue) -> JsonError {
+    let fields = message
+        .fields
+        .iter()
+        .map(|f| (json_name(&f.name), scalar_to_json(&f.value)))
+        .collect();
+    JsonError::InvalidValue(message.type_name.clone(), Json::Object(fields))
+}
+
+// Message of well-known type, with default values left out
+fn well_known_value(
+    desc: &MessageDesc,
+    values: Vec<(&str, Value)>,
+) -> MessageValue {
+    let fields = values
+        .into_iter()
+        .filter(|(_, value)| match value {
+            Value::I32(x) => *x != 0,
+            Value::I64(x) => *x != 0,
+            Value::String(x) => !x.is_empty(),
+            Value::Bytes(x) => !x.is_empty(),
+            _ => true,
+        })
+        .filter_map(|(name, value)| {
+            let field = desc.field_by_name(name)?;
+            Some(FieldValue {
+                number: field.number,
+                name: field.name.clone(),
+                value,
+            })
+        })
+        .collect();
+    MessageValue {
+        type_name: desc.name.clone(),
+        fields,
+        unknown: vec![],
+    }
+}
+
+fn int_field(message: &MessageValue, name: &str) -> i64 {
+    match message.get(name) {
+        Some(Value::I64(x)) => *x,
+        Some(Value::I32(x)) => *x as i64,
+        _ => 0,
+    }
+}
+
+// `nanos` as 0, 3, 6 or 9 fractional digits
+fn fraction(nanos: i64) -> String {
+    if nanos == 0 {
+        String::new()
+    } else if nanos % 1_000_000 == 0 {
+        format!(".{:03}", nanos / 1_000_000)
+    } else if nanos % 1000 == 0 {
+        format!(".{:06}", nanos / 1000)
+    } else {
+        format!(".{:09}", nanos)
+    }
+}
+
+// Optional `.` and up to 9 digits at start of `text`, as nanoseconds
+fn parse_fraction(text: &str) -> Option<(i64, &str)> {
+    let rest = match text.strip_prefix('.') {
+        Some(x) => x,
+        None => return Some((0, text)),
+    };
+    let len = rest.bytes().take_while(u8::is_ascii_digit).count();
+    if len == 0 || len > 9 {
+        return None;
+    }
+    let nanos = rest[..len].parse::<i64>().ok()? * 10i64.pow(9 - len as u32);
+    Some((nanos, &rest[len..]))
+}
+
+// Year, month and day of days since Unix epoch, in proleptic Gregorian
+// calendar (http://howardhinnant.github.io/date_algorithms.html)
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn timestamp_to_json(message: &MessageValue) -> Result<Json, JsonError> {
+    let seconds = int_field(message, "seconds");
+    let nanos = int_field(message, "nanos");
+    if !(MIN_TIMESTAMP..=MAX_TIMESTAMP).contains(&seconds)
+        || !(0..NANOS_PER_SECOND).contains(&nanos)
+    {
+        return Err(invalid_message(message));
+    }
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86400));
+    let time = seconds.rem_euclid(86400);
+    Ok(Json::String(format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}Z",
+        year,
+        month,
+        day,
+        time / 3600,
+        time / 60 % 60,
+        time % 60,
+        fraction(nanos)
+    )))
+}
+
+// RFC 3339, with `Z` or `+hh:mm` offset
+fn parse_timestamp(text: &str) -> Option<(i64, i64)> {
+    let number = |start: usize, len: usize| -> Option<i64> {
+        let digits = text.get(start..start + len)?;
+        if !digits.bytes().all(|x| x.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse().ok()
+    };
+    let separators = [(4, b'-'), (7, b'-'), (13, b':'), (16, b':')];
+    let bytes = text.as_bytes();
+    if bytes.len() < 20
+        || separators.iter().any(|&(i, x)| bytes[i] != x)
+        || !matches!(bytes[10], b'T' | b't')
+    {
+        return None;
+    }
+    let (year, month, day) = (number(0, 4)?, number(5, 2)?, number(8, 2)?);
+    let (hour, minute) = (number(11, 2)?, number(14, 2)?);
+    let second = number(17, 2)?;
+    // Invalid dates like February 30th don't survive roundtrip
+    let days = days_from_civil(year, month, day);
+    if !(1..=12).contains(&month)
+        || civil_from_days(days) != (year, month, day)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return None;
+    }
+    let (nanos, zone) = parse_fraction(&text[19..])?;
+    let offset = match zone.as_bytes() {
+        [b'Z'] | [b'z'] => 0,
+        [sign @ (b'+' | b'-'), h1, h2, b':', m1, m2] => {
+            let digits = [h1, h2, m1, m2];
+            if !digits.iter().all(|x| x.is_ascii_digit()) {
+                return None;
+            }
+            let digit = |i: usize| (digits[i] - b'0') as i64;
+            let (hours, minutes) =
+                (digit(0) * 10 + digit(1), digit(2) * 10 + digit(3));
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            let offset = hours * 3600 + minutes * 60;
+            if *sign == b'-' {
+                -offset
+            } else {
+                offset
+            }
+        }
+        _ => return None,
+    };
+    let seconds = days * 86400 + hour * 3600 + minute * 60 + second - offset;
+    (MIN_TIMESTAMP..=MAX_TIMESTAMP)
+        .contains(&seconds)
+        .then_some((seconds, nanos))
+}
+
+fn timestamp_from_json(
+    desc: &MessageDesc,
+    json: &Json,
+) -> Result<MessageValue, JsonError> {
+    let (seconds, nanos) = match json {
+        Json::String(x) => parse_timestamp(x),
+        _ => None,
+    }
+    .ok_or_else(|| invalid(desc, json))?;
+    Ok(well_known_value(
+        desc,
+        vec![
+            ("seconds", Value::I64(seconds)),
+            ("nanos", Value::I32(nanos as i32)),
+        ],
+    ))
+}
+
+fn duration_to_json(message: &MessageValue) -> Result<Json, JsonError> {
+    let seconds = int_field(message, "seconds");
+    let nanos = int_field(message, "nanos");
+    if seconds.abs() > MAX_DURATION
+        || nanos.abs() >= NANOS_PER_SECOND
+        || (seconds < 0 && nanos > 0)
+        || (seconds > 0 && nanos < 0)
+    {
+        return Err(invalid_message(message));
+    }
+    let sign = if seconds < 0 || nanos < 0 { "-" } else { "" };
+    Ok(Json::String(format!(
+        "{}{}{}s",
+        sign,
+        seconds.abs(),
+        fraction(nanos.abs())
+    )))
+}
+
+// Seconds with optional fraction and `s` suffix, like `-1.5s`
+fn parse_duration(text: &str) -> Option<(i64, i64)> {
+    let text = text.strip_suffix('s')?;
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(x) => (true, x),
+        None => (false, text),
+    };
+    let len = text.bytes().take_while(u8::is_ascii_digit).count();
+    let seconds: i64 = text[..len].parse().ok()?;
+    let (nanos, rest) = parse_fraction(&text[len..])?;
+    if !rest.is_empty() || seconds > MAX_DURATION {
+        return None;
+    }
+    Some(if negative {
+        (-seconds, -nanos)
+    } else {
+        (seconds, nanos)
+    })
+}
+
+fn duration_from_json(
+    desc: &MessageDesc,
+    json: &Json,
+) -> Result<MessageValue, JsonError> {
+    let (seconds, nanos) = match json {
+        Json::String(x) => parse_duration(x),
+        _ => None,
+    }
+    .ok_or_else(|| invalid(desc, json))?;
+    Ok(well_known_value(
+        desc,
+        vec![
+            ("seconds", Value::I64(seconds)),
+            ("nanos", Value::I32(nanos as i32)),
+        ],
+    ))
+}
+
+// Type name is the part of URL after last `/`
+fn any_type_name(url: &str) -> &str {
+    url.rsplit('/').next().unwrap_or(url)
+}
+
+fn any_to_json(pool: &Pool, message: &MessageValue) -> Result<Json, JsonError> {
+    let url = match message.get("type_url") {
+        Some(Value::String(x)) => x.as_str(),
+        _ => "",
+    };
+    let bytes = match message.get("value") {
+        Some(Value::Bytes(x)) => x.as_slice(),
+        _ => &[],
+    };
+    let mut object = serde_json::Map::new();
+    if url.is_empty() {
+        return match bytes.is_empty() {
+            true => Ok(Json::Object(object)),
+            false => Err(invalid_message(message)),
+        };
+    }
+    let packed = pool.decode(any_type_name(url), bytes)?;
+    object.insert("@type".into(), Json::String(url.into()));
+    match message_to_json(pool, &packed)? {
+        Json::Object(fields) if !has_special_json(&packed.type_name) => {
+            object.extend(fields)
+        }
+        json => {
+            object.insert("value".into(), json);
+        }
+    }
+    Ok(Json::Object(object))
+}
+
+fn any_from_json(
+    pool: &Pool,
+    desc: &MessageDesc,
+    json: &Json,
+) -> Result<MessageValue, JsonError> {
+    let object = match json {
+        Json::Object(x) => x,
+        _ => return Err(invalid(desc, json)),
+    };
+    let url = match object.get("@type") {
+        Some(Json::String(x)) => x,
+        None if object.is_empty() => return Ok(well_known_value(desc, vec![])),
+        _ => return Err(invalid(desc, json)),
+    };
+    let name = any_type_name(url);
+    let packed_desc = pool
+        .message(name)
+        .ok_or_else(|| EncodingError::UnknownMessage(name.to_string()))?;
+    let packed = if has_special_json(&packed_desc.name) {
+        let value = object.get("value").ok_or_else(|| invalid(desc, json))?;
+        message_from_json(pool, packed_desc, value)?
+    } else {
+        let mut fields = object.clone();
+        fields.remove("@type");
+        message_from_json(pool, packed_desc, &Json::Object(fields))?
+    };
+    Ok(well_known_value(
+        desc,
+        vec![
+            ("type_url", Value::String(url.clone())),
+            ("value", Value::Bytes(pool.encode(&packed)?)),
+        ],
+    ))
+}
+
+const BASE64: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard base64, with padding
+pub fn base64_encode(bytes: &[u8]) -> String {
+    let mut res = String::new();
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                res.push(BASE64[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                res.push('=');
+            }
+        }
+    }
+    res
+}
+
+/// Accepts both standard and URL-safe alphabet, with or without padding
+pub fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    let mut res = vec![];
+    let mut n = 0u32;
+    let mut bits = 0;
+    for c in text.trim_end_matches('=').bytes() {
+        let x = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            _ => return None,
+        };
+        n = n << 6 | x as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            res.push((n >> bits) as u8);
+        }
+    }
+    Some(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64() {
+        let cases: &[(&[u8], &str)] = &[
+            (b"", ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"\xfb\xff", "+/8="),
+        ];
+        for (bytes, text) in cases {
+            assert_eq!(base64_encode(bytes), *text);
+            assert_eq!(base64_decode(text).unwrap(), *bytes);
+        }
+        assert_eq!(base64_decode("-_8").unwrap(), b"\xfb\xff");
+        assert!(base64_decode("Zm9v!").is_none());
+    }
+
+    #[test]
+    fn mapping() {
+        let source = r#"
+            syntax = "proto3";
+            package test;
+            message Foo {
+                enum Kind { A = 0; B = 1; }
+                int32 small_id = 1;
+                uint64 big_id = 2;
+                Kind kind = 3;
+                bytes data = 4;
+                repeated float values = 5;
+                map<int32, string> names = 6;
+                Foo next = 7;
+                map<bool, int32> flags = 8;
+                bool on = 9;
+            }
+        "#;
+        let pool = Pool::from_source(source).unwrap();
+        let json: Json = serde_json::from_str(
+            r#"{
+                "smallId": -3,
+                "big_id": "18446744073709551615",
+                "kind": "B",
+                "data": "AQID",
+                "values": [1.5, "NaN"],
+                "names": {"7": "seven"},
+                "next": {"kind": 1}
+            }"#,
+        )
+        .unwrap();
+        let value = pool.from_json("test.Foo", &json).unwrap();
+        let bytes = pool.encode(&value).unwrap();
+        let decoded = pool.decode("test.Foo", &bytes).unwrap();
+        assert_eq!(
+            pool.to_json(&decoded).unwrap().to_string(),
+            r#"{"smallId":-3,"bigId":"18446744073709551615","kind":"B","data":"AQID","values":[1.5,"NaN"],"names":{"7":"seven"},"next":{"kind":"B"}}"#
+        );
+
+        let json = serde_json::json!({ "unknown": 1 });
+        assert!(pool.from_json("test.Foo", &json).is_err());
+        let json = serde_json::json!({ "smallId": 3000000000u32 });
+        assert!(pool.from_json("test.Foo", &json).is_err());
+
+        // Floats out of f32 range aren't rounded to infinity
+        let json = serde_json::json!({ "values": [3.4e38, "-Infinity"] });
+        assert!(pool.from_json("test.Foo", &json).is_ok());
+        let json = serde_json::json!({ "values": [1e300] });
+        assert!(pool.from_json("test.Foo", &json).is_err());
+
+        // Bools are strings only as map keys
+        let json = serde_json::json!({ "flags": {"true": 1}, "on": true });
+        let value = pool.from_json("test.Foo", &json).unwrap();
+        assert_eq!(pool.to_json(&value).unwrap(), json);
+        let json = serde_json::json!({ "on": "true" });
+        assert!(pool.from_json("test.Foo", &json).is_err());
+        let json = serde_json::json!({ "flags": {"yes": 1} });
+        assert!(pool.from_json("test.Foo", &json).is_err());
+    }
+
+    #[test]
+    fn well_known_types() {
+        let source = r#"
+            syntax = "proto3";
+            package test;
+            message Event {
+                google.protobuf.Timestamp at = 1;
+                google.protobuf.Duration took = 2;
+                google.protobuf.Int64Value count = 3;
+                google.protobuf.StringValue label = 4;
+                repeated google.protobuf.Any details = 5;
+            }
+        "#;
+        let pool = Pool::from_source(source).unwrap();
+        let json = serde_json::json!({
+            "at": "1972-01-01T10:00:20.021Z",
+            "took": "-1.000340012s",
+            "count": "-5",
+            "label": "",
+            "details": [
+                {"@type": "type.googleapis.com/test.Event", "count": "1"},
+                {"@type": "/google.protobuf.Duration", "value": "2s"},
+                {},
+            ],
+        });
+        let value = pool.from_json("test.Event", &json).unwrap();
+        let bytes = pool.encode(&value).unwrap();
+        let decoded = pool.decode("test.Event", &bytes).unwrap();
+        assert_eq!(pool.to_json(&decoded).unwrap(), json);
+        let at = match decoded.get("at") {
+            Some(Value::Message(x)) => x,
+            _ => panic!("Timestamp missing"),
+        };
+        assert_eq!(at.get("seconds"), Some(&Value::I64(63_108_020)));
+        assert_eq!(at.get("nanos"), Some(&Value::I32(21_000_000)));
+
+        let cases = [
+            ("1970-01-01T00:00:00Z", (0, 0)),
+            ("1969-12-31T23:59:59.500Z", (-1, 500_000_000)),
+            ("0001-01-01T00:00:00Z", (MIN_TIMESTAMP, 0)),
+            ("9999-12-31T23:59:59.000001Z", (MAX_TIMESTAMP, 1000)),
+            ("2000-02-29T12:00:00.000000001Z", (951_825_600, 1)),
+        ];
+        for (text, parsed) in cases {
+            assert_eq!(parse_timestamp(text), Some(parsed), "{}", text);
+            let json = serde_json::json!({ "at": text });
+            let value = pool.from_json("test.Event", &json).unwrap();
+            assert_eq!(pool.to_json(&value).unwrap(), json);
+        }
+        assert_eq!(
+            parse_timestamp("1970-01-01T01:30:00.25+01:30"),
+            Some((0, 250_000_000))
+        );
+        assert_eq!(parse_timestamp("1970-01-01T00:00:00-00:01"), Some((60, 0)));
+        assert_eq!(parse_duration("0.5s"), Some((0, 500_000_000)));
+        assert_eq!(parse_duration("-0.5s"), Some((0, -500_000_000)));
+
+        let invalid = [
+            serde_json::json!({ "at": "2001-02-29T00:00:00Z" }),
+            serde_json::json!({ "at": "1970-01-01T24:00:00Z" }),
+            serde_json::json!({ "at": "1970-01-01 00:00:00Z" }),
+            serde_json::json!({ "at": "1970-01-01T00:00:00" }),
+            serde_json::json!({ "at": "1970-01-01T00:00:00.Z" }),
+            serde_json::json!({ "at": "1970-01-01T00:00:00.0000000001Z" }),
+            serde_json::json!({ "at": "10000-01-01T00:00:00Z" }),
+            serde_json::json!({ "at": 0 }),
+            serde_json::json!({ "took": "1" }),
+            serde_json::json!({ "took": "s" }),
+            serde_json::json!({ "took": "1.s" }),
+            serde_json::json!({ "took": "315576000001s" }),
+            serde_json::json!({ "count": "x" }),
+            serde_json::json!({ "details": [{ "count": "1" }] }),
+            serde_json::json!({ "details": [{ "@type": "/test.Nope" }] }),
+            serde_json::json!({
+                "details": [{ "@type": "/google.protobuf.Duration" }],
+            }),
+        ];
+        for json in &invalid {
+            assert!(pool.from_json("test.Event", json).is_err(), "{}", json);
+        }
+
+        // Out of range values can't be printed
+        let mut took = pool
+            .from_json("google.protobuf.Duration", &"1s".into())
+            .unwrap();
+        took.fields.push(FieldValue {
+            number: 2,
+            name: "nanos".into(),
+            value: Value::I32(-1),
+        });
+        assert!(pool.to_json(&took).is_err());
+    }
+}
@@ -1,39 +1,56 @@
-use std::{
-    io,
-    path::{Path, PathBuf},
-};
+use std::{io, path::Path};
 
+#[cfg(feature = "bytes")]
+pub use bytes;
 pub use protobuf_parser;
 
 pub mod codegen;
+pub mod config;
+pub mod descriptor;
+pub mod dynamic;
 pub mod encoding;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod rpc;
+mod source;
+pub mod text;
+pub mod well_known;
 
-pub fn generate(proto_file: impl AsRef<Path>) -> Result<(), io::Error> {
+pub use config::{BytesType, Config, FieldStyle, Layout, MapType};
+
+/// Parsed .proto file, along with details `protobuf_parser` doesn't keep
+#[derive(Debug)]
+pub struct ProtoFile {
+    pub descriptor: protobuf_parser::FileDescriptor,
+    pub(crate) info: source::SourceInfo,
+}
+
+/// Parses .proto file contents
+pub fn parse_str(source: &str) -> Result<ProtoFile, io::Error> {
+    let info = source::scan(source);
+    let source = source::strip_unsupported(source);
+    let descriptor =
+        protobuf_parser::FileDescriptor::parse(source).map_err(|e| {
+            let msg = format!("{:?}", e);
+            io::Error::new(io::ErrorKind::InvalidInput, msg)
+        })?;
+    Ok(ProtoFile { descriptor, info })
+}
+
+/// Reads and parses .proto file
+pub fn parse(proto_file: impl AsRef<Path>) -> Result<ProtoFile, io::Error> {
     let proto_file = proto_file.as_ref();
     let bytes = std::fs::read(proto_file).map_err(|e| {
         let msg = format!("Error reading {}: {}", proto_file.display(), e);
-        io::Error::new(io::ErrorKind::Other, msg)
+        io::Error::other(msg)
     })?;
-    let proto = protobuf_parser::FileDescriptor::parse(bytes).map_err(|e| {
-        let msg =
-            format!("Error when parsing {}: {:?}", proto_file.display(), e);
+    parse_str(&String::from_utf8_lossy(&bytes)).map_err(|e| {
+        let msg = format!("Error when parsing {}: {}", proto_file.display(), e);
         io::Error::new(io::ErrorKind::InvalidInput, msg)
-    })?;
-    let code = codegen::gen_proto(&proto).map_err(|e| {
-        let msg = format!(
-            "Error when generating code for {}: {:?}",
-            proto_file.display(),
-            e
-        );
-        io::Error::new(io::ErrorKind::InvalidInput, msg)
-    })?;
+    })
+}
 
-    let rs_file = proto_file.with_extension("rs");
-    let out_dir = std::env::var_os("OUT_DIR").ok_or_else(|| {
-        let msg = format!("Missing \"OUT_DIR\" env var");
-        io::Error::new(io::ErrorKind::Other, msg)
-    })?;
-    let out_path = PathBuf::from(out_dir).join(rs_file);
-    std::fs::write(out_path, code)?;
-    Ok(())
+/// Generates code for .proto file into `OUT_DIR`, with default [`Config`]
+pub fn generate(proto_file: impl AsRef<Path>) -> Result<(), io::Error> {
+    Config::new().compile(&[proto_file])
 }
@@ -0,0 +1,226 @@
+// Transport for clients generated from `service` definitions
+//
+// Over a stream, each call is a length-prefixed request frame
+// (`method`, `payload`), answered with a length-prefixed response frame
+// (`payload` or `error`), the same framing kRPC uses.
+
+use std::{
+    io::{Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+};
+
+use crate::encoding::*;
+
+#[derive(Debug, thiserror::Error)]
+pub enum RpcError {
+    #[error("Encoding error: {0}")]
+    Encoding(#[from] EncodingError),
+    #[error("Error from server: {0}")]
+    Server(String),
+}
+
+impl From<std::io::Error> for RpcError {
+    fn from(e: std::io::Error) -> Self {
+        RpcError::Encoding(e.into())
+    }
+}
+
+/// Carries encoded requests to a server. Generated service clients
+/// are implemented for every transport.
+pub trait Transport {
+    /// Sends request for `method` (`/package.Service/Method`),
+    /// and returns encoded response
+    fn call_raw(
+        &mut self,
+        method: &str,
+        request: Vec<u8>,
+    ) -> Result<Vec<u8>, RpcError>;
+
+    fn call<Req: Encode, Res: Decode>(
+        &mut self,
+        method: &str,
+        request: &Req,
+    ) -> Result<Res, RpcError> {
+        let mut bytes = vec![];
+        request.encode(&mut bytes)?;
+        let response = self.call_raw(method, bytes)?;
+        Ok(Res::decode(response.as_slice())?)
+    }
+}
+
+/// Request frame
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct RpcRequest {
+    pub method: String,
+    pub payload: Vec<u8>,
+}
+
+/// Response frame, with either payload or error
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct RpcResponse {
+    pub payload: Vec<u8>,
+    pub error: String,
+}
+
+impl Encode for RpcRequest {
+    fn size(&self) -> u32 {
+        key_size(1) + self.method.size() + key_size(2) + self.payload.size()
+    }
+
+    fn encode<W: Write>(&self, mut w: W) -> Result<(), EncodingError> {
+        encode_field(&mut w, 1, 2, &self.method)?;
+        encode_field(&mut w, 2, 2, &self.payload)
+    }
+}
+
+impl Decode for RpcRequest {
+    fn decode<R: Read>(mut r: R) -> Result<Self, EncodingError> {
+        let mut res = Self::default();
+        while let Some((number, wire_type)) = decode_key(&mut r)? {
+            match (number, wire_type) {
+                (1, 2) => res.method = String::decode_as_field(&mut r)?,
+                (2, 2) => res.payload = Vec::decode_as_field(&mut r)?,
+                _ => skip_field(&mut r, wire_type)?,
+            }
+        }
+        Ok(res)
+    }
+}
+
+impl Encode for RpcResponse {
+    fn size(&self) -> u32 {
+        key_size(1) + self.payload.size() + key_size(2) + self.error.size()
+    }
+
+    fn encode<W: Write>(&self, mut w: W) -> Result<(), EncodingError> {
+        encode_field(&mut w, 1, 2, &self.payload)?;
+        encode_field(&mut w, 2, 2, &self.error)
+    }
+}
+
+impl Decode for RpcResponse {
+    fn decode<R: Read>(mut r: R) -> Result<Self, EncodingError> {
+        let mut res = Self::default();
+        while let Some((number, wire_type)) = decode_key(&mut r)? {
+            match (number, wire_type) {
+                (1, 2) => res.payload = Vec::decode_as_field(&mut r)?,
+                (2, 2) => res.error = String::decode_as_field(&mut r)?,
+                _ => skip_field(&mut r, wire_type)?,
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// Blocking transport over any stream, usually [`TcpStream`]
+pub struct StreamTransport<S = TcpStream> {
+    stream: S,
+    decoder: FrameDecoder,
+}
+
+impl StreamTransport {
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> Result<Self, RpcError> {
+        Ok(Self::new(TcpStream::connect(addr)?))
+    }
+}
+
+impl<S: Read + Write> StreamTransport<S> {
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            decoder: FrameDecoder::new(),
+        }
+    }
+
+    pub fn stream(&self) -> &S {
+        &self.stream
+    }
+}
+
+impl<S: Read + Write> Transport for StreamTransport<S> {
+    fn call_raw(
+        &mut self,
+        method: &str,
+        request: Vec<u8>,
+    ) -> Result<Vec<u8>, RpcError> {
+        let request = RpcRequest {
+            method: method.to_string(),
+            payload: request,
+        };
+        request.encode_with_len(&mut self.stream)?;
+        self.stream.flush()?;
+        let response = loop {
+            if let Some(response) = self.decoder.decode::<RpcResponse>()? {
+                break response;
+            }
+            if self.decoder.read_from(&mut self.stream)? == 0 {
+                let eof = std::io::ErrorKind::UnexpectedEof;
+                return Err(std::io::Error::from(eof).into());
+            }
+        };
+        if response.error.is_empty() {
+            Ok(response.payload)
+        } else {
+            Err(RpcError::Server(response.error))
+        }
+    }
+}
+
+/// Answers requests coming from `stream` with `handler`, until
+/// the other side closes it. Handler gets method name and encoded request.
+pub fn serve<S: Read + Write>(
+    mut stream: S,
+    mut handler: impl FnMut(&str, &[u8]) -> Result<Vec<u8>, String>,
+) -> Result<(), RpcError> {
+    let mut decoder = FrameDecoder::new();
+    loop {
+        while let Some(request) = decoder.decode::<RpcRequest>()? {
+            let response = match handler(&request.method, &request.payload) {
+                Ok(payload) => RpcResponse {
+                    payload,
+                    error: String::new(),
+                },
+                Err(error) => RpcResponse {
+                    payload: vec![],
+                    error,
+                },
+            };
+            response.encode_with_len(&mut stream)?;
+            stream.flush()?;
+        }
+        if decoder.read_from(&mut stream)? == 0 {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            serve(stream, |method, request| match method {
+                "/test.Echo/Twice" => {
+                    let x = String::decode(request).unwrap();
+                    Ok((x.clone() + &x).encode_to_vec().unwrap())
+                }
+                _ => Err(format!("Unknown method {}", method)),
+            })
+            .unwrap()
+        });
+
+        let mut client = StreamTransport::connect(addr).unwrap();
+        let res: String = client.call("/test.Echo/Twice", &"ab").unwrap();
+        assert_eq!(res, "abab");
+        let res = client.call::<_, String>("/test.Echo/Nope", &"ab");
+        assert!(matches!(res, Err(RpcError::Server(e)) if e.contains("Nope")));
+        drop(client);
+        server.join().unwrap();
+    }
+}
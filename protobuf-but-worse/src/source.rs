@@ -0,0 +1,413 @@
+// Lightweight scanner over raw .proto text
+// `protobuf_parser` rejects a few constructs we don't generate code for,
+// so we blank them out before handing the source over to it.
+// It also drops some details we care about, which we collect here.
+
+use std::collections::{HashMap, HashSet};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum TokenKind {
+    Word,
+    Str,
+    Symbol,
+    Comment,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    pub start: usize,
+}
+
+impl<'a> Token<'a> {
+    pub fn end(&self) -> usize {
+        self.start + self.text.len()
+    }
+
+    pub fn is_symbol(&self, c: char) -> bool {
+        self.kind == TokenKind::Symbol && self.text.starts_with(c)
+    }
+
+    pub fn is_word(&self, word: &str) -> bool {
+        self.kind == TokenKind::Word && self.text == word
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '.'
+}
+
+pub(crate) fn tokenize(src: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut chars = src.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let end = match c {
+            c if c.is_whitespace() => continue,
+            '/' if src[start..].starts_with("//") => {
+                src[start..].find('\n').map_or(src.len(), |i| start + i)
+            }
+            '/' if src[start..].starts_with("/*") => src[start + 2..]
+                .find("*/")
+                .map_or(src.len(), |i| start + 2 + i + 2),
+            '"' | '\'' => {
+                let mut end = src.len();
+                let mut escaped = false;
+                for (i, x) in src[start + 1..].char_indices() {
+                    match x {
+                        _ if escaped => escaped = false,
+                        '\\' => escaped = true,
+                        x if x == c => {
+                            end = start + 1 + i + 1;
+                            break;
+                        }
+                        _ => (),
+                    }
+                }
+                end
+            }
+            c if is_word_char(c) => src[start..]
+                .find(|x| !is_word_char(x))
+                .map_or(src.len(), |i| start + i),
+            c => start + c.len_utf8(),
+        };
+        while chars.peek().is_some_and(|&(i, _)| i < end) {
+            chars.next();
+        }
+        let text = &src[start..end];
+        let kind = match c {
+            '/' if text.starts_with("//") || text.starts_with("/*") => {
+                TokenKind::Comment
+            }
+            '"' | '\'' => TokenKind::Str,
+            c if is_word_char(c) => TokenKind::Word,
+            _ => TokenKind::Symbol,
+        };
+        tokens.push(Token { kind, text, start });
+    }
+    tokens
+}
+
+// Replaces everything but newlines in range with spaces,
+// so that positions and line numbers stay the same
+fn blank(src: &mut String, start: usize, end: usize) {
+    let blanked: String = src[start..end]
+        .chars()
+        .map(|c| if c == '\n' { '\n' } else { ' ' })
+        .collect();
+    src.replace_range(start..end, &blanked);
+}
+
+/// Removes `extensions` ranges and nested `extend` blocks from messages,
+/// and `service` blocks.
+///
+/// We don't generate anything for extensions, and on the wire
+/// extension fields are skipped like any other unknown field.
+/// Services are collected by [`scan`] instead, as `protobuf_parser`
+/// fails on streaming rpcs and rpc options.
+pub fn strip_unsupported(src: &str) -> String {
+    let tokens: Vec<_> = tokenize(src)
+        .into_iter()
+        .filter(|t| t.kind != TokenKind::Comment)
+        .collect();
+    let mut ranges = vec![];
+    // Is each currently open block a message?
+    let mut blocks = vec![];
+    let mut statement_start = true;
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        let in_message = blocks.last().copied().unwrap_or(false);
+        let is_service = blocks.is_empty() && token.is_word("service");
+        if statement_start
+            && (is_service
+                || in_message
+                    && (token.is_word("extensions") || token.is_word("extend")))
+        {
+            // `extensions ...;` ends on semicolon, `extend X { ... }`
+            // and `service X { ... }` on brace
+            let is_block = !token.is_word("extensions");
+            let mut depth = 0;
+            let mut j = i;
+            while j < tokens.len() {
+                let t = tokens[j];
+                if t.is_symbol('{') {
+                    depth += 1;
+                } else if t.is_symbol('}') {
+                    depth -= 1;
+                    if is_block && depth == 0 {
+                        break;
+                    }
+                } else if !is_block && t.is_symbol(';') {
+                    break;
+                }
+                j += 1;
+            }
+            let end = tokens.get(j).map_or(src.len(), Token::end);
+            ranges.push((token.start, end));
+            i = j + 1;
+            statement_start = true;
+            continue;
+        }
+        if token.is_symbol('{') {
+            let is_message = i >= 2 && tokens[i - 2].is_word("message");
+            blocks.push(is_message);
+        } else if token.is_symbol('}') {
+            blocks.pop();
+        }
+        statement_start = token.is_symbol('{')
+            || token.is_symbol('}')
+            || token.is_symbol(';');
+        i += 1;
+    }
+
+    let mut res = src.to_string();
+    for (start, end) in ranges {
+        blank(&mut res, start, end);
+    }
+    res
+}
+
+/// Details of .proto source that `protobuf_parser` doesn't keep
+#[derive(Default, Debug)]
+pub(crate) struct SourceInfo {
+    /// Fully qualified names of fields with explicit `optional` label
+    pub explicit_optional: HashSet<String>,
+    pub services: Vec<ServiceInfo>,
+    /// Leading and trailing comments of messages, fields, enums,
+    /// enum values (`.package.Enum.VALUE`), services and rpcs,
+    /// by fully qualified name
+    pub docs: HashMap<String, String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ServiceInfo {
+    pub name: String,
+    pub methods: Vec<MethodInfo>,
+}
+
+#[derive(Debug)]
+pub(crate) struct MethodInfo {
+    pub name: String,
+    /// Request and response types, as written in source
+    pub input: String,
+    pub output: String,
+    pub client_streaming: bool,
+    pub server_streaming: bool,
+}
+
+// rpc <name> ( [stream] <type> ) returns ( [stream] <type> )
+fn scan_method(tokens: &[Token]) -> Option<MethodInfo> {
+    let mut i = 0;
+    let mut next = || {
+        i += 1;
+        tokens.get(i).copied()
+    };
+    let name = next().filter(|t| t.kind == TokenKind::Word)?;
+    let mut types = vec![];
+    for keyword in &[None, Some("returns")] {
+        if let Some(keyword) = keyword {
+            next().filter(|t| t.is_word(keyword))?;
+        }
+        next().filter(|t| t.is_symbol('('))?;
+        let mut typ = next()?;
+        let streaming = typ.is_word("stream");
+        if streaming {
+            typ = next()?;
+        }
+        next().filter(|t| t.is_symbol(')'))?;
+        types.push((typ.text.to_string(), streaming));
+    }
+    let (output, server_streaming) = types.pop()?;
+    let (input, client_streaming) = types.pop()?;
+    Some(MethodInfo {
+        name: name.text.to_string(),
+        input,
+        output,
+        client_streaming,
+        server_streaming,
+    })
+}
+
+// Text of comment token, without `//` or `/* */` markers
+fn comment_text(comment: &str) -> String {
+    let strip = |line: &str| {
+        let line = line.strip_prefix(' ').unwrap_or(line);
+        line.trim_end().to_string()
+    };
+    if let Some(line) = comment.strip_prefix("//") {
+        return strip(line);
+    }
+    let body = comment.trim_start_matches("/*").trim_end_matches("*/");
+    let lines: Vec<_> = body
+        .lines()
+        .map(|line| {
+            let line = line.trim_start();
+            strip(line.strip_prefix('*').unwrap_or(line))
+        })
+        .collect();
+    lines.join("\n").trim_matches('\n').to_string()
+}
+
+// Comments right above `all[i]`, not separated from it by a blank line,
+// and not trailing other tokens
+fn leading_comment(src: &str, all: &[Token], i: usize) -> Option<String> {
+    let mut start = i;
+    while start > 0 {
+        let comment = all[start - 1];
+        let gap = &src[comment.end()..all[start].start];
+        if comment.kind != TokenKind::Comment || gap.matches('\n').count() > 1 {
+            break;
+        }
+        let before = match start.checked_sub(2) {
+            Some(j) => &src[all[j].end()..comment.start],
+            None => "\n",
+        };
+        if !before.contains('\n') {
+            break;
+        }
+        start -= 1;
+    }
+    let lines: Vec<_> =
+        all[start..i].iter().map(|c| comment_text(c.text)).collect();
+    (start < i).then(|| lines.join("\n"))
+}
+
+// Comment after `all[i]`, on the same line
+fn trailing_comment(src: &str, all: &[Token], i: usize) -> Option<String> {
+    let comment = all.get(i + 1)?;
+    let gap = &src[all[i].end()..comment.start];
+    (comment.kind == TokenKind::Comment && !gap.contains('\n'))
+        .then(|| comment_text(comment.text))
+}
+
+pub(crate) fn scan(src: &str) -> SourceInfo {
+    let all = tokenize(src);
+    // Position in `all` of each token that is not a comment
+    let positions: Vec<_> = (0..all.len())
+        .filter(|&i| all[i].kind != TokenKind::Comment)
+        .collect();
+    let tokens: Vec<_> = positions.iter().map(|&i| all[i]).collect();
+    let mut info = SourceInfo::default();
+    let mut package = String::new();
+    // Message name for each open block, `None` for other blocks
+    let mut blocks: Vec<Option<&str>> = vec![];
+    // Fully qualified name of message, enum or service each open block
+    // declares things in, `None` for other blocks
+    let mut scopes: Vec<Option<String>> = vec![];
+    let mut in_service = false;
+    let mut statement_start = true;
+    let mut statement = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        if statement_start {
+            statement = i;
+        }
+        // Name declared by statement ending on this token
+        let mut declared = None;
+        if statement_start && blocks.is_empty() && token.is_word("package") {
+            if let Some(name) = tokens.get(i + 1) {
+                package = format!(".{}", name.text);
+            }
+        }
+        if statement_start && blocks.is_empty() && token.is_word("service") {
+            if let Some(name) = tokens.get(i + 1) {
+                info.services.push(ServiceInfo {
+                    name: name.text.to_string(),
+                    methods: vec![],
+                });
+            }
+        }
+        if statement_start
+            && in_service
+            && blocks.len() == 1
+            && token.is_word("rpc")
+        {
+            if let (Some(service), Some(method)) =
+                (info.services.last_mut(), scan_method(&tokens[i..]))
+            {
+                service.methods.push(method);
+            }
+        }
+        let in_message = matches!(blocks.last(), Some(Some(_)));
+        if statement_start && in_message && token.is_word("optional") {
+            // optional <type> <name> = <number>;
+            if let Some(name) = tokens.get(i + 2) {
+                let mut path = package.clone();
+                for message in blocks.iter().flatten() {
+                    path = format!("{}.{}", path, message);
+                }
+                info.explicit_optional
+                    .insert(format!("{}.{}", path, name.text));
+            }
+        }
+        let parent = match scopes.last() {
+            Some(scope) => scope.clone(),
+            None => Some(package.clone()),
+        };
+        let first = tokens[statement];
+        if token.is_symbol('{') {
+            let is_message = i >= 2 && tokens[i - 2].is_word("message");
+            blocks.push(is_message.then(|| tokens[i - 1].text));
+            in_service |=
+                blocks.len() == 1 && i >= 2 && tokens[i - 2].is_word("service");
+            let is_declaration = ["message", "enum", "service"]
+                .iter()
+                .any(|x| first.is_word(x));
+            let scope = match (&parent, statement + 2 == i) {
+                (Some(parent), true) if is_declaration => {
+                    let name = format!("{}.{}", parent, tokens[i - 1].text);
+                    declared = Some(name.clone());
+                    Some(name)
+                }
+                // Fields of oneof belong to the message
+                _ if first.is_word("oneof") => parent.clone(),
+                _ => None,
+            };
+            if first.is_word("rpc") {
+                declared = parent
+                    .as_ref()
+                    .zip(tokens.get(statement + 1))
+                    .map(|(parent, name)| format!("{}.{}", parent, name.text));
+            }
+            scopes.push(scope);
+        } else if token.is_symbol('}') {
+            blocks.pop();
+            scopes.pop();
+            in_service &= !blocks.is_empty();
+        } else if token.is_symbol(';') && !scopes.is_empty() {
+            // Field, enum value or rpc, named before `=` or after `rpc`
+            let skipped = ["option", "reserved", "extensions"];
+            let name = if first.is_word("rpc") {
+                tokens.get(statement + 1)
+            } else if skipped.iter().any(|x| first.is_word(x)) {
+                None
+            } else {
+                tokens[statement..i]
+                    .iter()
+                    .position(|t| t.is_symbol('='))
+                    .filter(|&j| j > 0)
+                    .map(|j| &tokens[statement + j - 1])
+            };
+            declared = parent
+                .as_ref()
+                .zip(name)
+                .map(|(parent, name)| format!("{}.{}", parent, name.text));
+        }
+        if let Some(name) = declared {
+            let doc: Vec<_> = vec![
+                leading_comment(src, &all, positions[statement]),
+                trailing_comment(src, &all, positions[i]),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            if !doc.is_empty() {
+                info.docs.insert(name, doc.join("\n"));
+            }
+        }
+        statement_start = token.is_symbol('{')
+            || token.is_symbol('}')
+            || token.is_symbol(';');
+    }
+    info
+}
@@ -0,0 +1,761 @@
+// Protobuf text format, on top of `dynamic` values
+// https://developers.google.com/protocol-buffers/docs/text-format-spec
+
+use std::{convert::TryFrom, fmt};
+
+use crate::{
+    descriptor::{Kind, Label, MessageDesc, Pool},
+    dynamic::{escape_bytes, FieldValue, MessageValue, RawValue, Value},
+    encoding::*,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum TextError {
+    #[error("Encoding error: {0}")]
+    Encoding(#[from] EncodingError),
+    #[error("Line {0}: {1}")]
+    Parse(usize, String),
+}
+
+/// Prints message in text format
+pub fn to_string<T: Encode + MessageName>(
+    pool: &Pool,
+    message: &T,
+) -> Result<String, EncodingError> {
+    let mut bytes = vec![];
+    message.encode(&mut bytes)?;
+    Ok(pool.decode(T::FULL_NAME, &bytes)?.to_string())
+}
+
+/// Parses message from text format
+pub fn from_str<T: Decode + MessageName>(
+    pool: &Pool,
+    text: &str,
+) -> Result<T, TextError> {
+    let value = pool.parse_text(T::FULL_NAME, text)?;
+    Ok(T::decode(pool.encode(&value)?.as_slice())?)
+}
+
+/// Text format, with two space indent. Unknown fields are printed
+/// with their numbers, and can't be parsed back.
+impl fmt::Display for MessageValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_message(f, self, 0)
+    }
+}
+
+fn write_message(
+    f: &mut fmt::Formatter<'_>,
+    message: &MessageValue,
+    indent: usize,
+) -> fmt::Result {
+    for field in &message.fields {
+        match &field.value {
+            Value::List(values) => {
+                for value in values {
+                    write_field(f, &field.name, value, indent)?;
+                }
+            }
+            Value::Map(entries) => {
+                let pad = "  ".repeat(indent);
+                for (key, value) in entries {
+                    writeln!(f, "{}{} {{", pad, field.name)?;
+                    write_field(f, "key", key, indent + 1)?;
+                    write_field(f, "value", value, indent + 1)?;
+                    writeln!(f, "{}}}", pad)?;
+                }
+            }
+            value => write_field(f, &field.name, value, indent)?,
+        }
+    }
+    for field in &message.unknown {
+        let pad = "  ".repeat(indent);
+        match &field.value {
+            RawValue::Varint(x) => {
+                writeln!(f, "{}{}: {}", pad, field.number, x)?
+            }
+            RawValue::Fixed32(x) => {
+                writeln!(f, "{}{}: 0x{:08x}", pad, field.number, x)?
+            }
+            RawValue::Fixed64(x) => {
+                writeln!(f, "{}{}: 0x{:016x}", pad, field.number, x)?
+            }
+            RawValue::LengthDelimited(x) => {
+                writeln!(f, "{}{}: \"{}\"", pad, field.number, escape_bytes(x))?
+            }
+            RawValue::Group(_) => {
+                writeln!(f, "{}{} {{ ... }}", pad, field.number)?
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_field(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    value: &Value,
+    indent: usize,
+) -> fmt::Result {
+    let pad = "  ".repeat(indent);
+    match value {
+        Value::Message(message) => {
+            writeln!(f, "{}{} {{", pad, name)?;
+            write_message(f, message, indent + 1)?;
+            writeln!(f, "{}}}", pad)
+        }
+        value => {
+            write!(f, "{}{}: ", pad, name)?;
+            write_scalar(f, value)?;
+            writeln!(f)
+        }
+    }
+}
+
+fn write_scalar(f: &mut fmt::Formatter<'_>, value: &Value) -> fmt::Result {
+    match value {
+        Value::Bool(x) => write!(f, "{}", x),
+        Value::I32(x) => write!(f, "{}", x),
+        Value::I64(x) => write!(f, "{}", x),
+        Value::U32(x) => write!(f, "{}", x),
+        Value::U64(x) => write!(f, "{}", x),
+        Value::F32(x) => write_float(f, *x as f64, x.to_string()),
+        Value::F64(x) => write_float(f, *x, x.to_string()),
+        Value::String(x) => write!(f, "\"{}\"", escape_bytes(x.as_bytes())),
+        Value::Bytes(x) => write!(f, "\"{}\"", escape_bytes(x)),
+        Value::Enum(_, Some(name)) => write!(f, "{}", name),
+        Value::Enum(number, None) => write!(f, "{}", number),
+        // Only reachable for nested lists, which protobuf doesn't have
+        Value::Message(_) | Value::List(_) | Value::Map(_) => {
+            write!(f, "{:?}", value)
+        }
+    }
+}
+
+fn write_float(
+    f: &mut fmt::Formatter<'_>,
+    x: f64,
+    text: String,
+) -> fmt::Result {
+    if x.is_nan() {
+        write!(f, "nan")
+    } else if x == f64::INFINITY {
+        write!(f, "inf")
+    } else if x == f64::NEG_INFINITY {
+        write!(f, "-inf")
+    } else {
+        write!(f, "{}", text)
+    }
+}
+
+impl Pool {
+    /// Parses text format as message `message_name`
+    pub fn parse_text(
+        &self,
+        message_name: &str,
+        text: &str,
+    ) -> Result<MessageValue, TextError> {
+        let desc = self.message(message_name).ok_or_else(|| {
+            EncodingError::UnknownMessage(message_name.to_string())
+        })?;
+        let mut parser = Parser {
+            pool: self,
+            tokens: tokenize(text)?,
+            pos: 0,
+        };
+        let message = parser.message(desc, None)?;
+        match parser.peek() {
+            None => Ok(message),
+            Some(token) => Err(parser.error(token, "expected field name")),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum TokenKind {
+    Ident,
+    Number,
+    Str,
+    Symbol,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Token<'a> {
+    kind: TokenKind,
+    text: &'a str,
+    line: usize,
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token<'_>>, TextError> {
+    let mut tokens = vec![];
+    let mut line = 1;
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let c = bytes[i];
+        let start = i;
+        let kind = match c {
+            b'\n' => {
+                line += 1;
+                i += 1;
+                continue;
+            }
+            c if c.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            b'#' => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            b'"' | b'\'' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != c {
+                    if bytes[i] == b'\n' {
+                        break;
+                    }
+                    // Skip escaped char
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                if i >= bytes.len() || bytes[i] != c {
+                    let msg = "unterminated string".to_string();
+                    return Err(TextError::Parse(line, msg));
+                }
+                i += 1;
+                TokenKind::Str
+            }
+            // Also `-inf` and `-nan`
+            c if c.is_ascii_digit()
+                || (c == b'-'
+                    && bytes.get(i + 1).is_some_and(|x| {
+                        x.is_ascii_alphanumeric() || *x == b'.'
+                    }))
+                || (c == b'.'
+                    && bytes
+                        .get(i + 1)
+                        .is_some_and(|x| x.is_ascii_digit())) =>
+            {
+                i += 1;
+                while i < bytes.len() {
+                    let x = bytes[i];
+                    let is_exponent_sign = matches!(x, b'+' | b'-')
+                        && matches!(bytes[i - 1], b'e' | b'E')
+                        && !text[start..i].starts_with("0x");
+                    if x.is_ascii_alphanumeric()
+                        || x == b'.'
+                        || is_exponent_sign
+                    {
+                        i += 1;
+                    } else {
+                        break;
+                    }
+                }
+                TokenKind::Number
+            }
+            c if c.is_ascii_alphabetic() || c == b'_' => {
+                while i < bytes.len()
+                    && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_')
+                {
+                    i += 1;
+                }
+                TokenKind::Ident
+            }
+            _ => {
+                // Whole char, in case of non-ASCII
+                i += text[i..].chars().next().map_or(1, char::len_utf8);
+                TokenKind::Symbol
+            }
+        };
+        tokens.push(Token {
+            kind,
+            text: &text[start..i],
+            line,
+        });
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    pool: &'a Pool,
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<Token<'a>> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Result<Token<'a>, TextError> {
+        let token = self.peek().ok_or_else(|| {
+            let line = self.tokens.last().map_or(1, |t| t.line);
+            TextError::Parse(line, "unexpected end of input".to_string())
+        })?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn eat(&mut self, symbol: &str) -> bool {
+        let matches = self
+            .peek()
+            .is_some_and(|t| t.kind == TokenKind::Symbol && t.text == symbol);
+        if matches {
+            self.pos += 1;
+        }
+        matches
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<(), TextError> {
+        let token = self.next()?;
+        if token.kind == TokenKind::Symbol && token.text == symbol {
+            Ok(())
+        } else {
+            Err(self.error(token, &format!("expected `{}`", symbol)))
+        }
+    }
+
+    fn error(&self, token: Token, msg: &str) -> TextError {
+        TextError::Parse(token.line, format!("{}, found `{}`", msg, token.text))
+    }
+
+    // Parses fields until `end` symbol, or end of input if `None`
+    fn message(
+        &mut self,
+        desc: &MessageDesc,
+        end: Option<&str>,
+    ) -> Result<MessageValue, TextError> {
+        let limits = DecodeLimits::current();
+        let _depth = DepthGuard::enter()?;
+        let mut fields: Vec<FieldValue> = vec![];
+        loop {
+            match (self.peek(), end) {
+                (None, None) => break,
+                (Some(t), Some(end))
+                    if t.kind == TokenKind::Symbol && t.text == end =>
+                {
+                    self.pos += 1;
+                    break;
+                }
+                _ => (),
+            }
+            let name = self.next()?;
+            let field = match desc.field_by_name(name.text) {
+                Some(field) if name.kind == TokenKind::Ident => field,
+                _ => {
+                    let msg = format!("unknown field of {}", desc.name);
+                    return Err(self.error(name, &msg));
+                }
+            };
+
+            let mut values = vec![];
+            if let Kind::Map(key_kind, value_kind) = &field.kind {
+                self.eat(":");
+                let entries = if self.eat("[") {
+                    self.list(|p| p.entry(key_kind, value_kind))?
+                } else {
+                    vec![self.entry(key_kind, value_kind)?]
+                };
+                values.extend(entries.into_iter().map(Value::List));
+            } else if let Kind::Message(name) = &field.kind {
+                let desc = self.pool.message(name).ok_or_else(|| {
+                    EncodingError::UnknownMessage(name.clone())
+                })?;
+                self.eat(":");
+                if self.eat("[") {
+                    values = self.list(|p| p.block(desc))?;
+                } else {
+                    values.push(self.block(desc)?);
+                }
+            } else {
+                self.expect(":")?;
+                if self.eat("[") {
+                    values = self.list(|p| p.scalar(&field.kind))?;
+                } else {
+                    values.push(self.scalar(&field.kind)?);
+                }
+            }
+            // Optional separator
+            if !self.eat(",") {
+                self.eat(";");
+            }
+
+            let existing = fields.iter_mut().find(|f| f.number == field.number);
+            match (existing, &field.kind, field.label) {
+                (Some(f), Kind::Map(..), _) | (Some(f), _, Label::Repeated) => {
+                    match &mut f.value {
+                        Value::List(list) => {
+                            list.extend(values);
+                            limits.check_repeated(list.len())?;
+                        }
+                        Value::Map(map) => {
+                            map.extend(values.into_iter().filter_map(entry));
+                            limits.check_repeated(map.len())?;
+                        }
+                        _ => (),
+                    }
+                }
+                (Some(_), _, _) => {
+                    let msg = "field set more than once";
+                    return Err(self.error(name, msg));
+                }
+                (None, Kind::Map(..), _) => fields.push(FieldValue {
+                    number: field.number,
+                    name: field.name.clone(),
+                    value: Value::Map(
+                        values.into_iter().filter_map(entry).collect(),
+                    ),
+                }),
+                (None, _, Label::Repeated) => fields.push(FieldValue {
+                    number: field.number,
+                    name: field.name.clone(),
+                    value: Value::List(values),
+                }),
+                (None, _, _) => {
+                    if values.len() != 1 {
+                        let msg = "list for non-repeated field";
+                        return Err(self.error(name, msg));
+                    }
+                    fields.push(FieldValue {
+                        number: field.number,
+                        name: field.name.clone(),
+                        value: values.remove(0),
+                    })
+                }
+            }
+        }
+        fields.sort_by_key(|f| f.number);
+        Ok(MessageValue {
+            type_name: desc.name.clone(),
+            fields,
+            unknown: vec![],
+        })
+    }
+
+    // `[a, b, c]`, after opening bracket
+    fn list<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T, TextError>,
+    ) -> Result<Vec<T>, TextError> {
+        let mut items = vec![];
+        if self.eat("]") {
+            return Ok(items);
+        }
+        loop {
+            items.push(item(self)?);
+            if self.eat("]") {
+                return Ok(items);
+            }
+            self.expect(",")?;
+        }
+    }
+
+    // `{ ... }` or `< ... >`
+    fn block(&mut self, desc: &MessageDesc) -> Result<Value, TextError> {
+        let end = if self.eat("{") {
+            "}"
+        } else {
+            self.expect("<")?;
+            ">"
+        };
+        Ok(Value::Message(self.message(desc, Some(end))?))
+    }
+
+    // Map entry block, returned as two-element list
+    fn entry(
+        &mut self,
+        key_kind: &Kind,
+        value_kind: &Kind,
+    ) -> Result<Vec<Value>, TextError> {
+        let end = if self.eat("{") {
+            "}"
+        } else {
+            self.expect("<")?;
+            ">"
+        };
+        let mut key = None;
+        let mut value = None;
+        while !self.eat(end) {
+            let name = self.next()?;
+            let (kind, slot) = match name.text {
+                "key" => (key_kind, &mut key),
+                "value" => (value_kind, &mut value),
+                _ => return Err(self.error(name, "expected `key` or `value`")),
+            };
+            let parsed = match kind {
+                Kind::Message(name) => {
+                    let desc = self.pool.message(name).ok_or_else(|| {
+                        EncodingError::UnknownMessage(name.clone())
+                    })?;
+                    self.eat(":");
+                    self.block(desc)?
+                }
+                kind => {
+                    self.expect(":")?;
+                    self.scalar(kind)?
+                }
+            };
+            *slot = Some(parsed);
+            if !self.eat(",") {
+                self.eat(";");
+            }
+        }
+        let key = key.unwrap_or_else(|| {
+            crate::dynamic::default_value(self.pool, key_kind)
+        });
+        let value = value.unwrap_or_else(|| {
+            crate::dynamic::default_value(self.pool, value_kind)
+        });
+        Ok(vec![key, value])
+    }
+
+    fn scalar(&mut self, kind: &Kind) -> Result<Value, TextError> {
+        let token = self.next()?;
+        let invalid = |p: &Self| p.error(token, "invalid value");
+        let int = || parse_int(token.text);
+        Ok(match kind {
+            Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => Value::I32(
+                int()
+                    .and_then(|x| i32::try_from(x).ok())
+                    .ok_or_else(|| invalid(self))?,
+            ),
+            Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => Value::I64(
+                int()
+                    .and_then(|x| i64::try_from(x).ok())
+                    .ok_or_else(|| invalid(self))?,
+            ),
+            Kind::Uint32 | Kind::Fixed32 => Value::U32(
+                int()
+                    .and_then(|x| u32::try_from(x).ok())
+                    .ok_or_else(|| invalid(self))?,
+            ),
+            Kind::Uint64 | Kind::Fixed64 => Value::U64(
+                int()
+                    .and_then(|x| u64::try_from(x).ok())
+                    .ok_or_else(|| invalid(self))?,
+            ),
+            Kind::Float => Value::F32(
+                parse_float(token.text).ok_or_else(|| invalid(self))? as f32,
+            ),
+            Kind::Double => Value::F64(
+                parse_float(token.text).ok_or_else(|| invalid(self))?,
+            ),
+            Kind::Bool => match token.text {
+                "true" | "True" | "t" | "1" => Value::Bool(true),
+                "false" | "False" | "f" | "0" => Value::Bool(false),
+                _ => return Err(invalid(self)),
+            },
+            Kind::String => {
+                let bytes = self.strings(token)?;
+                let string = String::from_utf8(bytes)
+                    .map_err(|_| self.error(token, "invalid UTF-8"))?;
+                Value::String(string)
+            }
+            Kind::Bytes => Value::Bytes(self.strings(token)?),
+            Kind::Enum(name) => {
+                let desc = self.pool.enumeration(name);
+                match token.kind {
+                    TokenKind::Ident => {
+                        let number = desc
+                            .and_then(|e| e.number_of(token.text))
+                            .ok_or_else(|| invalid(self))?;
+                        Value::Enum(number, Some(token.text.to_string()))
+                    }
+                    _ => {
+                        let number = int()
+                            .and_then(|x| i32::try_from(x).ok())
+                            .ok_or_else(|| invalid(self))?;
+                        let name = desc
+                            .and_then(|e| e.name_of(number))
+                            .map(String::from);
+                        Value::Enum(number, name)
+                    }
+                }
+            }
+            Kind::Message(_) | Kind::Map(..) => return Err(invalid(self)),
+        })
+    }
+
+    // One or more adjacent string literals, concatenated
+    fn strings(&mut self, first: Token) -> Result<Vec<u8>, TextError> {
+        if first.kind != TokenKind::Str {
+            return Err(self.error(first, "expected string"));
+        }
+        let mut res = unescape(&first.text[1..first.text.len() - 1])
+            .ok_or_else(|| self.error(first, "invalid escape"))?;
+        while let Some(token) = self.peek().filter(|t| t.kind == TokenKind::Str)
+        {
+            self.pos += 1;
+            let bytes = unescape(&token.text[1..token.text.len() - 1])
+                .ok_or_else(|| self.error(token, "invalid escape"))?;
+            res.extend(bytes);
+        }
+        Ok(res)
+    }
+}
+
+fn entry(value: Value) -> Option<(Value, Value)> {
+    match value {
+        Value::List(mut entry) if entry.len() == 2 => {
+            let value = entry.pop()?;
+            let key = entry.pop()?;
+            Some((key, value))
+        }
+        _ => None,
+    }
+}
+
+// Decimal, hex (`0x`) or octal (leading `0`) integer, with optional minus
+fn parse_int(text: &str) -> Option<i128> {
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(x) => (true, x),
+        None => (false, text),
+    };
+    let x = if let Some(hex) = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        i128::from_str_radix(hex, 16).ok()?
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        i128::from_str_radix(&digits[1..], 8).ok()?
+    } else {
+        digits.parse().ok()?
+    };
+    Some(if negative { -x } else { x })
+}
+
+fn parse_float(text: &str) -> Option<f64> {
+    let lower = text.to_ascii_lowercase();
+    let (negative, rest) = match lower.strip_prefix('-') {
+        Some(x) => (true, x),
+        None => (false, lower.as_str()),
+    };
+    let x = match rest {
+        "inf" | "infinity" => f64::INFINITY,
+        "nan" => f64::NAN,
+        // `1.5f` suffix, but not in hex
+        x if !x.starts_with("0x") => {
+            x.strip_suffix('f').unwrap_or(x).parse().ok()?
+        }
+        _ => return None,
+    };
+    Some(if negative { -x } else { x })
+}
+
+// C-style escapes, as written by `escape_bytes`, plus `\xhh`
+fn unescape(text: &str) -> Option<Vec<u8>> {
+    let mut res = vec![];
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'\\' {
+            res.push(bytes[i]);
+            i += 1;
+            continue;
+        }
+        i += 1;
+        let c = *bytes.get(i)?;
+        i += 1;
+        match c {
+            b'n' => res.push(b'\n'),
+            b'r' => res.push(b'\r'),
+            b't' => res.push(b'\t'),
+            b'a' => res.push(0x07),
+            b'b' => res.push(0x08),
+            b'f' => res.push(0x0c),
+            b'v' => res.push(0x0b),
+            b'\\' | b'\'' | b'"' | b'?' => res.push(c),
+            b'0'..=b'7' => {
+                let mut x = (c - b'0') as u32;
+                for _ in 0..2 {
+                    match bytes.get(i) {
+                        Some(d @ b'0'..=b'7') => {
+                            x = x * 8 + (d - b'0') as u32;
+                            i += 1;
+                        }
+                        _ => break,
+                    }
+                }
+                res.push(u8::try_from(x).ok()?);
+            }
+            b'x' | b'X' => {
+                let mut x = 0u32;
+                let start = i;
+                while i < bytes.len() && i - start < 2 {
+                    match (bytes[i] as char).to_digit(16) {
+                        Some(d) => x = x * 16 + d,
+                        None => break,
+                    }
+                    i += 1;
+                }
+                if i == start {
+                    return None;
+                }
+                res.push(x as u8);
+            }
+            _ => return None,
+        }
+    }
+    Some(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_format() {
+        let source = r#"
+            syntax = "proto3";
+            package test;
+            message Foo {
+                enum Kind { A = 0; B = 1; }
+                int32 id = 1;
+                repeated double values = 2;
+                Kind kind = 3;
+                bytes data = 4;
+                map<string, Foo> children = 5;
+                repeated Foo list = 6;
+                string name = 7;
+            }
+        "#;
+        let pool = Pool::from_source(source).unwrap();
+        let text = r#"
+            # comment
+            id: -0x10
+            values: [1.5, -inf, 1e3]
+            kind: B
+            data: "\001\x02" 'three'
+            children { key: "a" value { kind: 1 } }
+            children < key: "b", value: <> >
+            list: [{ id: 1 }, { id: 2 }];
+            name: "zażółć \"quoted\""
+        "#;
+        let value = pool.parse_text("test.Foo", text).unwrap();
+        assert_eq!(value.get("id"), Some(&Value::I32(-16)));
+        assert_eq!(
+            value.get("data"),
+            Some(&Value::Bytes(b"\x01\x02three".to_vec()))
+        );
+
+        let printed = value.to_string();
+        assert!(printed.contains("id: -16\n"));
+        assert!(printed.contains("values: -inf\n"));
+        assert!(printed.contains("kind: B\n"));
+        assert!(printed.contains("data: \"\\001\\002three\"\n"));
+        assert!(printed.contains("children {\n  key: \"a\"\n  value {\n"));
+        assert!(printed.contains("name: \"zażółć \\\"quoted\\\"\"\n"));
+        let reparsed = pool.parse_text("test.Foo", &printed).unwrap();
+        assert_eq!(reparsed, value);
+
+        let bad =
+            ["id: 1 id: 2", "id 1", "nope: 1", "kind: C", "data: \"\\q\""];
+        for text in &bad {
+            assert!(pool.parse_text("test.Foo", text).is_err(), "{}", text);
+        }
+    }
+}
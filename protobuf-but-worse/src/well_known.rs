@@ -0,0 +1,503 @@
+// Well-known types of `google.protobuf` package
+//
+// Codegen uses these instead of generating them, and `descriptor::Pool`
+// knows their definitions, so .proto files can refer to them without
+// having the files they're defined in. Wrappers (`Int32Value`...)
+// are represented as `Option` of their value.
+
+use std::{
+    convert::TryFrom,
+    io::{Read, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::encoding::*;
+
+/// Definitions of well-known types, added to every [`crate::descriptor::Pool`]
+pub const SOURCE: &str = r#"
+syntax = "proto3";
+package google.protobuf;
+
+message Timestamp {
+  int64 seconds = 1;
+  int32 nanos = 2;
+}
+
+message Duration {
+  int64 seconds = 1;
+  int32 nanos = 2;
+}
+
+message Any {
+  string type_url = 1;
+  bytes value = 2;
+}
+
+message Empty {}
+
+message DoubleValue { double value = 1; }
+message FloatValue { float value = 1; }
+message Int64Value { int64 value = 1; }
+message UInt64Value { uint64 value = 1; }
+message Int32Value { int32 value = 1; }
+message UInt32Value { uint32 value = 1; }
+message BoolValue { bool value = 1; }
+message StringValue { string value = 1; }
+message BytesValue { bytes value = 1; }
+"#;
+
+/// Well-known message types, with Rust types codegen uses for them.
+/// Wrappers are used as plain values, as their types are `Option`s.
+pub(crate) const TYPES: &[(&str, &str, bool)] = &[
+    (
+        "Timestamp",
+        "protobuf_but_worse::well_known::Timestamp",
+        false,
+    ),
+    (
+        "Duration",
+        "protobuf_but_worse::well_known::Duration",
+        false,
+    ),
+    ("Any", "protobuf_but_worse::well_known::Any", false),
+    ("Empty", "protobuf_but_worse::well_known::Empty", false),
+    ("DoubleValue", "Option<f64>", true),
+    ("FloatValue", "Option<f32>", true),
+    ("Int64Value", "Option<i64>", true),
+    ("UInt64Value", "Option<u64>", true),
+    ("Int32Value", "Option<i32>", true),
+    ("UInt32Value", "Option<u32>", true),
+    ("BoolValue", "Option<bool>", true),
+    ("StringValue", "Option<String>", true),
+    ("BytesValue", "Option<Vec<u8>>", true),
+];
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+#[derive(Debug, thiserror::Error)]
+#[error("Value out of range of std type")]
+pub struct OutOfRange;
+
+/// Point in time, as seconds and nanoseconds since Unix epoch.
+/// `nanos` is never negative, even before the epoch.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timestamp {
+    pub seconds: i64,
+    pub nanos: i32,
+}
+
+/// Signed span of time, `seconds` and `nanos` have the same sign
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Duration {
+    pub seconds: i64,
+    pub nanos: i32,
+}
+
+impl Timestamp {
+    pub fn now() -> Self {
+        SystemTime::now().into()
+    }
+
+    fn total_nanos(&self) -> i128 {
+        self.seconds as i128 * NANOS_PER_SECOND + self.nanos as i128
+    }
+}
+
+impl Duration {
+    fn total_nanos(&self) -> i128 {
+        self.seconds as i128 * NANOS_PER_SECOND + self.nanos as i128
+    }
+}
+
+fn std_duration(nanos: u128) -> Result<std::time::Duration, OutOfRange> {
+    let seconds = u64::try_from(nanos / NANOS_PER_SECOND as u128)
+        .map_err(|_| OutOfRange)?;
+    let nanos = (nanos % NANOS_PER_SECOND as u128) as u32;
+    Ok(std::time::Duration::new(seconds, nanos))
+}
+
+impl From<SystemTime> for Timestamp {
+    fn from(time: SystemTime) -> Self {
+        let nanos = match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => since.as_nanos() as i128,
+            Err(e) => -(e.duration().as_nanos() as i128),
+        };
+        Self {
+            seconds: nanos.div_euclid(NANOS_PER_SECOND) as i64,
+            nanos: nanos.rem_euclid(NANOS_PER_SECOND) as i32,
+        }
+    }
+}
+
+impl TryFrom<Timestamp> for SystemTime {
+    type Error = OutOfRange;
+
+    fn try_from(timestamp: Timestamp) -> Result<Self, OutOfRange> {
+        let nanos = timestamp.total_nanos();
+        let since = std_duration(nanos.unsigned_abs())?;
+        if nanos >= 0 {
+            UNIX_EPOCH.checked_add(since)
+        } else {
+            UNIX_EPOCH.checked_sub(since)
+        }
+        .ok_or(OutOfRange)
+    }
+}
+
+impl TryFrom<std::time::Duration> for Duration {
+    type Error = OutOfRange;
+
+    fn try_from(duration: std::time::Duration) -> Result<Self, OutOfRange> {
+        Ok(Self {
+            seconds: i64::try_from(duration.as_secs())
+                .map_err(|_| OutOfRange)?,
+            nanos: duration.subsec_nanos() as i32,
+        })
+    }
+}
+
+/// Fails for negative durations
+impl TryFrom<Duration> for std::time::Duration {
+    type Error = OutOfRange;
+
+    fn try_from(duration: Duration) -> Result<Self, OutOfRange> {
+        let nanos =
+            u128::try_from(duration.total_nanos()).map_err(|_| OutOfRange)?;
+        std_duration(nanos)
+    }
+}
+
+// Timestamp and Duration share the same fields
+macro_rules! impl_seconds_nanos {
+    ($t:ty, $name:literal) => {
+        impl Encode for $t {
+            fn size(&self) -> u32 {
+                let mut size = 0;
+                if self.seconds != 0 {
+                    size += key_size(1) + Varint(self.seconds).size();
+                }
+                if self.nanos != 0 {
+                    size += key_size(2) + Varint(self.nanos).size();
+                }
+                size
+            }
+
+            fn encode<W: Write>(&self, mut w: W) -> Result<(), EncodingError> {
+                if self.seconds != 0 {
+                    encode_field(&mut w, 1, 0, &Varint(self.seconds))?;
+                }
+                if self.nanos != 0 {
+                    encode_field(&mut w, 2, 0, &Varint(self.nanos))?;
+                }
+                Ok(())
+            }
+        }
+
+        impl Decode for $t {
+            fn decode<R: Read>(mut r: R) -> Result<Self, EncodingError> {
+                let mut res = Self::default();
+                while let Some((number, wire_type)) = decode_key(&mut r)? {
+                    match (number, wire_type) {
+                        (1, 0) => {
+                            res.seconds = <Varint<i64>>::decode(&mut r)?.0
+                        }
+                        (2, 0) => res.nanos = <Varint<i32>>::decode(&mut r)?.0,
+                        _ => skip_field(&mut r, wire_type)?,
+                    }
+                }
+                Ok(res)
+            }
+
+            fn decode_as_field(
+                r: &mut dyn Read,
+            ) -> Result<Self, EncodingError> {
+                Self::decode_with_len(r)
+            }
+        }
+
+        impl MessageName for $t {
+            const FULL_NAME: &'static str = $name;
+        }
+    };
+}
+
+impl_seconds_nanos!(Timestamp, "google.protobuf.Timestamp");
+impl_seconds_nanos!(Duration, "google.protobuf.Duration");
+
+const TYPE_URL_PREFIX: &str = "type.googleapis.com/";
+
+/// Message of any type, along with URL identifying the type
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Any {
+    pub type_url: String,
+    pub value: Vec<u8>,
+}
+
+impl Any {
+    /// Encodes message, with `type.googleapis.com/<full name>` URL
+    pub fn pack<T: Encode + MessageName>(
+        message: &T,
+    ) -> Result<Self, EncodingError> {
+        Ok(Self {
+            type_url: format!("{}{}", TYPE_URL_PREFIX, T::FULL_NAME),
+            value: message.encode_to_vec()?,
+        })
+    }
+
+    /// Fully qualified name of message type, last segment of type URL
+    pub fn type_name(&self) -> &str {
+        self.type_url.rsplit('/').next().unwrap_or_default()
+    }
+
+    pub fn is<T: MessageName>(&self) -> bool {
+        self.type_name() == T::FULL_NAME
+    }
+
+    /// Decodes message if it's of type `T`, `None` if it's not
+    pub fn unpack<T: Decode + MessageName>(
+        &self,
+    ) -> Result<Option<T>, EncodingError> {
+        if !self.is::<T>() {
+            return Ok(None);
+        }
+        T::decode(self.value.as_slice()).map(Some)
+    }
+}
+
+impl Encode for Any {
+    fn size(&self) -> u32 {
+        let mut size = 0;
+        if !self.type_url.is_empty() {
+            size += key_size(1) + self.type_url.size();
+        }
+        if !self.value.is_empty() {
+            size += key_size(2) + self.value.size();
+        }
+        size
+    }
+
+    fn encode<W: Write>(&self, mut w: W) -> Result<(), EncodingError> {
+        if !self.type_url.is_empty() {
+            encode_field(&mut w, 1, 2, &self.type_url)?;
+        }
+        if !self.value.is_empty() {
+            encode_field(&mut w, 2, 2, &self.value)?;
+        }
+        Ok(())
+    }
+}
+
+impl Decode for Any {
+    fn decode<R: Read>(mut r: R) -> Result<Self, EncodingError> {
+        let mut res = Self::default();
+        while let Some((number, wire_type)) = decode_key(&mut r)? {
+            match (number, wire_type) {
+                (1, 2) => res.type_url = String::decode_as_field(&mut r)?,
+                (2, 2) => res.value = Vec::decode_as_field(&mut r)?,
+                _ => skip_field(&mut r, wire_type)?,
+            }
+        }
+        Ok(res)
+    }
+
+    fn decode_as_field(r: &mut dyn Read) -> Result<Self, EncodingError> {
+        Self::decode_with_len(r)
+    }
+}
+
+impl MessageName for Any {
+    const FULL_NAME: &'static str = "google.protobuf.Any";
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Empty;
+
+impl Encode for Empty {
+    fn size(&self) -> u32 {
+        0
+    }
+
+    fn encode<W: Write>(&self, _: W) -> Result<(), EncodingError> {
+        Ok(())
+    }
+}
+
+impl Decode for Empty {
+    fn decode<R: Read>(mut r: R) -> Result<Self, EncodingError> {
+        while let Some((_, wire_type)) = decode_key(&mut r)? {
+            skip_field(&mut r, wire_type)?;
+        }
+        Ok(Empty)
+    }
+
+    fn decode_as_field(r: &mut dyn Read) -> Result<Self, EncodingError> {
+        Self::decode_with_len(r)
+    }
+}
+
+impl MessageName for Empty {
+    const FULL_NAME: &'static str = "google.protobuf.Empty";
+}
+
+// Values encoded without `Varint` wrapper
+fn plain<T>(x: &T) -> &T {
+    x
+}
+
+// Wrapper messages with single `value` field, as `Option<T>`.
+// `None` is encoded as empty message, like `Some` of default value.
+macro_rules! impl_wrapper {
+    ($t:ty, $name:literal, $wire_type:literal, $wrapper:ident) => {
+        impl_wrapper!($t, $name, $wire_type, |x: &$t| $wrapper(*x), |r| Ok(
+            <$wrapper<$t>>::decode_as_field(r)?.0
+        ));
+    };
+    ($t:ty, $name:literal, $wire_type:literal) => {
+        impl_wrapper!($t, $name, $wire_type, plain, |r| {
+            <$t>::decode_as_field(r)
+        });
+    };
+    ($t:ty, $name:literal, $wire_type:literal, $wrap:expr, $unwrap:expr) => {
+        impl Encode for Option<$t> {
+            fn size(&self) -> u32 {
+                match self {
+                    Some(x) if *x != <$t>::default() => {
+                        key_size(1) + $wrap(x).size()
+                    }
+                    _ => 0,
+                }
+            }
+
+            fn encode<W: Write>(&self, mut w: W) -> Result<(), EncodingError> {
+                match self {
+                    Some(x) if *x != <$t>::default() => {
+                        encode_key(&mut w, 1, $wire_type)?;
+                        $wrap(x).encode(w)
+                    }
+                    _ => Ok(()),
+                }
+            }
+        }
+
+        impl Decode for Option<$t> {
+            fn decode<R: Read>(mut r: R) -> Result<Self, EncodingError> {
+                let unwrap: fn(&mut dyn Read) -> Result<$t, EncodingError> =
+                    $unwrap;
+                let mut value = <$t>::default();
+                while let Some((number, wire_type)) = decode_key(&mut r)? {
+                    match (number, wire_type) {
+                        (1, $wire_type) => value = unwrap(&mut r)?,
+                        _ => skip_field(&mut r, wire_type)?,
+                    }
+                }
+                Ok(Some(value))
+            }
+
+            fn decode_as_field(
+                r: &mut dyn Read,
+            ) -> Result<Self, EncodingError> {
+                Self::decode_with_len(r)
+            }
+        }
+
+        impl MessageName for Option<$t> {
+            const FULL_NAME: &'static str = $name;
+        }
+    };
+}
+
+impl_wrapper!(f64, "google.protobuf.DoubleValue", 1);
+impl_wrapper!(f32, "google.protobuf.FloatValue", 5);
+impl_wrapper!(i64, "google.protobuf.Int64Value", 0, Varint);
+impl_wrapper!(u64, "google.protobuf.UInt64Value", 0, Varint);
+impl_wrapper!(i32, "google.protobuf.Int32Value", 0, Varint);
+impl_wrapper!(u32, "google.protobuf.UInt32Value", 0, Varint);
+impl_wrapper!(bool, "google.protobuf.BoolValue", 0);
+impl_wrapper!(String, "google.protobuf.StringValue", 2);
+impl_wrapper!(Vec<u8>, "google.protobuf.BytesValue", 2);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        let before_epoch = UNIX_EPOCH - std::time::Duration::from_millis(1500);
+        let timestamp = Timestamp::from(before_epoch);
+        assert_eq!(
+            timestamp,
+            Timestamp {
+                seconds: -2,
+                nanos: 500_000_000
+            }
+        );
+        assert_eq!(SystemTime::try_from(timestamp).unwrap(), before_epoch);
+
+        let duration = std::time::Duration::from_millis(1500);
+        let proto = Duration::try_from(duration).unwrap();
+        assert_eq!(proto.nanos, 500_000_000);
+        assert_eq!(std::time::Duration::try_from(proto).unwrap(), duration);
+        let negative = Duration {
+            seconds: -1,
+            nanos: -5,
+        };
+        assert!(std::time::Duration::try_from(negative).is_err());
+    }
+
+    #[test]
+    fn any_and_wrappers() {
+        let any = Any::pack(&Some(5i32)).unwrap();
+        assert_eq!(
+            any.type_url,
+            "type.googleapis.com/google.protobuf.Int32Value"
+        );
+        assert_eq!(any.value, [8, 5]);
+        assert_eq!(any.unpack::<Option<i32>>().unwrap(), Some(Some(5)));
+        assert_eq!(any.unpack::<Timestamp>().unwrap(), None);
+
+        let bytes = Any::pack(&any).unwrap().encode_to_vec().unwrap();
+        let outer = Any::decode(bytes.as_slice()).unwrap();
+        assert_eq!(outer.unpack::<Any>().unwrap(), Some(any));
+
+        let empty: Option<String> = Some(String::new());
+        assert!(empty.encode_to_vec().unwrap().is_empty());
+        let text = Some("a".to_string()).encode_to_vec().unwrap();
+        assert_eq!(
+            <Option<String>>::decode(text.as_slice()).unwrap(),
+            Some("a".into())
+        );
+    }
+
+    #[test]
+    fn negative_duration() {
+        let duration = Duration {
+            seconds: -1,
+            nanos: -500_000_000,
+        };
+        let bytes = duration.encode_to_vec().unwrap();
+        let mut expected = vec![8, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+        expected.extend([0xff, 0xff, 0xff, 1]);
+        expected.extend([16, 0x80, 0xb6, 0xca, 0x91, 0xfe, 0xff, 0xff]);
+        expected.extend([0xff, 0xff, 1]);
+        assert_eq!(bytes, expected);
+        assert_eq!(Duration::decode(bytes.as_slice()).unwrap(), duration);
+
+        let pool = crate::descriptor::Pool::new();
+        let text = "seconds: -1\nnanos: -500000000\n";
+        assert_eq!(crate::text::to_string(&pool, &duration).unwrap(), text);
+        let parsed: Duration = crate::text::from_str(&pool, text).unwrap();
+        assert_eq!(parsed, duration);
+
+        #[cfg(feature = "json")]
+        {
+            let json = crate::json::to_string(&pool, &duration).unwrap();
+            assert_eq!(json, "\"-1.500s\"");
+            let parsed: Duration = crate::json::from_str(&pool, &json).unwrap();
+            assert_eq!(parsed, duration);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use std::path::Path;
+
+use protobuf_but_worse::{Config, Layout};
+
+// Tree layout is compiled the way build scripts use it, through `include!`
+#[test]
+fn module_tree() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("module_tree");
+    let out = dir.join("out");
+    std::fs::create_dir_all(&out).unwrap();
+    let protos = [
+        (
+            "inner.proto",
+            "package a.b; message Inner { string name = 1; }",
+        ),
+        (
+            "outer.proto",
+            "package a; message Outer { b.Inner inner = 1; }",
+        ),
+        ("root.proto", "message Root { uint32 id = 1; }"),
+    ];
+    let mut files = vec![];
+    for (name, source) in &protos {
+        let source = format!("syntax = \"proto3\"; {}", source);
+        std::fs::write(dir.join(name), source).unwrap();
+        files.push(dir.join(name));
+    }
+    Config::new()
+        .out_dir(&out)
+        .layout(Layout::ModuleTree)
+        .compile(&files)
+        .unwrap();
+    assert!(out.join("a").join("mod.rs").exists());
+    assert!(out.join("a").join("b.rs").exists());
+
+    let main = format!(
+        r#"
+        include!({:?});
+
+        use protobuf_but_worse::encoding::{{Decode, Encode}};
+
+        fn main() {{
+            let inner = a::b::Inner::default();
+            let outer = a::Outer {{ inner: Some(inner) }};
+            let bytes = outer.encode_to_vec().unwrap();
+            assert_eq!(a::Outer::decode(bytes.as_slice()).unwrap(), outer);
+            let _ = Root {{ id: Some(1) }};
+        }}
+        "#,
+        out.join("mod.rs")
+    );
+    let main_file = dir.join("main.rs");
+    std::fs::write(&main_file, main).unwrap();
+    trybuild::TestCases::new().pass(&main_file);
+}
@@ -0,0 +1,127 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+};
+
+// Runs `pbw` in `dir`, with `input` on stdin
+fn pbw(dir: &Path, args: &[&str], input: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_pbw"))
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+// Stdout of successful run
+fn run(dir: &Path, args: &[&str], input: &[u8]) -> Vec<u8> {
+    let output = pbw(dir, args, input);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{:?}: {}", args, stderr);
+    output.stdout
+}
+
+fn setup(name: &str) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    std::fs::create_dir_all(&dir).unwrap();
+    let protos = [
+        ("b.proto", "message B { uint32 id = 1; }"),
+        (
+            "a.proto",
+            "import \"b.proto\"; \
+             import \"google/protobuf/timestamp.proto\"; \
+             message A { string name = 1; B b = 2; repeated int32 ids = 3; \
+             google.protobuf.Timestamp at = 4; }",
+        ),
+    ];
+    for (file, source) in &protos {
+        let source = format!("syntax = \"proto3\"; package test; {}", source);
+        std::fs::write(dir.join(file), source).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn generate() {
+    let dir = setup("pbw_generate");
+    run(&dir, &["generate", "-o", "out", "a.proto", "b.proto"], b"");
+    let a = std::fs::read_to_string(dir.join("out/a.rs")).unwrap();
+    assert!(a.contains("pub struct A"));
+    assert!(dir.join("out/b.rs").exists());
+
+    run(
+        &dir,
+        &["generate", "-o", "all.rs", "a.proto", "b.proto"],
+        b"",
+    );
+    let all = std::fs::read_to_string(dir.join("all.rs")).unwrap();
+    assert!(all.contains("pub mod test"));
+    assert!(all.contains("pub struct A") && all.contains("pub struct B"));
+}
+
+#[test]
+fn encode_decode() {
+    let dir = setup("pbw_encode_decode");
+    let text = "name: \"x\"\nb {\n  id: 5\n}\nids: -1\nids: 2\n";
+    let args = ["encode", "a.proto", "test.A"];
+    let bytes = run(&dir, &args, text.as_bytes());
+    assert_eq!(bytes[..7], [10, 1, b'x', 18, 2, 8, 5]);
+    let args = ["decode", "a.proto", "test.A"];
+    assert_eq!(run(&dir, &args, &bytes), text.as_bytes());
+
+    // Input from file, and imports relative to it
+    std::fs::write(dir.join("a.bin"), &bytes).unwrap();
+    let args = ["decode", "a.proto", "test.A", "a.bin"];
+    assert_eq!(run(&dir, &args, b""), text.as_bytes());
+    let parent = dir.parent().unwrap();
+    let args = ["decode", "pbw_encode_decode/a.proto", "test.A"];
+    assert_eq!(run(parent, &args, &bytes), text.as_bytes());
+
+    let raw = run(&dir, &["decode", "--raw"], &bytes);
+    let raw = String::from_utf8(raw).unwrap();
+    assert!(raw.starts_with("1: \"x\"\n2 {\n  1: 5\n}\n3: "), "{}", raw);
+
+    // Delimited messages, one after another
+    let args = ["encode", "-d", "a.proto", "test.A"];
+    let mut bytes = run(&dir, &args, text.as_bytes());
+    bytes.extend(run(&dir, &args, b"name: \"y\""));
+    let args = ["decode", "-d", "a.proto", "test.A"];
+    let decoded = run(&dir, &args, &bytes);
+    let expected = format!("# Message 0\n{}# Message 1\nname: \"y\"\n", text);
+    assert_eq!(String::from_utf8(decoded).unwrap(), expected);
+    let raw = run(&dir, &["decode", "--raw", "-d"], &bytes);
+    assert!(String::from_utf8(raw)
+        .unwrap()
+        .contains("# Message 1\n1: \"y\""));
+    bytes.pop();
+    let output = pbw(&dir, &args, &bytes);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn usage() {
+    let dir = setup("pbw_usage");
+    let cases: &[&[&str]] = &[
+        &[],
+        &["help"],
+        &["generate"],
+        &["decode", "a.proto"],
+        &["encode", "--raw", "a.proto", "test.A"],
+    ];
+    for args in cases {
+        let output = pbw(&dir, args, b"");
+        assert_eq!(output.status.code(), Some(1), "{:?}", args);
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.starts_with("Usage:"), "{:?}: {}", args, stderr);
+    }
+    let output = pbw(&dir, &["decode", "-x"], b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(output.stderr, b"Unknown option -x\n");
+    let output = pbw(&dir, &["decode", "a.proto", "test.Nope"], b"");
+    assert_eq!(output.status.code(), Some(1));
+}
@@ -0,0 +1,45 @@
+use std::path::Path;
+
+use protobuf_but_worse::{Config, Layout};
+
+#[test]
+fn per_file() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("per_file");
+    let out = dir.join("out");
+    std::fs::create_dir_all(dir.join("other")).unwrap();
+    std::fs::create_dir_all(&out).unwrap();
+    let protos = [
+        ("b.proto", "package other; message B { uint32 id = 1; }"),
+        ("c.proto", "package this; message C { other.B b = 1; }"),
+        ("d.proto", "package other; message D { B b = 1; }"),
+        ("other/d.proto", "package other; message E { }"),
+    ];
+    for (name, source) in &protos {
+        let source = format!("syntax = \"proto3\"; {}", source);
+        std::fs::write(dir.join(name), source).unwrap();
+    }
+    let compile = |layout: Layout, files: &[&str]| {
+        let files: Vec<_> = files.iter().map(|x| dir.join(x)).collect();
+        Config::new().out_dir(&out).layout(layout).compile(&files)
+    };
+
+    // Same package works, as files are included next to each other
+    compile(Layout::PerFile, &["b.proto", "d.proto"]).unwrap();
+    let code = std::fs::read_to_string(out.join("d.rs")).unwrap();
+    assert!(code.contains("pub b : Option < B >"));
+
+    // There's no `other` module to refer to
+    let err = compile(Layout::PerFile, &["b.proto", "c.proto"]).unwrap_err();
+    assert!(err.to_string().contains("Layout::PerFile"), "{}", err);
+    let single = Layout::SingleFile("all.rs".into());
+    compile(single, &["b.proto", "c.proto"]).unwrap();
+
+    let files = ["b.proto", "d.proto", "other/d.proto"];
+    let err = compile(Layout::PerFile, &files).unwrap_err();
+    assert!(
+        err.to_string().contains("would be generated to d.rs"),
+        "{}",
+        err
+    );
+    compile(Layout::ModuleTree, &files).unwrap();
+}
@@ -55,10 +55,7 @@ fn run() -> Result<(), Box<dyn Error>> {
     println!("TCP connected.");
     let status = krpc.get_status();
     match status {
-        Ok(status) => println!(
-            "Krpc version: {}",
-            status.version.as_deref().unwrap_or("<missing>")
-        ),
+        Ok(status) => println!("Krpc version: {}", status.version),
         Err(e) => println!("Error getting status: {:?}", e),
     }
 